@@ -105,15 +105,21 @@ impl AudioMixer {
             };
 
             // 볼륨 + 페이드 적용 + 합산
-            let volume = clip.volume;
+            // Volume 키프레임: 구간 시작/끝 볼륨 사이를 샘플 단위 선형 램프 (지퍼 노이즈 방지)
             let fade = calc_fade_volume(clip, timestamp_ms);
-            let effective_volume = volume * fade;
+            let gain_start = clip.volume_at(timestamp_ms as f64) * fade;
+            let gain_end = clip.volume_at(timestamp_ms as f64 + duration_ms) * fade;
+            let total_frames = (mixed.len() / OUTPUT_CHANNELS as usize).max(1) as f32;
+            let gain_at = |i: usize| -> f32 {
+                let frame = (i / OUTPUT_CHANNELS as usize) as f32;
+                gain_start + (gain_end - gain_start) * (frame / total_frames)
+            };
 
             if clip.speed == 1.0 {
                 // 속도 1.0: 디코딩된 샘플 직접 사용 (가장 효율적)
                 let len = mixed.len().min(samples.len());
                 for i in 0..len {
-                    mixed[i] += samples[i] * effective_volume;
+                    mixed[i] += samples[i] * gain_at(i);
                 }
             } else {
                 // 속도 변경: 디코딩된 샘플을 리샘플링하여 출력 크기에 맞춤
@@ -131,9 +137,9 @@ impl AudioMixer {
                     if src_idx + 1 < src_len {
                         let sample = samples[src_idx] * (1.0 - frac as f32)
                             + samples[src_idx + 1] * frac as f32;
-                        mixed[i] += sample * effective_volume;
+                        mixed[i] += sample * gain_at(i);
                     } else if src_idx < src_len {
-                        mixed[i] += samples[src_idx] * effective_volume;
+                        mixed[i] += samples[src_idx] * gain_at(i);
                     }
                 }
             }
@@ -0,0 +1,306 @@
+// 출력 컨테이너 (MP4 / MOV / MKV / WebM / 이미지 시퀀스 / 오디오 전용 WAV·FLAC·MP3·M4A)
+// 확장자 또는 명시 선택 → FFmpeg 먹서 이름 + 코덱 호환성 검증
+// write_header에서 실패하기 전에 Export 시작 단계에서 조합 오류를 잡음
+
+use crate::encoding::encoder::{AudioCodec, SubtitleCodec, VideoCodec};
+use std::path::Path;
+
+/// 출력 컨테이너 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Container {
+    Auto = 0,   // 출력 파일 확장자 기준
+    Mp4 = 1,
+    Mov = 2,
+    Mkv = 3,
+    WebM = 4,
+    ImageSequence = 5,  // 프레임마다 이미지 파일 1개 (FFmpeg image2 먹서)
+    // 오디오 전용 (비디오 렌더링 없이 믹스만 Export)
+    Wav = 6,
+    Flac = 7,
+    Mp3 = 8,
+    M4a = 9,
+}
+
+impl Container {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => Container::Mp4,
+            2 => Container::Mov,
+            3 => Container::Mkv,
+            4 => Container::WebM,
+            5 => Container::ImageSequence,
+            6 => Container::Wav,
+            7 => Container::Flac,
+            8 => Container::Mp3,
+            9 => Container::M4a,
+            _ => Container::Auto,
+        }
+    }
+
+    /// 확장자 → 컨테이너 (알 수 없으면 MP4)
+    pub fn from_path(output_path: &str) -> Self {
+        let ext = Path::new(output_path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "mov" | "qt" => Container::Mov,
+            "mkv" | "mka" => Container::Mkv,
+            "webm" => Container::WebM,
+            "png" | "jpg" | "jpeg" | "tif" | "tiff" => Container::ImageSequence,
+            "wav" => Container::Wav,
+            "flac" => Container::Flac,
+            "mp3" => Container::Mp3,
+            "m4a" => Container::M4a,
+            _ => Container::Mp4,
+        }
+    }
+
+    /// Auto를 출력 파일 확장자 기준으로 확정
+    pub fn resolve(self, output_path: &str) -> Self {
+        if self == Container::Auto {
+            Container::from_path(output_path)
+        } else {
+            self
+        }
+    }
+
+    /// FFmpeg 먹서 이름 (확장자와 무관하게 이 포맷으로 기록)
+    pub fn muxer_name(self) -> &'static str {
+        match self {
+            Container::Mov => "mov",
+            Container::Mkv => "matroska",
+            Container::WebM => "webm",
+            Container::ImageSequence => "image2",
+            Container::Wav => "wav",
+            Container::Flac => "flac",
+            Container::Mp3 => "mp3",
+            Container::M4a => "ipod",
+            Container::Mp4 | Container::Auto => "mp4",
+        }
+    }
+
+    /// 오디오/자막 스트림을 담을 수 있는지 (이미지 시퀀스는 비디오만)
+    pub fn has_audio(self) -> bool {
+        self != Container::ImageSequence
+    }
+
+    /// 오디오 전용 컨테이너 (Renderer 없이 AudioMixer 출력만 인코딩)
+    pub fn is_audio_only(self) -> bool {
+        matches!(self, Container::Wav | Container::Flac | Container::Mp3 | Container::M4a)
+    }
+
+    pub fn supports_video(self, codec: VideoCodec) -> bool {
+        match self {
+            Container::Mp4 | Container::Auto => {
+                matches!(codec, VideoCodec::H264 | VideoCodec::Hevc | VideoCodec::Av1 | VideoCodec::Vp9)
+            }
+            Container::Mov => matches!(codec, VideoCodec::H264 | VideoCodec::Hevc | VideoCodec::ProRes | VideoCodec::Dnxhr),
+            Container::Mkv => true,
+            Container::WebM => matches!(codec, VideoCodec::Vp9 | VideoCodec::Av1),
+            Container::ImageSequence => codec.is_image(),
+            Container::Wav | Container::Flac | Container::Mp3 | Container::M4a => false,
+        }
+    }
+
+    pub fn supports_audio(self, codec: AudioCodec) -> bool {
+        match self {
+            Container::Mp4 | Container::Auto => matches!(codec, AudioCodec::Aac | AudioCodec::Opus | AudioCodec::Mp3),
+            Container::Mov => matches!(codec, AudioCodec::Aac | AudioCodec::Mp3 | AudioCodec::Pcm16 | AudioCodec::Pcm24),
+            Container::Mkv => true,
+            Container::WebM => matches!(codec, AudioCodec::Opus),
+            Container::ImageSequence => false,
+            Container::Wav => matches!(codec, AudioCodec::Pcm16 | AudioCodec::Pcm24),
+            Container::Flac => codec == AudioCodec::Flac,
+            Container::Mp3 => codec == AudioCodec::Mp3,
+            Container::M4a => codec == AudioCodec::Aac,
+        }
+    }
+
+    pub fn supports_subtitle(self, codec: SubtitleCodec) -> bool {
+        match self {
+            Container::Mp4 | Container::Mov | Container::Auto => codec == SubtitleCodec::MovText,
+            Container::Mkv => matches!(codec, SubtitleCodec::Subrip | SubtitleCodec::Ass | SubtitleCodec::WebVtt),
+            Container::WebM => codec == SubtitleCodec::WebVtt,
+            Container::ImageSequence | Container::Wav | Container::Flac | Container::Mp3 | Container::M4a => false,
+        }
+    }
+
+    /// 오디오 코덱 Auto일 때 사용할 코덱
+    pub fn default_audio(self) -> AudioCodec {
+        match self {
+            Container::WebM => AudioCodec::Opus,
+            Container::Wav => AudioCodec::Pcm16,
+            Container::Flac => AudioCodec::Flac,
+            Container::Mp3 => AudioCodec::Mp3,
+            _ => AudioCodec::Aac,
+        }
+    }
+
+    /// 자막 코덱 Auto일 때 사용할 코덱
+    pub fn default_subtitle(self) -> SubtitleCodec {
+        match self {
+            Container::Mkv => SubtitleCodec::Subrip,
+            Container::WebM => SubtitleCodec::WebVtt,
+            _ => SubtitleCodec::MovText,
+        }
+    }
+}
+
+/// 확장자 → 이미지 코덱 (이미지 확장자가 아니면 None)
+pub fn image_codec_from_path(output_path: &str) -> Option<VideoCodec> {
+    let ext = Path::new(output_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())?;
+    match ext.as_str() {
+        "png" => Some(VideoCodec::Png),
+        "jpg" | "jpeg" => Some(VideoCodec::Jpeg),
+        "tif" | "tiff" => Some(VideoCodec::Tiff),
+        _ => None,
+    }
+}
+
+/// 이미지 시퀀스 파일명 패턴 (image2 먹서용, 번호 자리 %05d)
+/// "out/frame.png" → "out/frame_%05d.png", 이미 '%'가 있으면 그대로
+pub fn image_sequence_pattern(output_path: &str) -> String {
+    if output_path.contains('%') {
+        return output_path.to_string();
+    }
+    let path = Path::new(output_path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("frame");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}_%05d.{}", stem, ext),
+        None => format!("{}_%05d.png", stem),
+    };
+    path.with_file_name(name).to_string_lossy().to_string()
+}
+
+/// 구간 배치 Export 파일명 ("out/movie.mp4" → "out/movie_01.mp4" 또는 "out/movie_Intro.mp4")
+/// 구간 이름의 파일명 금지 문자는 '_'로 치환, 비어 있으면 번호 사용
+pub fn region_output_path(output_path: &str, index: usize, name: Option<&str>) -> String {
+    let suffix = match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name
+            .chars()
+            .map(|c| if c.is_control() || r#"\/:*?"<>|"#.contains(c) { '_' } else { c })
+            .collect(),
+        None => format!("{:02}", index + 1),
+    };
+    let path = Path::new(output_path);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("export");
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{}_{}.{}", stem, suffix, ext),
+        None => format!("{}_{}", stem, suffix),
+    };
+    path.with_file_name(name).to_string_lossy().to_string()
+}
+
+/// 컨테이너/코덱 조합 검증 (Auto는 모두 확정된 값으로 전달)
+/// video/audio/subtitle: 해당 스트림이 없으면 None
+pub fn validate_codecs(
+    container: Container,
+    video: Option<VideoCodec>,
+    audio: Option<AudioCodec>,
+    subtitle: Option<SubtitleCodec>,
+) -> Result<(), String> {
+    if let Some(video) = video.filter(|v| !container.supports_video(*v)) {
+        return Err(format!("{:?} 컨테이너는 {:?} 비디오를 지원하지 않습니다", container, video));
+    }
+    if video.is_none() && audio.is_none() {
+        return Err("비디오/오디오 스트림이 모두 없습니다".to_string());
+    }
+    if let Some(audio) = audio.filter(|a| !container.supports_audio(*a)) {
+        return Err(format!("{:?} 컨테이너는 {:?} 오디오를 지원하지 않습니다", container, audio));
+    }
+    if let Some(subtitle) = subtitle.filter(|s| !container.supports_subtitle(*s)) {
+        return Err(format!("{:?} 컨테이너는 {:?} 자막을 지원하지 않습니다", container, subtitle));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_from_extension() {
+        assert_eq!(Container::Auto.resolve("out/clip.MKV"), Container::Mkv);
+        assert_eq!(Container::Auto.resolve("clip.webm"), Container::WebM);
+        assert_eq!(Container::Auto.resolve("clip"), Container::Mp4);
+        // 명시 선택은 확장자보다 우선
+        assert_eq!(Container::Mov.resolve("clip.mp4"), Container::Mov);
+    }
+
+    #[test]
+    fn test_validate_codecs() {
+        let webm = Container::WebM;
+        assert!(validate_codecs(webm, Some(VideoCodec::Vp9), Some(webm.default_audio()), Some(webm.default_subtitle())).is_ok());
+        assert!(validate_codecs(webm, Some(VideoCodec::H264), None, None).is_err());
+        assert!(validate_codecs(webm, Some(VideoCodec::Av1), Some(AudioCodec::Aac), None).is_err());
+        assert!(validate_codecs(Container::Mp4, Some(VideoCodec::H264), Some(AudioCodec::Aac), Some(SubtitleCodec::Subrip)).is_err());
+        assert!(validate_codecs(Container::Mkv, Some(VideoCodec::Hevc), Some(AudioCodec::Opus), Some(SubtitleCodec::Ass)).is_ok());
+        // 중간 코덱: MOV/MKV만
+        assert!(validate_codecs(Container::Mov, Some(VideoCodec::ProRes), Some(AudioCodec::Aac), None).is_ok());
+        assert!(validate_codecs(Container::Mp4, Some(VideoCodec::Dnxhr), None, None).is_err());
+        assert!(validate_codecs(Container::Mov, Some(VideoCodec::Ffv1), None, None).is_err());
+        assert!(validate_codecs(Container::Mkv, Some(VideoCodec::Ffv1), None, None).is_ok());
+        // 이미지 시퀀스: 이미지 코덱(PNG/JPEG/TIFF)만, 오디오 스트림 없음
+        assert!(validate_codecs(Container::ImageSequence, Some(VideoCodec::Png), None, None).is_ok());
+        assert!(validate_codecs(Container::ImageSequence, Some(VideoCodec::Png), Some(AudioCodec::Aac), None).is_err());
+        assert!(validate_codecs(Container::ImageSequence, Some(VideoCodec::Tiff), None, None).is_ok());
+        assert!(validate_codecs(Container::Mp4, Some(VideoCodec::Jpeg), None, None).is_err());
+    }
+
+    #[test]
+    fn test_validate_audio_codecs() {
+        // PCM: MOV/MKV만, FLAC: MKV만
+        assert!(validate_codecs(Container::Mov, Some(VideoCodec::ProRes), Some(AudioCodec::Pcm24), None).is_ok());
+        assert!(validate_codecs(Container::Mp4, Some(VideoCodec::H264), Some(AudioCodec::Pcm16), None).is_err());
+        assert!(validate_codecs(Container::Mkv, Some(VideoCodec::H264), Some(AudioCodec::Flac), None).is_ok());
+        assert!(validate_codecs(Container::Mov, Some(VideoCodec::H264), Some(AudioCodec::Flac), None).is_err());
+        assert!(validate_codecs(Container::Mp4, Some(VideoCodec::H264), Some(AudioCodec::Mp3), None).is_ok());
+        assert!(validate_codecs(Container::WebM, Some(VideoCodec::Vp9), Some(AudioCodec::Mp3), None).is_err());
+    }
+
+    #[test]
+    fn test_audio_only_containers() {
+        assert_eq!(Container::Auto.resolve("mix.WAV"), Container::Wav);
+        assert_eq!(Container::Auto.resolve("podcast.m4a"), Container::M4a);
+        assert!(Container::Flac.is_audio_only());
+        assert!(!Container::Mkv.is_audio_only());
+
+        let wav = Container::Wav;
+        assert!(validate_codecs(wav, None, Some(wav.default_audio()), None).is_ok());
+        assert!(validate_codecs(wav, None, Some(AudioCodec::Mp3), None).is_err());
+        assert!(validate_codecs(Container::Mp3, Some(VideoCodec::H264), Some(AudioCodec::Mp3), None).is_err());
+        assert!(validate_codecs(Container::M4a, None, Some(Container::M4a.default_audio()), None).is_ok());
+        // 오디오 제외 + 오디오 전용 컨테이너 → 스트림 없음
+        assert!(validate_codecs(Container::Flac, None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_image_sequence_pattern() {
+        assert_eq!(image_sequence_pattern("frame.png"), "frame_%05d.png");
+        assert_eq!(image_sequence_pattern("out/title"), "out/title_%05d.png");
+        assert_eq!(image_sequence_pattern("out/f_%03d.png"), "out/f_%03d.png");
+        assert_eq!(image_sequence_pattern("shot.JPG"), "shot_%05d.JPG");
+    }
+
+    #[test]
+    fn test_region_output_path() {
+        assert_eq!(region_output_path("out/movie.mp4", 0, None), "out/movie_01.mp4");
+        assert_eq!(region_output_path("out/movie.mp4", 11, Some("  ")), "out/movie_12.mp4");
+        assert_eq!(region_output_path("movie.mov", 2, Some("Scene 3: Intro?")), "movie_Scene 3_ Intro_.mov");
+        assert_eq!(region_output_path("frames/shot.png", 1, Some("예고편")), "frames/shot_예고편.png");
+    }
+
+    #[test]
+    fn test_image_codec_from_path() {
+        assert_eq!(Container::Auto.resolve("frames/shot.tiff"), Container::ImageSequence);
+        assert_eq!(image_codec_from_path("still.JPEG"), Some(VideoCodec::Jpeg));
+        assert_eq!(image_codec_from_path("still.tif"), Some(VideoCodec::Tiff));
+        assert_eq!(image_codec_from_path("clip.mp4"), None);
+    }
+}
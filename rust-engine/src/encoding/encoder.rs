@@ -1,23 +1,34 @@
-// 비디오/오디오 인코더 - FFmpeg 기반 H.264 + AAC 인코딩
-// RGBA 프레임 → YUV420P → H.264 인코딩
-// f32 PCM → FLTP → AAC 인코딩
-// → MP4 먹싱
+// 비디오/오디오 인코더 - FFmpeg 기반 H.264/HEVC/AV1/VP9 + AAC/Opus/MP3/FLAC/PCM 인코딩
+// 편집용 중간 코덱: ProRes (prores_ks) / DNxHR / FFV1 (4:2:2·4:4:4, 10bit)
+// 알파 채널: ProRes 4444 / VP9 (yuva420p) / FFV1 / PNG·TIFF 시퀀스
+// 이미지: PNG / JPEG / TIFF 시퀀스 + 스틸 프레임 1장 (encode_still_image)
+// RGBA 프레임 → YUV(A) (420P/422P/444P, 8/10bit) 또는 RGB(A) → 비디오 인코딩
+// f32 PCM (48kHz 스테레오) → 리샘플/채널 변환 → FLTP/FLT/S32/S16 → 오디오 인코딩
+// 텍스트 자막 cue → mov_text / SRT / ASS / WebVTT 자막 스트림
+// → MP4 / MOV / MKV / WebM 먹싱
 // GPU 하드웨어 가속: NVENC / QSV / AMF 지원
+// 스마트 렌더: 원본 H.264 패킷 복사 (PacketCopy) + 재인코딩 구간 혼합
+// 비트레이트 제어: CRF / CRF 상한 (maxrate·bufsize) / 2-pass 목표 비트레이트 / CBR
+// 병렬 구간 Export: 워커가 인코딩한 구간 파일 패킷을 그대로 이어 붙임 (PacketCopy::open_segment)
 
 use ffmpeg_next as ffmpeg;
 use ffmpeg::format::Pixel;
 use ffmpeg::codec;
-use ffmpeg::software::scaling;
+use ffmpeg::software::{resampling, scaling};
+use crate::encoding::container::Container;
+use crate::encoding::smart_render::{annexb_to_length_prefixed, avcc_parameter_sets, CopySource};
+use crate::subtitle::cue::{SubtitleCue, ass_escape, ass_header, iso639_2};
+use std::path::Path;
 
 /// 인코더 타입 (FFI u32 매핑)
 #[repr(u32)]
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EncoderType {
-    Auto = 0,       // NVENC → QSV → AMF → libx264 순서 시도
-    Software = 1,   // libx264
-    Nvenc = 2,      // h264_nvenc (NVIDIA)
-    Qsv = 3,        // h264_qsv (Intel)
-    Amf = 4,        // h264_amf (AMD)
+    Auto = 0,       // NVENC → QSV → AMF → 소프트웨어 순서 시도
+    Software = 1,   // libx264 / libx265 / libsvtav1 / libvpx-vp9
+    Nvenc = 2,      // *_nvenc (NVIDIA)
+    Qsv = 3,        // *_qsv (Intel)
+    Amf = 4,        // *_amf (AMD)
 }
 
 impl EncoderType {
@@ -32,65 +43,1406 @@ impl EncoderType {
     }
 }
 
+/// 비디오 코덱 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VideoCodec {
+    H264 = 0,
+    Hevc = 1,
+    Av1 = 2,
+    Vp9 = 3,
+    ProRes = 4,     // prores_ks (MOV/MKV)
+    Dnxhr = 5,      // dnxhd (DNxHR 프로필, MOV/MKV)
+    Ffv1 = 6,       // 무손실 (MKV)
+    Png = 7,        // PNG 이미지 시퀀스
+    Jpeg = 8,       // JPEG 이미지 시퀀스 (mjpeg)
+    Tiff = 9,       // TIFF 이미지 시퀀스
+}
+
+impl VideoCodec {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => VideoCodec::Hevc,
+            2 => VideoCodec::Av1,
+            3 => VideoCodec::Vp9,
+            4 => VideoCodec::ProRes,
+            5 => VideoCodec::Dnxhr,
+            6 => VideoCodec::Ffv1,
+            7 => VideoCodec::Png,
+            8 => VideoCodec::Jpeg,
+            9 => VideoCodec::Tiff,
+            _ => VideoCodec::H264,
+        }
+    }
+
+    pub fn codec_id(self) -> codec::Id {
+        match self {
+            VideoCodec::H264 => codec::Id::H264,
+            VideoCodec::Hevc => codec::Id::HEVC,
+            VideoCodec::Av1 => codec::Id::AV1,
+            VideoCodec::Vp9 => codec::Id::VP9,
+            VideoCodec::ProRes => codec::Id::PRORES,
+            VideoCodec::Dnxhr => codec::Id::DNXHD,
+            VideoCodec::Ffv1 => codec::Id::FFV1,
+            VideoCodec::Png => codec::Id::PNG,
+            VideoCodec::Jpeg => codec::Id::MJPEG,
+            VideoCodec::Tiff => codec::Id::TIFF,
+        }
+    }
+
+    /// 소프트웨어 인코더 이름 (앞쪽 우선)
+    pub fn software_encoders(self) -> &'static [&'static str] {
+        match self {
+            VideoCodec::H264 => &["libx264"],
+            VideoCodec::Hevc => &["libx265"],
+            VideoCodec::Av1 => &["libsvtav1", "libaom-av1"],
+            VideoCodec::Vp9 => &["libvpx-vp9"],
+            VideoCodec::ProRes => &["prores_ks", "prores"],
+            VideoCodec::Dnxhr => &["dnxhd"],
+            VideoCodec::Ffv1 => &["ffv1"],
+            VideoCodec::Png => &["png"],
+            VideoCodec::Jpeg => &["mjpeg"],
+            VideoCodec::Tiff => &["tiff"],
+        }
+    }
+
+    /// 하드웨어 인코더 이름 (해당 GPU가 이 코덱을 지원하지 않으면 None)
+    pub fn hardware_encoder(self, encoder_type: EncoderType) -> Option<&'static str> {
+        match (self, encoder_type) {
+            (VideoCodec::H264, EncoderType::Nvenc) => Some("h264_nvenc"),
+            (VideoCodec::H264, EncoderType::Qsv) => Some("h264_qsv"),
+            (VideoCodec::H264, EncoderType::Amf) => Some("h264_amf"),
+            (VideoCodec::Hevc, EncoderType::Nvenc) => Some("hevc_nvenc"),
+            (VideoCodec::Hevc, EncoderType::Qsv) => Some("hevc_qsv"),
+            (VideoCodec::Hevc, EncoderType::Amf) => Some("hevc_amf"),
+            (VideoCodec::Av1, EncoderType::Nvenc) => Some("av1_nvenc"),
+            (VideoCodec::Av1, EncoderType::Qsv) => Some("av1_qsv"),
+            (VideoCodec::Av1, EncoderType::Amf) => Some("av1_amf"),
+            (VideoCodec::Vp9, EncoderType::Qsv) => Some("vp9_qsv"),
+            _ => None,
+        }
+    }
+
+    /// 인트라 전용 코덱 (중간 코덱/이미지, 품질은 CRF 대신 프로필로 결정)
+    pub fn is_intra_only(self) -> bool {
+        matches!(self, VideoCodec::ProRes | VideoCodec::Dnxhr | VideoCodec::Ffv1) || self.is_image()
+    }
+
+    /// 이미지 코덱 (이미지 시퀀스 / 스틸 프레임)
+    pub fn is_image(self) -> bool {
+        matches!(self, VideoCodec::Png | VideoCodec::Jpeg | VideoCodec::Tiff)
+    }
+
+    /// 인코더 입력 픽셀 포맷 (JPEG은 풀 레인지 yuvj 포맷으로 인코딩)
+    fn encoder_pixel(self, pixel_format: OutputPixelFormat) -> Pixel {
+        match (self, pixel_format) {
+            (VideoCodec::Jpeg, OutputPixelFormat::Yuv422p) => Pixel::YUVJ422P,
+            (VideoCodec::Jpeg, OutputPixelFormat::Yuv444p) => Pixel::YUVJ444P,
+            (VideoCodec::Jpeg, _) => Pixel::YUVJ420P,
+            (_, pixel_format) => pixel_format.pixel(),
+        }
+    }
+
+    /// 알파 채널을 담을 수 있는 코덱 (ProRes는 4444 프로필만)
+    pub fn supports_alpha(self, codec_profile: u32) -> bool {
+        match self {
+            VideoCodec::ProRes => ProResProfile::from_u32(codec_profile).pixel_format() == OutputPixelFormat::Yuv444p10,
+            VideoCodec::Vp9 | VideoCodec::Ffv1 | VideoCodec::Png | VideoCodec::Tiff => true,
+            _ => false,
+        }
+    }
+
+    /// 코덱/프로필 기준 출력 픽셀 포맷 확정
+    /// - ProRes/DNxHR: 프로필이 픽셀 포맷을 결정 (명시값이 다르면 에러)
+    /// - FFV1: 기본 4:4:4 10bit, PNG/TIFF: RGB, 그 외 배포용 코덱: 기본 4:2:0 8bit
+    /// - 코덱이 인코딩할 수 없는 조합 (H.264 + RGB, VP9 알파 + 4:4:4 등)은 에러
+    /// - alpha: Auto일 때 알파 포함 포맷 선택 (명시 포맷에 알파가 있어도 알파 Export)
+    pub fn resolve_pixel_format(
+        self,
+        codec_profile: u32,
+        requested: OutputPixelFormat,
+        alpha: bool,
+    ) -> Result<OutputPixelFormat, String> {
+        let alpha = alpha || requested.has_alpha();
+        if alpha && !self.supports_alpha(codec_profile) {
+            return Err(format!("{:?} 코덱(프로필)은 알파 채널을 지원하지 않습니다", self));
+        }
+
+        let required = match self {
+            VideoCodec::ProRes => Some(ProResProfile::from_u32(codec_profile).pixel_format()),
+            VideoCodec::Dnxhr => Some(DnxhrProfile::from_u32(codec_profile).pixel_format()),
+            _ => None,
+        };
+        let required = required.map(|r| if alpha { r.with_alpha() } else { r });
+        let resolved = match (required, requested) {
+            (Some(required), OutputPixelFormat::Auto) => required,
+            (Some(required), requested) if requested == required => required,
+            (Some(required), requested) => return Err(format!(
+                "{:?} 프로필은 {:?} 픽셀 포맷만 지원합니다 (요청={:?})",
+                self, required, requested
+            )),
+            (None, OutputPixelFormat::Auto) => match self {
+                VideoCodec::Ffv1 => OutputPixelFormat::Yuv444p10,
+                VideoCodec::Png | VideoCodec::Tiff => OutputPixelFormat::Rgb24,
+                _ => OutputPixelFormat::Yuv420p,
+            },
+            (None, requested) => requested,
+        };
+        let resolved = if alpha { resolved.with_alpha() } else { resolved };
+        if !self.supports_pixel_format(resolved) {
+            return Err(format!(
+                "{:?} 코덱은 {:?} 픽셀 포맷을 지원하지 않습니다 (요청={:?}, 알파={})",
+                self, resolved, requested, alpha
+            ));
+        }
+        Ok(resolved)
+    }
+
+    /// 코덱이 인코딩할 수 있는 픽셀 포맷 (ProRes/DNxHR은 프로필에서 검증)
+    /// - H.264/HEVC: YUV 8/10bit (알파/RGB 없음), AV1: 4:2:0만 (SVT-AV1/하드웨어 공통)
+    /// - VP9: 알파는 yuva420p만 (libvpx), FFV1: YUV + 알파, PNG/TIFF: RGB, JPEG: 8bit YUV
+    pub fn supports_pixel_format(self, pixel_format: OutputPixelFormat) -> bool {
+        use OutputPixelFormat as F;
+        match self {
+            VideoCodec::H264 | VideoCodec::Hevc => matches!(
+                pixel_format,
+                F::Yuv420p | F::Yuv422p | F::Yuv444p | F::Yuv420p10 | F::Yuv422p10 | F::Yuv444p10
+            ),
+            VideoCodec::Av1 => matches!(pixel_format, F::Yuv420p | F::Yuv420p10),
+            VideoCodec::Vp9 => !matches!(pixel_format, F::Yuva444p10 | F::Rgb24 | F::Rgba),
+            VideoCodec::ProRes | VideoCodec::Dnxhr => true,
+            VideoCodec::Ffv1 => !matches!(pixel_format, F::Rgb24 | F::Rgba),
+            VideoCodec::Png | VideoCodec::Tiff => matches!(pixel_format, F::Rgb24 | F::Rgba),
+            VideoCodec::Jpeg => matches!(pixel_format, F::Yuv420p | F::Yuv422p | F::Yuv444p),
+        }
+    }
+
+    /// H.264 CRF 기준 품질값 → 코덱 고유 스케일
+    /// UI는 H.264 CRF(0~51) 하나만 노출, 같은 값이면 비슷한 화질이 되도록 보정
+    /// (x264 23 ≈ x265 28 ≈ AV1 35 ≈ VP9 31)
+    pub fn map_crf(self, crf: u32) -> u32 {
+        let crf = crf.min(51);
+        match self {
+            VideoCodec::H264 => crf,
+            VideoCodec::Hevc => (crf + 5).min(51),
+            VideoCodec::Av1 => (crf * 63 / 51 + 7).min(63),
+            VideoCodec::Vp9 => (crf * 63 / 51 + 3).min(63),
+            // JPEG: qscale 2(최고)~31
+            VideoCodec::Jpeg => 2 + crf * 29 / 51,
+            VideoCodec::ProRes | VideoCodec::Dnxhr | VideoCodec::Ffv1 | VideoCodec::Png | VideoCodec::Tiff => crf,
+        }
+    }
+}
+
+/// 출력 픽셀 포맷 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputPixelFormat {
+    Auto = 0,       // 코덱/프로필 기본값
+    Yuv420p = 1,
+    Yuv422p = 2,
+    Yuv444p = 3,
+    Yuv420p10 = 4,
+    Yuv422p10 = 5,
+    Yuv444p10 = 6,
+    Yuva420p = 7,   // VP9 알파
+    Yuva444p10 = 8, // ProRes 4444 / FFV1 알파
+    Rgb24 = 9,      // PNG / TIFF
+    Rgba = 10,      // PNG / TIFF 알파
+}
+
+impl OutputPixelFormat {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => OutputPixelFormat::Yuv420p,
+            2 => OutputPixelFormat::Yuv422p,
+            3 => OutputPixelFormat::Yuv444p,
+            4 => OutputPixelFormat::Yuv420p10,
+            5 => OutputPixelFormat::Yuv422p10,
+            6 => OutputPixelFormat::Yuv444p10,
+            7 => OutputPixelFormat::Yuva420p,
+            8 => OutputPixelFormat::Yuva444p10,
+            9 => OutputPixelFormat::Rgb24,
+            10 => OutputPixelFormat::Rgba,
+            _ => OutputPixelFormat::Auto,
+        }
+    }
+
+    /// FFmpeg 픽셀 포맷 (Auto는 resolve_pixel_format으로 확정 후 호출)
+    pub fn pixel(self) -> Pixel {
+        match self {
+            OutputPixelFormat::Yuv420p | OutputPixelFormat::Auto => Pixel::YUV420P,
+            OutputPixelFormat::Yuv422p => Pixel::YUV422P,
+            OutputPixelFormat::Yuv444p => Pixel::YUV444P,
+            OutputPixelFormat::Yuv420p10 => Pixel::YUV420P10LE,
+            OutputPixelFormat::Yuv422p10 => Pixel::YUV422P10LE,
+            OutputPixelFormat::Yuv444p10 => Pixel::YUV444P10LE,
+            OutputPixelFormat::Yuva420p => Pixel::YUVA420P,
+            OutputPixelFormat::Yuva444p10 => Pixel::YUVA444P10LE,
+            OutputPixelFormat::Rgb24 => Pixel::RGB24,
+            OutputPixelFormat::Rgba => Pixel::RGBA,
+        }
+    }
+
+    pub fn has_alpha(self) -> bool {
+        matches!(self, OutputPixelFormat::Yuva420p | OutputPixelFormat::Yuva444p10 | OutputPixelFormat::Rgba)
+    }
+
+    /// 같은 샘플링의 알파 포함 포맷 (알파 버전이 없으면 가장 가까운 포맷)
+    pub fn with_alpha(self) -> Self {
+        match self {
+            OutputPixelFormat::Yuv420p | OutputPixelFormat::Auto => OutputPixelFormat::Yuva420p,
+            OutputPixelFormat::Rgb24 => OutputPixelFormat::Rgba,
+            f if f.has_alpha() => f,
+            _ => OutputPixelFormat::Yuva444p10,
+        }
+    }
+}
+
+/// ProRes 프로필 (FFI u32 매핑, prores_ks profile 옵션)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProResProfile {
+    Auto = 0,       // 422 HQ
+    Proxy = 1,
+    Lt = 2,
+    Standard = 3,
+    Hq = 4,
+    P4444 = 5,
+    P4444Xq = 6,
+}
+
+impl ProResProfile {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => ProResProfile::Proxy,
+            2 => ProResProfile::Lt,
+            3 => ProResProfile::Standard,
+            4 => ProResProfile::Hq,
+            5 => ProResProfile::P4444,
+            6 => ProResProfile::P4444Xq,
+            _ => ProResProfile::Auto,
+        }
+    }
+
+    fn option_value(self) -> &'static str {
+        match self {
+            ProResProfile::Proxy => "proxy",
+            ProResProfile::Lt => "lt",
+            ProResProfile::Standard => "standard",
+            ProResProfile::Hq | ProResProfile::Auto => "hq",
+            ProResProfile::P4444 => "4444",
+            ProResProfile::P4444Xq => "4444xq",
+        }
+    }
+
+    /// 422 계열 → 4:2:2 10bit, 4444 계열 → 4:4:4 10bit
+    pub fn pixel_format(self) -> OutputPixelFormat {
+        match self {
+            ProResProfile::P4444 | ProResProfile::P4444Xq => OutputPixelFormat::Yuv444p10,
+            _ => OutputPixelFormat::Yuv422p10,
+        }
+    }
+}
+
+/// DNxHR 프로필 (FFI u32 매핑, dnxhd profile 옵션)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DnxhrProfile {
+    Auto = 0,       // HQ
+    Lb = 1,
+    Sq = 2,
+    Hq = 3,
+    Hqx = 4,
+    P444 = 5,
+}
+
+impl DnxhrProfile {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => DnxhrProfile::Lb,
+            2 => DnxhrProfile::Sq,
+            3 => DnxhrProfile::Hq,
+            4 => DnxhrProfile::Hqx,
+            5 => DnxhrProfile::P444,
+            _ => DnxhrProfile::Auto,
+        }
+    }
+
+    fn option_value(self) -> &'static str {
+        match self {
+            DnxhrProfile::Lb => "dnxhr_lb",
+            DnxhrProfile::Sq => "dnxhr_sq",
+            DnxhrProfile::Hq | DnxhrProfile::Auto => "dnxhr_hq",
+            DnxhrProfile::Hqx => "dnxhr_hqx",
+            DnxhrProfile::P444 => "dnxhr_444",
+        }
+    }
+
+    /// LB/SQ/HQ → 4:2:2 8bit, HQX → 4:2:2 10bit, 444 → 4:4:4 10bit
+    pub fn pixel_format(self) -> OutputPixelFormat {
+        match self {
+            DnxhrProfile::Hqx => OutputPixelFormat::Yuv422p10,
+            DnxhrProfile::P444 => OutputPixelFormat::Yuv444p10,
+            _ => OutputPixelFormat::Yuv422p,
+        }
+    }
+}
+
+/// 오디오 코덱 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioCodec {
+    Auto = 0,   // 컨테이너 기준 (WebM → Opus, 그 외 → AAC)
+    Aac = 1,
+    Opus = 2,
+    Mp3 = 3,
+    Flac = 4,
+    Pcm16 = 5,  // 16-bit little-endian PCM
+    Pcm24 = 6,  // 24-bit little-endian PCM (방송 납품용)
+}
+
+impl AudioCodec {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => AudioCodec::Aac,
+            2 => AudioCodec::Opus,
+            3 => AudioCodec::Mp3,
+            4 => AudioCodec::Flac,
+            5 => AudioCodec::Pcm16,
+            6 => AudioCodec::Pcm24,
+            _ => AudioCodec::Auto,
+        }
+    }
+
+    /// 무손실 코덱 (비트레이트 설정 무시)
+    pub fn is_lossless(self) -> bool {
+        matches!(self, AudioCodec::Flac | AudioCodec::Pcm16 | AudioCodec::Pcm24)
+    }
+
+    /// Auto를 컨테이너 기본 코덱으로 확정
+    pub fn resolve(self, container: Container) -> Self {
+        if self == AudioCodec::Auto {
+            container.default_audio()
+        } else {
+            self
+        }
+    }
+
+    /// 인코더 이름 (앞쪽 우선, libopus 없으면 FFmpeg 내장 opus)
+    fn encoder_names(self) -> &'static [&'static str] {
+        match self {
+            AudioCodec::Opus => &["libopus", "opus"],
+            AudioCodec::Mp3 => &["libmp3lame"],
+            AudioCodec::Flac => &["flac"],
+            AudioCodec::Pcm16 => &["pcm_s16le"],
+            AudioCodec::Pcm24 => &["pcm_s24le"],
+            AudioCodec::Aac | AudioCodec::Auto => &["aac"],
+        }
+    }
+}
+
+/// 채널 수 → 채널 레이아웃 (1=모노, 2=스테레오, 6=5.1)
+fn channel_layout(channels: u32) -> ffmpeg::ChannelLayout {
+    match channels {
+        1 => ffmpeg::ChannelLayout::MONO,
+        6 => ffmpeg::ChannelLayout::_5POINT1,
+        _ => ffmpeg::ChannelLayout::STEREO,
+    }
+}
+
+/// 오디오 인코더 설정 (AudioCodec은 컨테이너 기준으로 확정된 값)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioSettings {
+    pub codec: AudioCodec,
+    pub sample_rate: u32,
+    /// 1=모노, 2=스테레오, 6=5.1
+    pub channels: u32,
+    /// bps (무손실 코덱은 무시)
+    pub bitrate: usize,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            codec: AudioCodec::Aac,
+            sample_rate: 48000,
+            channels: 2,
+            bitrate: 192000,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// 코덱별 샘플레이트/채널 제약 검증 (인코더 open 실패 전에 명확한 에러)
+    pub fn validate(&self) -> Result<(), String> {
+        if !matches!(self.channels, 1 | 2 | 6) {
+            return Err(format!("지원하지 않는 오디오 채널 수: {} (1, 2, 6만 가능)", self.channels));
+        }
+        if !(8000..=192000).contains(&self.sample_rate) {
+            return Err(format!("지원하지 않는 샘플레이트: {}Hz", self.sample_rate));
+        }
+        match self.codec {
+            AudioCodec::Opus if ![8000, 12000, 16000, 24000, 48000].contains(&self.sample_rate) => {
+                Err(format!("Opus는 {}Hz를 지원하지 않습니다 (8/12/16/24/48kHz)", self.sample_rate))
+            }
+            AudioCodec::Mp3 if self.sample_rate > 48000 || self.channels > 2 => {
+                Err("MP3는 48kHz 이하 모노/스테레오만 지원합니다".to_string())
+            }
+            AudioCodec::Aac if self.sample_rate > 96000 => {
+                Err("AAC는 96kHz 이하만 지원합니다".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 인코더 입력 샘플 포맷 선택 (f32 우선, 없으면 정수 PCM — pcm_s16le/s24le/FLAC)
+fn choose_sample_format(supported: &[ffmpeg::format::Sample]) -> Option<ffmpeg::format::Sample> {
+    use ffmpeg::format::sample::Type;
+    use ffmpeg::format::Sample;
+    if supported.is_empty() {
+        return Some(Sample::F32(Type::Planar));
+    }
+    [
+        Sample::F32(Type::Planar),
+        Sample::F32(Type::Packed),
+        Sample::I32(Type::Planar),
+        Sample::I32(Type::Packed),
+        Sample::I16(Type::Planar),
+        Sample::I16(Type::Packed),
+    ]
+    .into_iter()
+    .find(|f| supported.contains(f))
+}
+
+/// interleaved f32 → 인코더 입력 포맷으로 프레임 채우기
+/// planar면 채널별 plane으로 deinterleave, 정수 포맷은 [-1, 1] 클램프 후 스케일
+fn fill_audio_frame(
+    frame: &mut ffmpeg::frame::Audio,
+    format: ffmpeg::format::Sample,
+    samples: &[f32],
+    channels: usize,
+) {
+    let frame_size = samples.len() / channels;
+    let planar = format.is_planar();
+    let (planes, per_plane) = if planar { (channels, frame_size) } else { (1, samples.len()) };
+    let bytes = format.bytes();
+
+    for p in 0..planes {
+        // 오디오는 linesize[0]만 설정되므로 plane 길이를 직접 계산
+        let data = unsafe {
+            std::slice::from_raw_parts_mut(frame.data_mut(p).as_mut_ptr(), per_plane * bytes)
+        };
+        for (i, out) in data.chunks_exact_mut(bytes).enumerate() {
+            let v = if planar { samples[i * channels + p] } else { samples[i] };
+            match format {
+                ffmpeg::format::Sample::I16(_) => {
+                    out.copy_from_slice(&((v.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_ne_bytes());
+                }
+                ffmpeg::format::Sample::I32(_) => {
+                    out.copy_from_slice(&((v.clamp(-1.0, 1.0) as f64 * i32::MAX as f64) as i32).to_ne_bytes());
+                }
+                _ => out.copy_from_slice(&v.to_ne_bytes()),
+            }
+        }
+    }
+}
+
+/// 인코딩 속도/품질 프리셋 (FFI u32 매핑, 인코더별 옵션으로 변환)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EncoderPreset {
+    Balanced = 0,   // x264 medium 상당
+    Fast = 1,       // 미리보기/초안용
+    Quality = 2,    // 최종 납품용 (느림)
+}
+
+impl EncoderPreset {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => EncoderPreset::Fast,
+            2 => EncoderPreset::Quality,
+            _ => EncoderPreset::Balanced,
+        }
+    }
+
+    /// 인코더별 옵션 설정 (crf: 코덱 고유 스케일로 변환된 값, None이면 속도 옵션만 — 목표 비트레이트 모드)
+    /// 반환: 고정 비트레이트가 필요한 인코더(AMF 등)면 false
+    fn apply(self, codec_name: &str, crf: Option<u32>, opts: &mut ffmpeg::Dictionary) -> bool {
+        let pick = |fast: &str, balanced: &str, quality: &str| -> String {
+            match self {
+                EncoderPreset::Fast => fast,
+                EncoderPreset::Balanced => balanced,
+                EncoderPreset::Quality => quality,
+            }
+            .to_string()
+        };
+        let quality = crf.map(|c| c.to_string());
+
+        match codec_name {
+            "libx264" | "libx265" => {
+                if let Some(q) = &quality {
+                    opts.set("crf", q);
+                }
+                opts.set("preset", &pick("veryfast", "medium", "slow"));
+                if codec_name == "libx265" {
+                    opts.set("x265-params", "log-level=error");
+                }
+            }
+            "libsvtav1" => {
+                if let Some(q) = &quality {
+                    opts.set("crf", q);
+                }
+                opts.set("preset", &pick("10", "8", "5"));
+            }
+            "libaom-av1" => {
+                // b:v 0 + crf → 고정 품질 모드
+                if let Some(q) = &quality {
+                    opts.set("crf", q);
+                }
+                opts.set("cpu-used", &pick("8", "6", "4"));
+                opts.set("row-mt", "1");
+            }
+            "libvpx-vp9" => {
+                // b:v 0 + crf → 고정 품질 모드
+                if let Some(q) = &quality {
+                    opts.set("crf", q);
+                }
+                opts.set("deadline", "good");
+                opts.set("cpu-used", &pick("5", "3", "1"));
+                opts.set("row-mt", "1");
+            }
+            "h264_nvenc" | "hevc_nvenc" | "av1_nvenc" => {
+                // NVENC: VBR + CQ (Constant Quality) 모드
+                if let Some(q) = &quality {
+                    opts.set("rc", "vbr");
+                    opts.set("cq", q);
+                    eprintln!("[ENCODER] NVENC CQ={}", q);
+                }
+                opts.set("preset", &pick("p2", "p4", "p6"));
+            }
+            "h264_qsv" | "hevc_qsv" | "av1_qsv" | "vp9_qsv" => {
+                if let Some(q) = &quality {
+                    opts.set("global_quality", q);
+                    eprintln!("[ENCODER] QSV global_quality={}", q);
+                }
+                opts.set("preset", &pick("veryfast", "medium", "veryslow"));
+            }
+            "h264_amf" | "hevc_amf" | "av1_amf" => {
+                opts.set("quality", &pick("speed", "balanced", "quality"));
+                return false;
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// 비트레이트 제어 방식 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RateControl {
+    Crf = 0,        // 고정 품질 (기본)
+    CappedCrf = 1,  // 고정 품질 + 최대 비트레이트 (maxrate/bufsize, 플랫폼 상한)
+    TwoPass = 2,    // 목표 비트레이트 2-pass (파일 크기 예측)
+    Cbr = 3,        // 고정 비트레이트 (방송 규격)
+}
+
+impl RateControl {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => RateControl::CappedCrf,
+            2 => RateControl::TwoPass,
+            3 => RateControl::Cbr,
+            _ => RateControl::Crf,
+        }
+    }
+}
+
+/// 2-pass를 실제로 두 번 인코딩하는 인코더 (그 외는 단일 패스 VBR, NVENC는 내부 multipass)
+const TWO_PASS_ENCODERS: [&str; 4] = ["libx264", "libx265", "libvpx-vp9", "libaom-av1"];
+
+/// 비트레이트/VBV 버퍼 상한 (코덱 컨텍스트 rc_buffer_size가 int)
+const MAX_RATE_BITS: usize = i32::MAX as usize;
+
+/// 비트레이트 제어 설정 (단위 bps / bit)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateSettings {
+    pub mode: RateControl,
+    /// 목표 비트레이트 (TwoPass / Cbr, CappedCrf는 고정 품질 옵션이 없는 인코더의 평균 비트레이트, 0=CRF 근사)
+    pub bitrate: usize,
+    /// 최대 비트레이트 (CappedCrf 필수, TwoPass는 0이면 제한 없음)
+    pub max_bitrate: usize,
+    /// VBV 버퍼 크기 (0이면 Cbr은 목표 비트레이트 1초분, 그 외는 최대 비트레이트 2초분)
+    pub buffer_size: usize,
+}
+
+impl Default for RateSettings {
+    fn default() -> Self {
+        Self { mode: RateControl::Crf, bitrate: 0, max_bitrate: 0, buffer_size: 0 }
+    }
+}
+
+impl RateSettings {
+    /// 설정 검증 (인트라 전용 코덱은 프로필이 비트레이트를 결정하므로 CRF만)
+    pub fn validate(&self, codec: VideoCodec) -> Result<(), String> {
+        if self.mode == RateControl::Crf {
+            return Ok(());
+        }
+        if codec.is_intra_only() {
+            return Err(format!("{:?} 코덱은 비트레이트 제어를 지원하지 않습니다", codec));
+        }
+        // FFI에서 음수가 u32로 넘어온 값(-1 → 4294967295kbps) 방지, rc_buffer_size는 i32
+        if [self.bitrate, self.max_bitrate, self.vbv_buffer()].iter().any(|&v| v > MAX_RATE_BITS) {
+            return Err(format!(
+                "비트레이트/버퍼 크기가 범위를 벗어났습니다 (최대 {}kbps)",
+                MAX_RATE_BITS / 1000
+            ));
+        }
+        match self.mode {
+            RateControl::CappedCrf if self.max_bitrate == 0 => {
+                Err("CRF 상한 모드에 최대 비트레이트가 설정되지 않았습니다".to_string())
+            }
+            RateControl::TwoPass | RateControl::Cbr if self.bitrate == 0 => {
+                Err("목표 비트레이트가 설정되지 않았습니다".to_string())
+            }
+            RateControl::CappedCrf | RateControl::TwoPass
+                if self.max_bitrate != 0 && self.max_bitrate < self.bitrate =>
+            {
+                Err(format!(
+                    "최대 비트레이트({}kbps)가 목표 비트레이트({}kbps)보다 작습니다",
+                    self.max_bitrate / 1000, self.bitrate / 1000
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// VBV 버퍼 크기 (미지정 시 모드별 기본값)
+    fn vbv_buffer(&self) -> usize {
+        if self.buffer_size > 0 {
+            self.buffer_size
+        } else if self.mode == RateControl::Cbr {
+            self.bitrate
+        } else {
+            self.max_bitrate * 2
+        }
+    }
+}
+
+/// 2-pass 인코딩 단계 (통계 파일 경로)
+#[derive(Debug, Clone, Copy)]
+pub enum EncoderPass<'a> {
+    /// 단일 패스
+    Single,
+    /// 1차: null 먹서로 출력 없이 통계만 기록
+    First(&'a Path),
+    /// 2차: 1차 통계로 비트 배분
+    Second(&'a Path),
+}
+
+/// 통계를 stats_out/stats_in 메모리로 주고받는 인코더 (libx264/x265는 통계 파일 직접 기록)
+fn uses_memory_stats(codec_name: &str) -> bool {
+    matches!(codec_name, "libvpx-vp9" | "libaom-av1")
+}
+
+/// 비트레이트 제어 계획 (인코더 옵션 외에 코덱 컨텍스트에 직접 설정할 값)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RatePlan {
+    /// 인코더 bit_rate (bps, 0=고정 품질)
+    bit_rate: usize,
+    /// VBV 제약 (rc_min_rate, rc_max_rate, rc_buffer_size)
+    limits: Option<(usize, usize, usize)>,
+    /// 2-pass 단계 (1 / 2, 단일 패스는 0)
+    pass_number: u32,
+}
+
+impl RatePlan {
+    /// 코덱 컨텍스트에 적용 (인코더 열기 전)
+    fn apply(&self, encoder: &mut ffmpeg::encoder::video::Video) {
+        encoder.set_bit_rate(self.bit_rate);
+        if let Some((min_rate, max_rate, buffer_size)) = self.limits {
+            encoder.set_max_bit_rate(max_rate);
+            unsafe {
+                let ctx = encoder.as_mut_ptr();
+                (*ctx).rc_min_rate = min_rate as i64;
+                (*ctx).rc_buffer_size = buffer_size as i32;
+            }
+        }
+        let flag = match self.pass_number {
+            1 => codec::flag::Flags::PASS1,
+            2 => codec::flag::Flags::PASS2,
+            _ => return,
+        };
+        unsafe {
+            (*encoder.as_mut_ptr()).flags |= flag.bits() as i32;
+        }
+    }
+}
+
+/// x265-params에 옵션 추가 (':' 구분, 프리셋의 log-level 유지)
+fn append_x265_params(opts: &mut ffmpeg::Dictionary, params: &str) {
+    let merged = match opts.get("x265-params") {
+        Some(existing) if !existing.is_empty() => format!("{}:{}", existing, params),
+        _ => params.to_string(),
+    };
+    opts.set("x265-params", &merged);
+}
+
+/// 배포용 코덱 비트레이트 제어 계획 (인코더 옵션은 opts에 기록)
+/// fallback_bitrate: 고정 품질 옵션이 없는 인코더(AMF 등)의 CRF 근사 비트레이트
+fn plan_rate_control(
+    rate: RateSettings,
+    preset: EncoderPreset,
+    codec_name: &str,
+    codec_crf: u32,
+    fallback_bitrate: usize,
+    pass: EncoderPass,
+    opts: &mut ffmpeg::Dictionary,
+) -> RatePlan {
+    if matches!(rate.mode, RateControl::TwoPass | RateControl::Cbr) {
+        preset.apply(codec_name, None, opts);
+        return plan_bitrate_mode(rate, codec_name, pass, opts);
+    }
+
+    let capped = rate.mode == RateControl::CappedCrf;
+    let bit_rate = if preset.apply(codec_name, Some(codec_crf), opts) {
+        // 고정 품질 모드: 기본 bit_rate(200k)가 남아 있으면 libvpx/libaom이 CQ 모드로 동작
+        // CRF 상한: libvpx/libaom은 crf + b:v가 제한 품질(Constrained Quality) 모드
+        if capped && matches!(codec_name, "libvpx-vp9" | "libaom-av1") {
+            rate.max_bitrate
+        } else {
+            0
+        }
+    } else {
+        let bitrate = match rate.mode {
+            RateControl::CappedCrf if rate.bitrate > 0 => rate.bitrate,
+            RateControl::CappedCrf => fallback_bitrate.min(rate.max_bitrate),
+            _ => fallback_bitrate,
+        };
+        eprintln!("[ENCODER] {} bitrate={}kbps", codec_name, bitrate / 1000);
+        bitrate
+    };
+    let limits = capped.then(|| (0, rate.max_bitrate, rate.vbv_buffer()));
+    if capped {
+        eprintln!(
+            "[ENCODER] CRF 상한: maxrate={}kbps, bufsize={}kbit",
+            rate.max_bitrate / 1000, rate.vbv_buffer() / 1000
+        );
+    }
+    RatePlan { bit_rate, limits, pass_number: 0 }
+}
+
+/// 목표 비트레이트 모드 계획 (TwoPass / Cbr)
+fn plan_bitrate_mode(
+    rate: RateSettings,
+    codec_name: &str,
+    pass: EncoderPass,
+    opts: &mut ffmpeg::Dictionary,
+) -> RatePlan {
+    if rate.mode == RateControl::Cbr {
+        // minrate = maxrate = bitrate → libvpx/libaom/SVT-AV1/QSV는 CBR로 동작
+        match codec_name {
+            "libx264" => opts.set("nal-hrd", "cbr"),
+            "libx265" => append_x265_params(opts, "strict-cbr=1"),
+            "h264_nvenc" | "hevc_nvenc" | "av1_nvenc" | "h264_amf" | "hevc_amf" | "av1_amf" => {
+                opts.set("rc", "cbr")
+            }
+            _ => {}
+        }
+        eprintln!("[ENCODER] {} CBR {}kbps", codec_name, rate.bitrate / 1000);
+        return RatePlan {
+            bit_rate: rate.bitrate,
+            limits: Some((rate.bitrate, rate.bitrate, rate.vbv_buffer())),
+            pass_number: 0,
+        };
+    }
+
+    let limits = (rate.max_bitrate > 0).then(|| (0, rate.max_bitrate, rate.vbv_buffer()));
+    let plan = |pass_number| RatePlan { bit_rate: rate.bitrate, limits, pass_number };
+    let (pass_number, stats) = match pass {
+        EncoderPass::Single => {
+            // 하드웨어 인코더: 단일 패스 VBR (NVENC는 풀 해상도 내부 2-pass)
+            match codec_name {
+                "h264_nvenc" | "hevc_nvenc" | "av1_nvenc" => {
+                    opts.set("rc", "vbr");
+                    opts.set("multipass", "fullres");
+                }
+                "h264_amf" | "hevc_amf" | "av1_amf" => opts.set("rc", "vbr_peak"),
+                _ => {}
+            }
+            eprintln!("[ENCODER] {} VBR {}kbps (단일 패스)", codec_name, rate.bitrate / 1000);
+            return plan(0);
+        }
+        EncoderPass::First(stats) => (1, stats),
+        EncoderPass::Second(stats) => (2, stats),
+    };
+
+    match codec_name {
+        "libx264" => opts.set("stats", &stats.to_string_lossy()),
+        // Windows 경로의 ':'가 구분자로 해석되지 않도록 따옴표
+        "libx265" => append_x265_params(
+            opts,
+            &format!("pass={}:stats='{}'", pass_number, stats.to_string_lossy()),
+        ),
+        _ => {}
+    }
+    eprintln!("[ENCODER] {} 2-pass {}차, 목표 {}kbps", codec_name, pass_number, rate.bitrate / 1000);
+    plan(pass_number)
+}
+
+/// 중간 코덱 옵션 설정 (인트라 전용, 비트레이트는 프로필이 결정)
+fn apply_intermediate_options(
+    video_codec: VideoCodec,
+    codec_name: &str,
+    codec_profile: u32,
+    codec_crf: u32,
+    opts: &mut ffmpeg::Dictionary,
+) {
+    match video_codec {
+        VideoCodec::ProRes => {
+            opts.set("profile", ProResProfile::from_u32(codec_profile).option_value());
+            if codec_name == "prores_ks" {
+                opts.set("vendor", "apl0"); // Apple 툴 호환
+            }
+        }
+        VideoCodec::Dnxhr => {
+            opts.set("profile", DnxhrProfile::from_u32(codec_profile).option_value());
+        }
+        VideoCodec::Ffv1 => {
+            // FFV1 v3: 슬라이스 병렬 + 슬라이스 CRC (아카이브 무결성 검사)
+            opts.set("level", "3");
+            opts.set("slicecrc", "1");
+            opts.set("slices", "16");
+        }
+        VideoCodec::Jpeg => {
+            // 고정 양자화 (map_crf로 변환된 qscale)
+            let q = codec_crf.to_string();
+            opts.set("qmin", &q);
+            opts.set("qmax", &q);
+        }
+        VideoCodec::Tiff => {
+            opts.set("compression_algo", "lzw");
+        }
+        _ => {}
+    }
+}
+
+/// detect_available_encoders 비트 순서 (bit N = ENCODER_NAMES[N])
+pub const ENCODER_NAMES: [&str; 18] = [
+    "libx264", "h264_nvenc", "h264_qsv", "h264_amf",
+    "libx265", "hevc_nvenc", "hevc_qsv", "hevc_amf",
+    "libsvtav1", "libaom-av1", "av1_nvenc", "av1_qsv", "av1_amf",
+    "libvpx-vp9", "vp9_qsv",
+    "prores_ks", "dnxhd", "ffv1",
+];
+
+/// 소프트 자막 코덱 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubtitleCodec {
+    Auto = 0,       // 출력 컨테이너 기준 자동 선택
+    MovText = 1,    // MP4/MOV (tx3g)
+    Subrip = 2,     // MKV (SRT)
+    Ass = 3,        // MKV (ASS/SSA)
+    WebVtt = 4,     // WebM
+}
+
+impl SubtitleCodec {
+    pub fn from_u32(v: u32) -> Self {
+        match v {
+            1 => SubtitleCodec::MovText,
+            2 => SubtitleCodec::Subrip,
+            3 => SubtitleCodec::Ass,
+            4 => SubtitleCodec::WebVtt,
+            _ => SubtitleCodec::Auto,
+        }
+    }
+
+    /// Auto를 컨테이너 기본 코덱으로 확정 (MKV → SRT, WebM → WebVTT, 그 외 → mov_text)
+    pub fn resolve(self, container: Container) -> Self {
+        if self == SubtitleCodec::Auto {
+            container.default_subtitle()
+        } else {
+            self
+        }
+    }
+
+    fn codec_id(self) -> codec::Id {
+        match self {
+            SubtitleCodec::Subrip => codec::Id::SUBRIP,
+            SubtitleCodec::Ass => codec::Id::ASS,
+            SubtitleCodec::WebVtt => codec::Id::WEBVTT,
+            SubtitleCodec::MovText | SubtitleCodec::Auto => codec::Id::MOV_TEXT,
+        }
+    }
+}
+
+/// 소프트 자막 스트림 (텍스트 자막 인코더 + 출력 스트림)
+struct SubtitleStream {
+    encoder: ffmpeg::encoder::subtitle::Encoder,
+    stream_index: usize,
+    /// 인코딩 출력 버퍼 (cue 1개분)
+    buffer: Vec<u8>,
+}
+
+/// 오디오 스트림 (오디오 인코더 + 출력 스트림 + 프레임 크기 버퍼링)
+/// VideoEncoder / AudioEncoder(오디오 전용 Export) 공용
+struct AudioStream {
+    encoder: ffmpeg::encoder::Audio,
+    stream_index: usize,
+    time_base: ffmpeg::Rational,
+    pts: i64,
+    // 오디오 버퍼링 (인코더 프레임 크기 정렬)
+    buffer: Vec<f32>,       // interleaved f32 (출력 샘플레이트/채널 기준)
+    frame_size: usize,      // 프레임당 채널당 샘플 수 (AAC 1024, Opus 960, MP3 1152)
+    channels: u32,
+    sample_rate: u32,
+    layout: ffmpeg::ChannelLayout,
+    /// 인코더 입력 샘플 포맷 (AAC/내장 opus = FLTP, libopus = FLT, PCM = S16/S32)
+    format: ffmpeg::format::Sample,
+    /// 믹서 출력(48kHz 스테레오) → 출력 샘플레이트/채널 (같으면 None)
+    resampler: Option<resampling::Context>,
+}
+
+impl AudioStream {
+    /// 오디오 인코더 생성 + 출력 스트림 추가 (write_header 전에 호출)
+    fn new(
+        output_ctx: &mut ffmpeg::format::context::Output,
+        settings: AudioSettings,
+    ) -> Result<Self, String> {
+        settings.validate()?;
+        let AudioSettings { codec: audio_codec, sample_rate, channels, bitrate } = settings;
+        let bitrate = if audio_codec.is_lossless() { 0 } else { bitrate };
+        let layout = channel_layout(channels);
+
+        let codec = audio_codec.encoder_names().iter()
+            .find_map(|name| ffmpeg::encoder::find_by_name(name))
+            .ok_or_else(|| format!("{:?} 오디오 인코더를 찾을 수 없습니다", audio_codec))?;
+
+        eprintln!("[ENCODER] 오디오 인코더: {}", codec.name());
+
+        // 입력 포맷: FLTP 우선, 없으면 FLT interleaved (libopus), 정수 PCM (pcm_s16le/s24le, FLAC)
+        let supported: Vec<ffmpeg::format::Sample> = codec.audio().ok()
+            .and_then(|a| a.formats())
+            .map(|f| f.collect())
+            .unwrap_or_default();
+        let sample_format = choose_sample_format(&supported)
+            .ok_or_else(|| format!("{} 인코더가 지원하는 샘플 포맷이 없습니다", codec.name()))?;
+
+        // 믹서 출력(48kHz 스테레오 f32)과 다르면 리샘플러 생성 (출력도 f32 interleaved → 버퍼링)
+        let resampler = if sample_rate != 48000 || channels != 2 {
+            let r = resampling::Context::get(
+                ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
+                ffmpeg::ChannelLayout::STEREO,
+                48000,
+                ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed),
+                layout,
+                sample_rate,
+            ).map_err(|e| format!("Failed to create audio resampler: {}", e))?;
+            Some(r)
+        } else {
+            None
+        };
+
+        let needs_global_header = output_ctx.format().flags()
+            .contains(ffmpeg::format::flag::Flags::GLOBAL_HEADER);
+
+        // 오디오 스트림 추가
+        let mut audio_stream = output_ctx.add_stream(codec)
+            .map_err(|e| format!("Failed to add audio stream: {}", e))?;
+
+        let audio_stream_index = audio_stream.index();
+        let audio_time_base = ffmpeg::Rational::new(1, sample_rate as i32);
+
+        // 오디오 인코더 설정
+        let mut audio_enc = codec::context::Context::new_with_codec(codec)
+            .encoder()
+            .audio()
+            .map_err(|e| format!("Failed to get audio encoder: {}", e))?;
+
+        audio_enc.set_rate(sample_rate as i32);
+        audio_enc.set_channel_layout(layout);
+        audio_enc.set_format(sample_format);
+        audio_enc.set_bit_rate(bitrate);
+        audio_enc.set_time_base(audio_time_base);
+
+        if needs_global_header {
+            unsafe {
+                (*audio_enc.as_mut_ptr()).flags |= codec::flag::Flags::GLOBAL_HEADER.bits() as i32;
+            }
+        }
+        if codec.name() == "opus" {
+            // FFmpeg 내장 opus 인코더는 experimental
+            audio_enc.compliance(codec::Compliance::Experimental);
+        }
+        if matches!(sample_format, ffmpeg::format::Sample::I32(_)) {
+            // S32 입력 중 유효 비트 (FLAC 24bit 출력)
+            unsafe {
+                (*audio_enc.as_mut_ptr()).bits_per_raw_sample = 24;
+            }
+        }
+
+        let mut opts = ffmpeg::Dictionary::new();
+        if codec.name() == "libopus" && channels > 2 {
+            // 5.1 Opus는 Vorbis 채널 매핑 필요
+            opts.set("mapping_family", "1");
+        }
+
+        let audio_enc = audio_enc.open_as_with(codec, opts)
+            .map_err(|e| format!("Failed to open audio encoder: {}", e))?;
+
+        // 프레임 크기 (AAC 1024, Opus 960, MP3 1152, PCM은 0 → 1024)
+        let frame_size = unsafe { (*audio_enc.as_ptr()).frame_size as usize };
+        let frame_size = if frame_size > 0 { frame_size } else { 1024 };
+
+        eprintln!(
+            "[ENCODER] {:?} 오디오 인코더 성공: {}Hz {}ch, {}kbps, frame_size={}, fmt={:?}",
+            audio_codec, sample_rate, channels, bitrate / 1000, frame_size, sample_format
+        );
+
+        audio_stream.set_parameters(&audio_enc);
+
+        Ok(Self {
+            encoder: audio_enc,
+            stream_index: audio_stream_index,
+            time_base: audio_time_base,
+            pts: 0,
+            buffer: Vec::new(),
+            frame_size,
+            channels,
+            sample_rate,
+            layout,
+            format: sample_format,
+            resampler,
+        })
+    }
+
+    /// 48kHz 스테레오 f32 샘플 버퍼링 → 완전한 프레임만큼 인코딩
+    fn encode_samples(
+        &mut self,
+        output_ctx: &mut ffmpeg::format::context::Output,
+        samples: &[f32],
+    ) -> Result<(), String> {
+        if self.resampler.is_some() {
+            self.resample_into_buffer(Some(samples))?;
+        } else {
+            self.buffer.extend_from_slice(samples);
+        }
+        self.encode_buffered(output_ctx)
+    }
+
+    /// 48kHz 스테레오 → 출력 샘플레이트/채널 변환 후 buffer에 추가
+    /// samples None: 리샘플러 내부 잔여 샘플 flush (finish에서 호출)
+    fn resample_into_buffer(&mut self, samples: Option<&[f32]>) -> Result<(), String> {
+        let resampler = match self.resampler.as_mut() {
+            Some(r) => r,
+            None => return Ok(()),
+        };
+        let packed = ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Packed);
+        let channels = self.channels as usize;
+
+        let in_samples = samples.map_or(0, |s| s.len() / 2);
+        // 출력 용량: 비율 변환분 + 리샘플러 지연분 여유
+        let capacity = in_samples * self.sample_rate as usize / 48000 + 256;
+        let mut output = ffmpeg::frame::Audio::new(packed, capacity, self.layout);
+
+        match samples {
+            Some(samples) => {
+                let mut input = ffmpeg::frame::Audio::new(packed, in_samples, ffmpeg::ChannelLayout::STEREO);
+                input.set_rate(48000);
+                let data = unsafe {
+                    std::slice::from_raw_parts_mut(input.data_mut(0).as_mut_ptr() as *mut f32, in_samples * 2)
+                };
+                data.copy_from_slice(&samples[..in_samples * 2]);
+                resampler.run(&input, &mut output)
+                    .map_err(|e| format!("Audio resample failed: {}", e))?;
+            }
+            None => {
+                resampler.flush(&mut output)
+                    .map_err(|e| format!("Audio resample flush failed: {}", e))?;
+            }
+        }
+
+        // swr_convert_frame이 실제 출력 샘플 수로 갱신
+        let produced = output.samples() * channels;
+        let data = unsafe {
+            std::slice::from_raw_parts(output.data(0).as_ptr() as *const f32, produced)
+        };
+        self.buffer.extend_from_slice(data);
+        Ok(())
+    }
+
+    /// 버퍼에서 완전한 오디오 프레임만큼 인코딩
+    fn encode_buffered(&mut self, output_ctx: &mut ffmpeg::format::context::Output) -> Result<(), String> {
+        let frame_size = self.frame_size;
+        let channels = self.channels as usize;
+        let samples_per_frame = frame_size * channels; // interleaved 기준
+
+        while self.buffer.len() >= samples_per_frame {
+            // 오디오 프레임 생성 (인코더 입력 포맷)
+            let mut frame = ffmpeg::frame::Audio::new(self.format, frame_size, self.layout);
+            frame.set_pts(Some(self.pts));
+            frame.set_rate(self.sample_rate);
+            self.pts += frame_size as i64;
+
+            fill_audio_frame(&mut frame, self.format, &self.buffer[..samples_per_frame], channels);
+            self.buffer.drain(..samples_per_frame);
+
+            // 인코더에 프레임 전송 → 인코딩된 패킷 기록
+            self.encoder.send_frame(&frame)
+                .map_err(|e| format!("Failed to send audio frame: {}", e))?;
+            self.write_packets(output_ctx)?;
+        }
+
+        Ok(())
+    }
+
+    /// 잔여 버퍼 인코딩 + EOF + 남은 패킷 기록
+    fn finish(&mut self, output_ctx: &mut ffmpeg::format::context::Output) -> Result<(), String> {
+        // 리샘플러 지연분 → 버퍼 (완전한 프레임은 먼저 인코딩해 잔여분을 1프레임 미만으로)
+        self.resample_into_buffer(None)?;
+        self.encode_buffered(output_ctx)?;
+
+        // 잔여 샘플을 0으로 패딩하여 마지막 프레임 완성
+        let channels = self.channels as usize;
+        let remaining = self.buffer.len() / channels;
+        if remaining > 0 {
+            let pad = (self.frame_size - remaining) * channels;
+            self.buffer.extend(std::iter::repeat(0.0f32).take(pad));
+            self.encode_buffered(output_ctx)?;
+        }
+
+        self.encoder.send_eof()
+            .map_err(|e| format!("Failed to send audio EOF: {}", e))?;
+        self.write_packets(output_ctx)
+    }
+
+    /// 오디오 패킷 수신 → 출력 파일에 기록
+    fn write_packets(&mut self, output_ctx: &mut ffmpeg::format::context::Output) -> Result<(), String> {
+        let mut packet = ffmpeg::Packet::empty();
+        while self.encoder.receive_packet(&mut packet).is_ok() {
+            packet.set_stream(self.stream_index);
+            packet.rescale_ts(
+                self.time_base,
+                output_ctx.stream(self.stream_index)
+                    .ok_or("Audio stream not found")?
+                    .time_base(),
+            );
+            packet.write_interleaved(output_ctx)
+                .map_err(|e| format!("Failed to write audio packet: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+/// 오디오 전용 인코더 (WAV / FLAC / MP3 / M4A, 비디오 스트림 없음)
+pub struct AudioEncoder {
+    output_ctx: ffmpeg::format::context::Output,
+    audio: AudioStream,
+}
+
+impl AudioEncoder {
+    /// 출력 파일 + 오디오 스트림 생성 (container: 확정된 컨테이너)
+    pub fn new(output_path: &str, container: Container, settings: AudioSettings) -> Result<Self, String> {
+        ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+        let mut output_ctx = ffmpeg::format::output_as(output_path, container.muxer_name())
+            .map_err(|e| format!("Failed to create output ({:?}): {}", container, e))?;
+        let audio = AudioStream::new(&mut output_ctx, settings)?;
+
+        Ok(Self { output_ctx, audio })
+    }
+
+    /// 출력 파일 헤더 작성 (첫 샘플 인코딩 전에 호출)
+    pub fn write_header(&mut self) -> Result<(), String> {
+        self.output_ctx.write_header()
+            .map_err(|e| format!("Failed to write header: {}", e))
+    }
+
+    /// f32 PCM 오디오 인코딩 (interleaved stereo 48kHz, AudioMixer 출력 그대로)
+    pub fn encode_audio_samples(&mut self, samples: &[f32]) -> Result<(), String> {
+        self.audio.encode_samples(&mut self.output_ctx, samples)
+    }
+
+    /// 인코딩 완료 (flush + trailer)
+    pub fn finish(&mut self) -> Result<(), String> {
+        self.audio.finish(&mut self.output_ctx)?;
+        self.output_ctx.write_trailer()
+            .map_err(|e| format!("Failed to write trailer: {}", e))?;
+        eprintln!("[ENCODER] 오디오 Export 완료 ({}샘플)", self.audio.pts);
+        Ok(())
+    }
+}
+
 /// 사용 가능한 인코더 탐지 (비트마스크 반환)
-/// bit 0 = libx264, bit 1 = NVENC, bit 2 = QSV, bit 3 = AMF
+/// bit 0 = libx264, bit 1 = NVENC, bit 2 = QSV, bit 3 = AMF (H.264)
+/// bit 4~7 = libx265 / hevc_nvenc / hevc_qsv / hevc_amf
+/// bit 8~12 = libsvtav1 / libaom-av1 / av1_nvenc / av1_qsv / av1_amf
+/// bit 13~14 = libvpx-vp9 / vp9_qsv
+/// bit 15~17 = prores_ks / dnxhd / ffv1
 pub fn detect_available_encoders() -> u32 {
     ffmpeg::init().ok();
     let mut mask = 0u32;
-    if ffmpeg::encoder::find_by_name("libx264").is_some() { mask |= 1; }
-    if ffmpeg::encoder::find_by_name("h264_nvenc").is_some() { mask |= 2; }
-    if ffmpeg::encoder::find_by_name("h264_qsv").is_some() { mask |= 4; }
-    if ffmpeg::encoder::find_by_name("h264_amf").is_some() { mask |= 8; }
-    eprintln!("[ENCODER] 탐지된 인코더: mask=0b{:04b} (x264={}, nvenc={}, qsv={}, amf={})",
-        mask, mask & 1 != 0, mask & 2 != 0, mask & 4 != 0, mask & 8 != 0);
+    for (bit, name) in ENCODER_NAMES.iter().enumerate() {
+        if ffmpeg::encoder::find_by_name(name).is_some() {
+            mask |= 1 << bit;
+        }
+    }
+    let found: Vec<&str> = ENCODER_NAMES.iter().enumerate()
+        .filter(|(bit, _)| mask & (1 << bit) != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    eprintln!("[ENCODER] 탐지된 인코더: mask=0b{:018b} ({})", mask, found.join(", "));
     mask
 }
 
-/// 비디오+오디오 인코더 (H.264 + AAC + MP4 컨테이너)
+/// 비디오 스트림 설정 (VideoEncoder::new 인자 묶음)
+#[derive(Debug, Clone, Copy)]
+pub struct VideoSettings {
+    pub codec: VideoCodec,
+    pub encoder_type: EncoderType,
+    pub preset: EncoderPreset,
+    /// ProRes/DNxHR 프로필 (ProResProfile / DnxhrProfile 값, 그 외 코덱은 무시)
+    pub codec_profile: u32,
+    /// 확정된 픽셀 포맷 (resolve_pixel_format 결과, Auto면 4:2:0 8bit)
+    pub pixel_format: OutputPixelFormat,
+    /// 비트레이트 제어 (기본 CRF)
+    pub rate: RateSettings,
+    /// 인코더 스레드 수 (0=인코더 기본, 병렬 Export 워커는 코어를 나눠 지정)
+    pub threads: u32,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            codec: VideoCodec::H264,
+            encoder_type: EncoderType::Auto,
+            preset: EncoderPreset::Balanced,
+            codec_profile: 0,
+            pixel_format: OutputPixelFormat::Yuv420p,
+            rate: RateSettings::default(),
+            threads: 0,
+        }
+    }
+}
+
+/// 비디오+오디오 인코더 (H.264/HEVC/AV1/VP9/중간 코덱 + AAC/Opus/MP3/FLAC/PCM + MP4/MOV/MKV/WebM 컨테이너)
 pub struct VideoEncoder {
     output_ctx: ffmpeg::format::context::Output,
-    encoder: ffmpeg::encoder::Video,
-    audio_encoder: Option<ffmpeg::encoder::Audio>,
-    scaler: scaling::Context,
+    /// 비디오 인코더 (병렬 Export 결합은 구간 파일 패킷만 복사 → None)
+    encoder: Option<ffmpeg::encoder::Video>,
+    /// 오디오 스트림 (init_audio 전/오디오 제외 시 None)
+    audio: Option<AudioStream>,
+    /// 소프트 자막 스트림 (add_subtitle_stream 순서 = 인덱스)
+    subtitle_streams: Vec<SubtitleStream>,
+    /// RGBA → 출력 픽셀 포맷 (인코더가 없으면 None)
+    scaler: Option<scaling::Context>,
+    /// Renderer YUV420P 프레임 → 출력 픽셀 포맷 (출력이 YUV420P면 None, 변환 없이 직행)
+    yuv_scaler: Option<scaling::Context>,
+    pixel_format: Pixel,
     video_stream_index: usize,
-    audio_stream_index: Option<usize>,
     frame_count: i64,
-    audio_pts: i64,
     time_base: ffmpeg::Rational,
-    audio_time_base: Option<ffmpeg::Rational>,
     width: u32,
     height: u32,
-    // 오디오 버퍼링 (AAC 프레임 크기 정렬)
-    audio_buffer: Vec<f32>,       // interleaved stereo (L, R, L, R, ...)
-    audio_frame_size: usize,      // AAC 프레임당 채널당 샘플 수 (보통 1024)
-    audio_channels: u32,
+    /// 인코더 재생성용 설정 (스마트 렌더: 재인코딩 구간마다 새 인코더, 인코더가 없으면 None)
+    setup: Option<EncoderSetup>,
+    /// 스마트 렌더 상태 (enable_smart_render 전 None)
+    smart: Option<SmartStream>,
+    /// 2-pass 1차: finish에서 stats_out을 기록할 통계 파일 (libvpx/libaom)
+    first_pass_stats: Option<std::path::PathBuf>,
+}
+
+/// 비디오 인코더 재생성용 설정 (코덱 + 옵션 + 비트레이트 제어)
+struct EncoderSetup {
+    codec: ffmpeg::Codec,
+    opts: ffmpeg::Dictionary<'static>,
+    rate_plan: RatePlan,
+}
+
+/// 스마트 렌더 상태 (원본 패킷 복사 + 재인코딩 구간 혼합)
+struct SmartStream {
+    /// 모든 패킷 dts를 pts보다 앞당길 프레임 수 (복사 소스 재정렬 지연 최댓값)
+    dts_delay: i64,
+    /// 마지막으로 기록한 비디오 dts (인코더 time_base)
+    last_dts: i64,
+    /// 재인코딩 구간 포함 여부 (병렬 Export 결합은 복사만 → 인코더 flush/재생성 생략)
+    part_encoders: bool,
+}
+
+impl SmartStream {
+    /// 다음 패킷 dts (먹서가 거부하지 않도록 단조 증가 보장)
+    fn next_dts(&mut self, dts: i64) -> i64 {
+        let dts = dts.max(self.last_dts.saturating_add(1));
+        self.last_dts = dts;
+        dts
+    }
 }
 
 impl VideoEncoder {
     /// 비디오 인코더 생성 (오디오는 init_audio로 추가)
+    /// container: 확정된 컨테이너 (확장자와 무관하게 이 먹서로 기록)
     pub fn new(
         output_path: &str,
+        container: Container,
         width: u32,
         height: u32,
         fps: f64,
         crf: u32,
-        encoder_type: EncoderType,
+        settings: VideoSettings,
     ) -> Result<Self, String> {
+        Self::with_pass(output_path, container, (width, height), fps, crf, settings, EncoderPass::Single)
+    }
+
+    /// 2-pass 단계 지정 인코더 생성 (RateControl::TwoPass + needs_first_pass일 때)
+    /// First: output_path에 null 먹서 (패킷 버림, 통계만), 오디오/자막 없이 사용
+    pub fn with_pass(
+        output_path: &str,
+        container: Container,
+        (width, height): (u32, u32),
+        fps: f64,
+        crf: u32,
+        settings: VideoSettings,
+        pass: EncoderPass,
+    ) -> Result<Self, String> {
+        let VideoSettings { codec: video_codec, encoder_type, preset, codec_profile, pixel_format, rate, threads } = settings;
+        let pixel_format = video_codec.encoder_pixel(pixel_format);
+
         ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
 
-        // 출력 컨텍스트 생성 (MP4 포맷)
-        let mut output_ctx = ffmpeg::format::output(output_path)
-            .map_err(|e| format!("Failed to create output: {}", e))?;
+        // 출력 컨텍스트 생성 (컨테이너 먹서 명시, 2-pass 1차는 null)
+        let muxer_name = match pass {
+            EncoderPass::First(_) => "null",
+            _ => container.muxer_name(),
+        };
+        let mut output_ctx = ffmpeg::format::output_as(output_path, muxer_name)
+            .map_err(|e| format!("Failed to create output ({:?}): {}", container, e))?;
 
-        // H.264 인코더 찾기 (타입별 분기 + 자동 폴백)
-        let (codec, codec_name) = Self::find_h264_encoder(encoder_type)?;
+        // 비디오 인코더 찾기 (코덱/타입별 분기 + 자동 폴백)
+        let (codec, codec_name) = Self::find_video_encoder(video_codec, encoder_type)?;
 
         eprintln!(
-            "[ENCODER] 사용 인코더: {} (요청={:?})",
+            "[ENCODER] 사용 인코더: {} (요청={:?}/{:?}, {:?}, {:?})",
             codec_name,
-            encoder_type
+            video_codec,
+            encoder_type,
+            preset,
+            pixel_format
         );
 
+        // 인코더가 픽셀 포맷을 지원하는지 사전 확인 (open 실패보다 명확한 에러)
+        if let Some(mut formats) = codec.video().ok().and_then(|v| v.formats()) {
+            if !formats.any(|f| f == pixel_format) {
+                return Err(format!("{} 인코더가 {:?} 픽셀 포맷을 지원하지 않습니다", codec_name, pixel_format));
+            }
+        }
+
+        // HEVC in MP4/MOV: hvc1 태그여야 Apple 플레이어/일부 플랫폼에서 재생됨
+        let hvc1_tag = video_codec == VideoCodec::Hevc
+            && matches!(container, Container::Mp4 | Container::Mov);
+
         // 글로벌 헤더 플래그 사전 확인 (borrow 충돌 방지)
         let needs_global_header = output_ctx.format().flags()
             .contains(ffmpeg::format::flag::Flags::GLOBAL_HEADER);
@@ -114,40 +1466,46 @@ impl VideoEncoder {
 
         encoder.set_width(width);
         encoder.set_height(height);
-        encoder.set_format(Pixel::YUV420P);
+        encoder.set_format(pixel_format);
         encoder.set_time_base(time_base);
         encoder.set_frame_rate(Some(ffmpeg::Rational::new(fps_num, fps_den)));
+        if threads > 0 {
+            encoder.set_threading(ffmpeg::threading::Config {
+                kind: ffmpeg::threading::Type::Frame,
+                count: threads as usize,
+            });
+        }
 
-        // 인코더별 옵션 설정
+        // 인코더별 옵션 설정 (품질값은 코덱 고유 스케일로 변환)
         let mut opts = ffmpeg::Dictionary::new();
-        match codec_name.as_str() {
-            "libx264" => {
-                opts.set("crf", &crf.to_string());
-                opts.set("preset", "medium");
-            }
-            "h264_nvenc" => {
-                // NVENC: VBR + CQ (Constant Quality) 모드
-                opts.set("rc", "vbr");
-                opts.set("cq", &crf.to_string());
-                opts.set("preset", "p4"); // medium 상당
-                eprintln!("[ENCODER] NVENC CQ={}", crf);
-            }
-            "h264_qsv" => {
-                opts.set("global_quality", &crf.to_string());
-                opts.set("preset", "medium");
-                eprintln!("[ENCODER] QSV global_quality={}", crf);
-            }
-            "h264_amf" => {
-                let bitrate = Self::crf_to_bitrate(crf, width, height);
-                encoder.set_bit_rate(bitrate);
-                eprintln!("[ENCODER] AMF bitrate={}kbps", bitrate / 1000);
+        let codec_crf = video_codec.map_crf(crf);
+        let rate_plan = if video_codec.is_intra_only() {
+            // 인트라 전용: 모든 프레임이 키프레임 (편집/그레이딩 툴에서 프레임 단위 탐색)
+            apply_intermediate_options(video_codec, &codec_name, codec_profile, codec_crf, &mut opts);
+            encoder.set_gop(1);
+            RatePlan { bit_rate: 0, limits: None, pass_number: 0 }
+        } else {
+            let fallback_bitrate = Self::crf_to_bitrate(crf, width, height);
+            plan_rate_control(rate, preset, &codec_name, codec_crf, fallback_bitrate, pass, &mut opts)
+        };
+        rate_plan.apply(&mut encoder);
+
+        // 2-pass 2차 (libvpx/libaom): 1차 통계를 stats_in으로 전달 (open 시 인코더가 복사)
+        let stats_in = match pass {
+            EncoderPass::Second(stats) if uses_memory_stats(&codec_name) => {
+                let data = std::fs::read(stats)
+                    .map_err(|e| format!("2-pass 통계 읽기 실패: {}", e))?;
+                Some(std::ffi::CString::new(data)
+                    .map_err(|_| "2-pass 통계 형식이 잘못되었습니다".to_string())?)
             }
-            _ => {
-                let bitrate = Self::crf_to_bitrate(crf, width, height);
-                encoder.set_bit_rate(bitrate);
-                eprintln!("[ENCODER] {} bitrate={}kbps", codec_name, bitrate / 1000);
+            _ => None,
+        };
+        if let Some(stats_in) = &stats_in {
+            unsafe {
+                (*encoder.as_mut_ptr()).stats_in = stats_in.as_ptr() as *mut _;
             }
         }
+        let setup = EncoderSetup { codec, opts: opts.clone(), rate_plan };
 
         // 글로벌 헤더 플래그 (MP4 컨테이너 호환)
         if needs_global_header {
@@ -164,155 +1522,466 @@ impl VideoEncoder {
         );
 
         // open_as_with: 코덱 포인터를 명시적 전달
-        let encoder = encoder.open_as_with(codec, opts)
+        let mut encoder = encoder.open_as_with(codec, opts)
             .map_err(|e| format!("Failed to open encoder: {}", e))?;
+        if stats_in.is_some() {
+            // FFmpeg는 stats_in을 해제하지 않음 → CString 해제 전에 포인터 제거
+            unsafe {
+                (*encoder.as_mut_ptr()).stats_in = std::ptr::null_mut();
+            }
+        }
 
         eprintln!("[ENCODER] 비디오 인코더 열기 성공");
 
         // 스트림 파라미터 업데이트 (open 후 — extradata/SPS/PPS 반영)
         video_stream.set_parameters(&encoder);
+        if hvc1_tag {
+            unsafe {
+                (*(*video_stream.as_mut_ptr()).codecpar).codec_tag =
+                    u32::from_le_bytes(*b"hvc1");
+            }
+        }
 
-        // RGBA → YUV420P 스케일러 (BICUBIC: 색상 변환 품질 최적화)
+        // RGBA → 출력 픽셀 포맷 스케일러 (BICUBIC: 색상 변환 품질 최적화)
+        // 4:2:2/4:4:4: 크로마를 풀 해상도로 보간해야 그래픽 경계 색 번짐이 없음
+        let scaler_flags = if pixel_format == Pixel::YUV420P {
+            scaling::Flags::BICUBIC
+        } else {
+            scaling::Flags::BICUBIC | scaling::Flags::ACCURATE_RND | scaling::Flags::FULL_CHR_H_INT
+        };
         let scaler = scaling::Context::get(
             Pixel::RGBA,
             width,
             height,
-            Pixel::YUV420P,
+            pixel_format,
             width,
             height,
-            scaling::Flags::BICUBIC,
+            scaler_flags,
         )
         .map_err(|e| format!("Failed to create scaler: {}", e))?;
 
+        let yuv_scaler = if pixel_format == Pixel::YUV420P {
+            None
+        } else {
+            Some(scaling::Context::get(
+                Pixel::YUV420P,
+                width,
+                height,
+                pixel_format,
+                width,
+                height,
+                scaler_flags,
+            )
+            .map_err(|e| format!("Failed to create YUV scaler: {}", e))?)
+        };
+
         Ok(Self {
             output_ctx,
-            encoder,
-            audio_encoder: None,
-            scaler,
+            encoder: Some(encoder),
+            audio: None,
+            subtitle_streams: Vec::new(),
+            scaler: Some(scaler),
+            yuv_scaler,
+            pixel_format,
             video_stream_index,
-            audio_stream_index: None,
             frame_count: 0,
-            audio_pts: 0,
             time_base,
-            audio_time_base: None,
             width,
             height,
-            audio_buffer: Vec::new(),
-            audio_frame_size: 1024,
-            audio_channels: 2,
+            setup: Some(setup),
+            smart: None,
+            first_pass_stats: match pass {
+                EncoderPass::First(stats) if uses_memory_stats(&codec_name) => Some(stats.to_path_buf()),
+                _ => None,
+            },
         })
     }
 
-    /// AAC 오디오 인코더 초기화 (write_header 전에 호출)
-    /// - sample_rate: 48000
-    /// - channels: 2 (stereo)
-    /// - bitrate: 192000 (192kbps)
-    pub fn init_audio(&mut self, sample_rate: u32, channels: u32, bitrate: usize) -> Result<(), String> {
-        let codec = ffmpeg::encoder::find(codec::Id::AAC)
-            .ok_or("AAC 인코더를 찾을 수 없습니다")?;
+    /// 2-pass를 실제로 두 번 인코딩하는지 (TwoPass + libx264/x265/libvpx/libaom)
+    /// 하드웨어 인코더는 단일 패스 VBR로 처리 → 1차 패스 불필요
+    pub fn needs_first_pass(settings: &VideoSettings) -> bool {
+        ffmpeg::init().ok();
+        settings.rate.mode == RateControl::TwoPass
+            && !settings.codec.is_intra_only()
+            && Self::find_video_encoder(settings.codec, settings.encoder_type)
+                .is_ok_and(|(_, name)| TWO_PASS_ENCODERS.contains(&name.as_str()))
+    }
 
-        eprintln!("[ENCODER] AAC 인코더: {}", codec.name());
+    /// 병렬 구간 Export 가능 여부 (소프트웨어 인코더 + CRF / CRF 상한, 이미지 시퀀스 제외)
+    /// 하드웨어 인코더는 GPU 인코딩 세션이 병목 → 구간을 나눠도 빨라지지 않음
+    /// 2-pass/CBR은 구간마다 비트레이트 배분/HRD 버퍼가 새로 시작 → 전체 목표를 보장할 수 없음
+    pub fn supports_parallel_segments(settings: &VideoSettings) -> bool {
+        ffmpeg::init().ok();
+        matches!(settings.rate.mode, RateControl::Crf | RateControl::CappedCrf)
+            && !settings.codec.is_image()
+            && Self::find_video_encoder(settings.codec, settings.encoder_type)
+                .is_ok_and(|(_, name)| settings.codec.software_encoders().contains(&name.as_str()))
+    }
 
-        let needs_global_header = self.output_ctx.format().flags()
-            .contains(ffmpeg::format::flag::Flags::GLOBAL_HEADER);
+    /// 스마트 렌더 모드 전환 (write_header 전에 호출)
+    /// - stream_source: 출력 스트림 코덱 파라미터(avcC)를 가져올 복사 소스
+    /// - dts_delay: 복사 소스 재정렬 지연 최댓값 (SmartPlan::dts_delay)
+    /// 복사 패킷은 원본 그대로, 재인코딩 구간은 구간마다 새 인코더 (SPS/PPS 인밴드)
+    pub fn enable_smart_render(&mut self, stream_source: &Path, dts_delay: i64) -> Result<(), String> {
+        self.copy_stream_parameters(stream_source)?;
+
+        // MP4/MOV: 재인코딩 구간/복사 구간 모두 SPS/PPS를 in-band로 다시 넣음 (avcC와 다를 수 있음)
+        // avc1은 모든 파라미터 셋이 avcC에 있어야 함 → in-band를 허용하는 avc3 샘플 엔트리
+        if matches!(self.output_ctx.format().name(), "mp4" | "mov") {
+            let mut video_stream = self.output_ctx.stream_mut(self.video_stream_index)
+                .ok_or("Video stream not found")?;
+            unsafe {
+                (*(*video_stream.as_mut_ptr()).codecpar).codec_tag = u32::from_le_bytes(*b"avc3");
+            }
+        }
+        self.encoder = Some(self.open_part_encoder()?);
+        self.smart = Some(SmartStream { dts_delay, last_dts: i64::MIN, part_encoders: true });
+        eprintln!("[ENCODER] 스마트 렌더 활성화 (스트림 소스: {}, dts 지연 {}프레임)", stream_source.display(), dts_delay);
+        Ok(())
+    }
 
-        // 오디오 스트림 추가
-        let mut audio_stream = self.output_ctx.add_stream(codec)
-            .map_err(|e| format!("Failed to add audio stream: {}", e))?;
+    /// 병렬 구간 Export 결합용 출력 생성 (비디오 인코더 없이 먹서만, 오디오는 init_audio로 추가)
+    /// - first_segment: 출력 스트림 코덱 파라미터를 가져올 첫 구간 파일 (구간 파일은 모두 같은 설정)
+    /// - dts_delay: 구간 파일 재정렬 지연 최댓값 (probe_segment_delay)
+    /// 모든 프레임은 copy_packets(PacketCopy::open_segment)로 기록
+    pub fn for_segment_concat(
+        output_path: &str,
+        container: Container,
+        (width, height): (u32, u32),
+        fps: f64,
+        first_segment: &Path,
+        dts_delay: i64,
+    ) -> Result<Self, String> {
+        ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
 
-        let audio_stream_index = audio_stream.index();
-        let audio_time_base = ffmpeg::Rational::new(1, sample_rate as i32);
+        let mut output_ctx = ffmpeg::format::output_as(output_path, container.muxer_name())
+            .map_err(|e| format!("Failed to create output ({:?}): {}", container, e))?;
+        let video_stream_index = output_ctx.add_stream(None::<ffmpeg::Codec>)
+            .map_err(|e| format!("Failed to add video stream: {}", e))?
+            .index();
 
-        // 오디오 인코더 설정
-        let mut audio_enc = codec::context::Context::new_with_codec(codec)
+        let mut encoder = Self {
+            output_ctx,
+            encoder: None,
+            audio: None,
+            subtitle_streams: Vec::new(),
+            scaler: None,
+            yuv_scaler: None,
+            pixel_format: Pixel::None,
+            video_stream_index,
+            frame_count: 0,
+            time_base: ffmpeg::Rational::new(1000, (fps * 1000.0) as i32),
+            width,
+            height,
+            setup: None,
+            smart: Some(SmartStream { dts_delay, last_dts: i64::MIN, part_encoders: false }),
+            first_pass_stats: None,
+        };
+        encoder.copy_stream_parameters(first_segment)?;
+
+        // HEVC in MP4/MOV: hvc1 태그 (with_pass와 동일)
+        if matches!(container, Container::Mp4 | Container::Mov) {
+            let mut video_stream = encoder.output_ctx.stream_mut(video_stream_index)
+                .ok_or("Video stream not found")?;
+            if video_stream.parameters().id() == codec::Id::HEVC {
+                unsafe {
+                    (*(*video_stream.as_mut_ptr()).codecpar).codec_tag =
+                        u32::from_le_bytes(*b"hvc1");
+                }
+            }
+        }
+        eprintln!("[ENCODER] 구간 파일 결합 모드 (dts 지연 {}프레임)", dts_delay);
+        Ok(encoder)
+    }
+
+    /// 복사 소스 비디오 스트림 코덱 파라미터(avcC/hvcC 등)를 출력 스트림에 적용
+    /// 코덱 태그는 출력 설정 유지 (소스 컨테이너 태그 대신 avc1 기본값 / hvc1, 스마트 렌더는 이후 avc3)
+    fn copy_stream_parameters(&mut self, source: &Path) -> Result<(), String> {
+        let input_ctx = ffmpeg::format::input(&source)
+            .map_err(|e| format!("Failed to open copy source: {}", e))?;
+        let source_stream = input_ctx.streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or("No video stream found")?;
+        let mut video_stream = self.output_ctx.stream_mut(self.video_stream_index)
+            .ok_or("Video stream not found")?;
+        unsafe {
+            let codec_tag = (*(*video_stream.as_mut_ptr()).codecpar).codec_tag;
+            video_stream.set_parameters(source_stream.parameters());
+            (*(*video_stream.as_mut_ptr()).codecpar).codec_tag = codec_tag;
+        }
+        Ok(())
+    }
+
+    /// 재인코딩 구간용 인코더 열기 (스마트 렌더)
+    /// 글로벌 헤더 없이 첫 키프레임에 SPS/PPS 포함, B-프레임 없음 (dts = pts - dts_delay)
+    fn open_part_encoder(&self) -> Result<ffmpeg::encoder::Video, String> {
+        let setup = self.setup.as_ref().ok_or("구간 결합 출력은 비디오 인코더를 열 수 없습니다")?;
+        let mut encoder = codec::context::Context::new_with_codec(setup.codec)
             .encoder()
-            .audio()
-            .map_err(|e| format!("Failed to get audio encoder: {}", e))?;
+            .video()
+            .map_err(|e| format!("Failed to get video encoder: {}", e))?;
+        encoder.set_width(self.width);
+        encoder.set_height(self.height);
+        encoder.set_format(self.pixel_format);
+        encoder.set_time_base(self.time_base);
+        encoder.set_frame_rate(Some(self.time_base.invert()));
+        // 스마트 렌더는 단일 패스 (VBV 제약은 구간 인코더에도 동일하게)
+        RatePlan { pass_number: 0, ..setup.rate_plan }.apply(&mut encoder);
+        encoder.set_max_b_frames(0);
+        encoder.open_as_with(setup.codec, setup.opts.clone())
+            .map_err(|e| format!("Failed to open encoder: {}", e))
+    }
 
-        audio_enc.set_rate(sample_rate as i32);
-        audio_enc.set_channel_layout(ffmpeg::ChannelLayout::STEREO);
-        audio_enc.set_format(ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Planar));
-        audio_enc.set_bit_rate(bitrate);
-        audio_enc.set_time_base(audio_time_base);
+    /// 원본 패킷 복사 (스마트 렌더 복사 구간 / 병렬 Export 구간 파일, 재인코딩 없음)
+    /// - 첫 호출: 진행 중인 재인코딩 구간 flush → 다음 재인코딩 구간은 새 인코더 (IDR부터)
+    /// - until_frame: 디코딩 순서상 이 프레임 이전 패킷까지만 기록 (오디오와 나눠 인터리빙)
+    /// - 반환: 복사 구간 끝까지 기록했으면 true (출력 위치가 복사한 프레임 수만큼 이동)
+    pub fn copy_packets(&mut self, copy: &mut PacketCopy, until_frame: i64) -> Result<bool, String> {
+        if self.smart.is_none() {
+            return Err("스마트 렌더 모드가 아닙니다".to_string());
+        }
 
-        if needs_global_header {
-            unsafe {
-                (*audio_enc.as_mut_ptr()).flags |= codec::flag::Flags::GLOBAL_HEADER.bits() as i32;
+        let part_encoders = self.smart.as_ref().is_some_and(|s| s.part_encoders);
+        let base = match copy.base_frame {
+            Some(base) => base,
+            None if !part_encoders => {
+                copy.base_frame = Some(self.frame_count);
+                self.frame_count
             }
+            None => {
+                self.video_encoder()?.send_eof()
+                    .map_err(|e| format!("Failed to send video EOF: {}", e))?;
+                self.receive_and_write_video_packets()?;
+                self.encoder = Some(self.open_part_encoder()?);
+                copy.base_frame = Some(self.frame_count);
+                self.frame_count
+            }
+        };
+
+        let out_time_base = self.output_ctx.stream(self.video_stream_index)
+            .ok_or("Video stream not found")?
+            .time_base();
+        let scale = f64::from(copy.time_base) / f64::from(self.time_base);
+
+        while let Some(packet) = copy.next_packet() {
+            // 시작 키프레임 기준 상대 프레임 번호 (fps 일치 → 정수)
+            let (first_pts, first_dts) = copy.first_ts.unwrap_or_default();
+            let to_frames = |ts: i64| ((ts - first_pts) as f64 * scale).round() as i64;
+            let raw_pts = packet.pts().unwrap_or(first_pts);
+            let pts = to_frames(raw_pts);
+            // 디코딩 순서 슬롯 (시작 키프레임 = 0)
+            let slot = if copy.count_slots {
+                copy.packets
+            } else {
+                to_frames(packet.dts().unwrap_or(raw_pts)) - to_frames(first_dts)
+            };
+            if base + slot >= until_frame {
+                copy.pending = Some(packet);
+                return Ok(false);
+            }
+
+            let smart = self.smart.as_mut().ok_or("스마트 렌더 모드가 아닙니다")?;
+            let dts = smart.next_dts(base + slot - smart.dts_delay);
+
+            // 구간 첫 키프레임: 원본 SPS/PPS를 다시 넣어 재인코딩 구간의 SPS/PPS를 대체
+            let mut packet = match copy.parameter_sets.take() {
+                Some(mut data) => {
+                    data.extend_from_slice(packet.data().unwrap_or(&[]));
+                    let mut with_headers = ffmpeg::Packet::copy(&data);
+                    with_headers.set_flags(packet.flags());
+                    with_headers
+                }
+                None => packet,
+            };
+            packet.set_stream(self.video_stream_index);
+            packet.set_pts(Some(base + pts));
+            packet.set_dts(Some(dts));
+            packet.set_duration(1);
+            packet.set_position(-1);
+            packet.rescale_ts(self.time_base, out_time_base);
+            packet.write_interleaved(&mut self.output_ctx)
+                .map_err(|e| format!("Failed to write copied packet: {}", e))?;
+            copy.frames = copy.frames.max(pts + 1);
+            copy.packets += 1;
         }
 
-        let audio_enc = audio_enc.open_as_with(codec, ffmpeg::Dictionary::new())
-            .map_err(|e| format!("Failed to open audio encoder: {}", e))?;
+        self.frame_count = base + copy.frames;
+        eprintln!("[ENCODER] 패킷 복사 완료: {}프레임 (출력 {}~)", copy.frames, base);
+        Ok(true)
+    }
 
-        // AAC 프레임 크기 (보통 1024)
-        let frame_size = unsafe { (*audio_enc.as_ptr()).frame_size as usize };
-        let frame_size = if frame_size > 0 { frame_size } else { 1024 };
+    /// 오디오 인코더 초기화 (write_header 전에 호출)
+    /// - settings.codec: AAC / Opus / MP3 / FLAC / PCM (Auto는 호출 전에 resolve로 확정)
+    /// - 입력(encode_audio_samples)은 항상 48kHz 스테레오, 다르면 내부에서 리샘플/채널 변환
+    pub fn init_audio(&mut self, settings: AudioSettings) -> Result<(), String> {
+        self.audio = Some(AudioStream::new(&mut self.output_ctx, settings)?);
+        Ok(())
+    }
+
+    /// 소프트 자막 스트림 추가 (write_header 전에 호출)
+    /// - language: "ko"/"kor" 등 (ISO 639-2로 변환해 메타데이터 기록)
+    /// - 반환: 자막 스트림 인덱스 (write_subtitle_cue에 전달)
+    pub fn add_subtitle_stream(
+        &mut self,
+        language: &str,
+        title: Option<&str>,
+        subtitle_codec: SubtitleCodec,
+    ) -> Result<usize, String> {
+        let codec_id = subtitle_codec.codec_id();
+        let codec = ffmpeg::encoder::find(codec_id)
+            .ok_or_else(|| format!("자막 인코더를 찾을 수 없습니다: {:?}", codec_id))?;
+
+        let mut stream = self.output_ctx.add_stream(codec)
+            .map_err(|e| format!("Failed to add subtitle stream: {}", e))?;
+        let stream_index = stream.index();
+
+        let mut sub_enc = codec::context::Context::new_with_codec(codec)
+            .encoder()
+            .subtitle()
+            .map_err(|e| format!("Failed to get subtitle encoder: {}", e))?;
+
+        // 텍스트 자막 인코더는 ASS 헤더(subtitle_header)로 스타일 초기화 → 없으면 open 실패
+        // 컨텍스트 해제 시 FFmpeg가 av_free하므로 av_malloc으로 복사
+        let header = ass_header(self.width, self.height);
+        unsafe {
+            let ctx = sub_enc.as_mut_ptr();
+            (*ctx).time_base = ffmpeg::Rational::new(1, 1000).into();
+            let buf = ffmpeg::ffi::av_mallocz(header.len() + 1) as *mut u8;
+            if buf.is_null() {
+                return Err("자막 헤더 메모리 할당 실패".to_string());
+            }
+            std::ptr::copy_nonoverlapping(header.as_ptr(), buf, header.len());
+            (*ctx).subtitle_header = buf;
+            (*ctx).subtitle_header_size = header.len() as i32;
+        }
+
+        let sub_enc = sub_enc.open_as(codec)
+            .map_err(|e| format!("Failed to open subtitle encoder: {}", e))?;
+
+        stream.set_parameters(&sub_enc);
+        stream.set_time_base(ffmpeg::Rational::new(1, 1000));
+
+        let mut metadata = ffmpeg::Dictionary::new();
+        metadata.set("language", &iso639_2(language));
+        if let Some(title) = title.filter(|t| !t.is_empty()) {
+            metadata.set("title", &title.replace('\0', ""));
+        }
+        stream.set_metadata(metadata);
 
         eprintln!(
-            "[ENCODER] AAC 오디오 인코더 성공: {}Hz {}ch, {}kbps, frame_size={}",
-            sample_rate, channels, bitrate / 1000, frame_size
+            "[ENCODER] 자막 스트림 추가: #{} {:?} lang={}",
+            stream_index, subtitle_codec, iso639_2(language)
         );
 
-        audio_stream.set_parameters(&audio_enc);
+        self.subtitle_streams.push(SubtitleStream {
+            encoder: sub_enc,
+            stream_index,
+            buffer: vec![0u8; 64 * 1024],
+        });
+        Ok(self.subtitle_streams.len() - 1)
+    }
+
+    /// 자막 cue 1개 인코딩 → 출력에 기록 (write_header 후, 시작 시간 순서대로 호출)
+    pub fn write_subtitle_cue(&mut self, subtitle_index: usize, cue: &SubtitleCue) -> Result<(), String> {
+        let sub = self.subtitle_streams.get_mut(subtitle_index)
+            .ok_or("Subtitle stream not found")?;
+
+        let duration_ms = (cue.end_ms - cue.start_ms).max(1);
+
+        // ASS 대사 형식 (ReadOrder,Layer,Style,Name,MarginL,MarginR,MarginV,Effect,Text)
+        // → 각 인코더가 mov_text/SRT/WebVTT 페이로드로 변환
+        let mut subtitle = ffmpeg::Subtitle::new();
+        subtitle.set_pts(Some(cue.start_ms * 1000)); // AV_TIME_BASE (us)
+        subtitle.set_start(0);
+        subtitle.set_end(duration_ms as u32);
+        if let ffmpeg::codec::subtitle::RectMut::Ass(mut rect) = subtitle.add_rect(ffmpeg::codec::subtitle::Type::Ass) {
+            rect.set(&format!("0,0,Default,,0,0,0,,{}", ass_escape(&cue.text)));
+        }
+
+        let written = unsafe {
+            let n = ffmpeg::ffi::avcodec_encode_subtitle(
+                sub.encoder.as_mut_ptr(),
+                sub.buffer.as_mut_ptr(),
+                sub.buffer.len() as i32,
+                subtitle.as_ptr(),
+            );
+            ffmpeg::ffi::avsubtitle_free(subtitle.as_mut_ptr());
+            n
+        };
+        if written < 0 {
+            return Err(format!("Failed to encode subtitle ({}ms): {}", cue.start_ms, ffmpeg::Error::from(written)));
+        }
 
-        self.audio_encoder = Some(audio_enc);
-        self.audio_stream_index = Some(audio_stream_index);
-        self.audio_time_base = Some(audio_time_base);
-        self.audio_frame_size = frame_size;
-        self.audio_channels = channels;
+        let mut packet = ffmpeg::Packet::copy(&sub.buffer[..written as usize]);
+        packet.set_stream(sub.stream_index);
+        packet.set_pts(Some(cue.start_ms));
+        packet.set_dts(Some(cue.start_ms));
+        packet.set_duration(duration_ms);
+        packet.rescale_ts(
+            ffmpeg::Rational::new(1, 1000),
+            self.output_ctx.stream(sub.stream_index)
+                .ok_or("Subtitle stream not found")?
+                .time_base(),
+        );
+        packet.write_interleaved(&mut self.output_ctx)
+            .map_err(|e| format!("Failed to write subtitle packet: {}", e))?;
 
         Ok(())
     }
 
-    /// H.264 인코더 찾기 (EncoderType에 따라 분기 + 자동 폴백)
+    /// 비디오 인코더 찾기 (VideoCodec + EncoderType에 따라 분기 + 자동 폴백)
+    /// 설치된 인코더 중 select_video_encoder 순서로 선택, 없으면 generic
     /// 반환: (Codec, codec_name)
-    fn find_h264_encoder(encoder_type: EncoderType) -> Result<(ffmpeg::Codec, String), String> {
-        match encoder_type {
-            EncoderType::Auto => {
-                // GPU 우선: NVENC → QSV → AMF → libx264 → generic
-                let try_order = ["h264_nvenc", "h264_qsv", "h264_amf", "libx264"];
-                for name in &try_order {
-                    if let Some(codec) = ffmpeg::encoder::find_by_name(name) {
-                        return Ok((codec, name.to_string()));
-                    }
-                }
-                // 최후의 폴백: generic H264
-                if let Some(codec) = ffmpeg::encoder::find(codec::Id::H264) {
-                    return Ok((codec, codec.name().to_string()));
-                }
-                Err("H.264 인코더를 찾을 수 없습니다".to_string())
-            }
-            EncoderType::Software => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("libx264") {
-                    return Ok((codec, "libx264".to_string()));
-                }
-                if let Some(codec) = ffmpeg::encoder::find(codec::Id::H264) {
-                    return Ok((codec, codec.name().to_string()));
-                }
-                Err("libx264 인코더를 찾을 수 없습니다".to_string())
-            }
-            EncoderType::Nvenc => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("h264_nvenc") {
-                    return Ok((codec, "h264_nvenc".to_string()));
-                }
-                eprintln!("[ENCODER] h264_nvenc 없음 → libx264 폴백");
-                Self::find_h264_encoder(EncoderType::Software)
+    fn find_video_encoder(
+        video_codec: VideoCodec,
+        encoder_type: EncoderType,
+    ) -> Result<(ffmpeg::Codec, String), String> {
+        let installed = |name: &str| ffmpeg::encoder::find_by_name(name).is_some();
+        if let Some(name) = Self::select_video_encoder(video_codec, encoder_type, installed) {
+            if let Some(codec) = ffmpeg::encoder::find_by_name(name) {
+                return Ok((codec, name.to_string()));
             }
-            EncoderType::Qsv => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("h264_qsv") {
-                    return Ok((codec, "h264_qsv".to_string()));
+        }
+
+        // 최후의 폴백: 코덱 ID 기준 generic 인코더
+        if let Some(codec) = ffmpeg::encoder::find(video_codec.codec_id()) {
+            return Ok((codec, codec.name().to_string()));
+        }
+        Err(format!("{:?} 인코더를 찾을 수 없습니다", video_codec))
+    }
+
+    /// 인코더 이름 선택 (available: 인코더 존재 여부, 없으면 다음 후보)
+    /// Auto: NVENC → QSV → AMF → 소프트웨어
+    /// 하드웨어 지정: 없으면 같은 코덱의 소프트웨어 인코더로 폴백
+    fn select_video_encoder(
+        video_codec: VideoCodec,
+        encoder_type: EncoderType,
+        available: impl Fn(&str) -> bool,
+    ) -> Option<&'static str> {
+        let hardware: Vec<&'static str> = match encoder_type {
+            EncoderType::Auto => [EncoderType::Nvenc, EncoderType::Qsv, EncoderType::Amf]
+                .iter()
+                .filter_map(|t| video_codec.hardware_encoder(*t))
+                .collect(),
+            EncoderType::Software => Vec::new(),
+            hw => match video_codec.hardware_encoder(hw) {
+                Some(name) => vec![name],
+                None => {
+                    eprintln!("[ENCODER] {:?}는 {:?} 미지원 → 소프트웨어 폴백", hw, video_codec);
+                    Vec::new()
                 }
-                eprintln!("[ENCODER] h264_qsv 없음 → libx264 폴백");
-                Self::find_h264_encoder(EncoderType::Software)
+            },
+        };
+
+        for &name in hardware.iter().chain(video_codec.software_encoders()) {
+            if available(name) {
+                return Some(name);
             }
-            EncoderType::Amf => {
-                if let Some(codec) = ffmpeg::encoder::find_by_name("h264_amf") {
-                    return Ok((codec, "h264_amf".to_string()));
-                }
-                eprintln!("[ENCODER] h264_amf 없음 → libx264 폴백");
-                Self::find_h264_encoder(EncoderType::Software)
+            if hardware.contains(&name) && encoder_type != EncoderType::Auto {
+                eprintln!("[ENCODER] {} 없음 → 소프트웨어 폴백", name);
             }
         }
+        None
     }
 
     /// CRF → 대략적 bitrate 변환 (비 libx264 인코더용)
@@ -340,7 +2009,7 @@ impl VideoEncoder {
         Ok(())
     }
 
-    /// RGBA 프레임 인코딩 → MP4에 기록
+    /// RGBA 프레임 인코딩 → 출력에 기록
     pub fn encode_frame(&mut self, rgba_data: &[u8], width: u32, height: u32) -> Result<(), String> {
         // 해상도 검증
         if width != self.width || height != self.height {
@@ -372,9 +2041,10 @@ impl VideoEncoder {
             }
         }
 
-        // RGBA → YUV420P 변환
+        // RGBA → 출력 픽셀 포맷 변환
         let mut yuv_frame = ffmpeg::frame::Video::empty();
-        self.scaler.run(&src_frame, &mut yuv_frame)
+        self.scaler.as_mut().ok_or("비디오 인코더가 없습니다")?
+            .run(&src_frame, &mut yuv_frame)
             .map_err(|e| format!("Scaler failed: {}", e))?;
 
         // PTS 설정
@@ -382,7 +2052,7 @@ impl VideoEncoder {
         self.frame_count += 1;
 
         // 인코더에 프레임 전송
-        self.encoder.send_frame(&yuv_frame)
+        self.video_encoder()?.send_frame(&yuv_frame)
             .map_err(|e| format!("Failed to send frame (pts={}): {}", self.frame_count, e))?;
 
         // 인코딩된 패킷 수신 → 출력에 기록
@@ -398,6 +2068,7 @@ impl VideoEncoder {
 
     /// YUV420P 프레임 직접 인코딩 (Export용 — RGBA→YUV 변환 건너뜀)
     /// yuv_data 레이아웃: [Y: w*h][U: w/2*h/2][V: w/2*h/2]
+    /// 출력 픽셀 포맷이 YUV420P가 아니면 (4:2:2/10bit 등) 포맷만 변환
     pub fn encode_frame_yuv(&mut self, yuv_data: &[u8], width: u32, height: u32) -> Result<(), String> {
         if width != self.width || height != self.height {
             return Err(format!(
@@ -459,12 +2130,20 @@ impl VideoEncoder {
             }
         }
 
+        // 출력 픽셀 포맷 변환 (YUV420P → 422P/444P/10bit)
+        if let Some(ref mut yuv_scaler) = self.yuv_scaler {
+            let mut converted = ffmpeg::frame::Video::empty();
+            yuv_scaler.run(&yuv_frame, &mut converted)
+                .map_err(|e| format!("YUV scaler failed: {}", e))?;
+            yuv_frame = converted;
+        }
+
         // PTS 설정
         yuv_frame.set_pts(Some(self.frame_count));
         self.frame_count += 1;
 
         // 인코더에 프레임 전송
-        self.encoder.send_frame(&yuv_frame)
+        self.video_encoder()?.send_frame(&yuv_frame)
             .map_err(|e| format!("Failed to send YUV frame (pts={}): {}", self.frame_count, e))?;
 
         self.receive_and_write_video_packets()?;
@@ -476,133 +2155,49 @@ impl VideoEncoder {
         Ok(())
     }
 
-    /// f32 PCM 오디오 인코딩 → MP4에 기록
-    /// samples: interleaved stereo f32 (L, R, L, R, ...)
+    /// f32 PCM 오디오 인코딩 → 출력 파일에 기록
+    /// samples: interleaved stereo f32 48kHz (L, R, L, R, ...) — AudioMixer 출력 그대로
     pub fn encode_audio_samples(&mut self, samples: &[f32]) -> Result<(), String> {
         // 오디오 인코더 없으면 스킵
-        let mut audio_enc = match self.audio_encoder.take() {
-            Some(e) => e,
-            None => return Ok(()),
-        };
-
-        self.audio_buffer.extend_from_slice(samples);
-
-        let result = self.flush_audio_buffer(&mut audio_enc);
-
-        // 인코더를 다시 넣기 (에러 여부 무관)
-        self.audio_encoder = Some(audio_enc);
-
-        result
-    }
-
-    /// 오디오 버퍼에서 완전한 AAC 프레임만큼 인코딩
-    fn flush_audio_buffer(&mut self, audio_enc: &mut ffmpeg::encoder::Audio) -> Result<(), String> {
-        let frame_size = self.audio_frame_size;
-        let channels = self.audio_channels as usize;
-        let samples_per_frame = frame_size * channels; // interleaved 기준
-        let audio_stream_idx = match self.audio_stream_index {
-            Some(idx) => idx,
-            None => return Ok(()),
-        };
-        let audio_tb = match self.audio_time_base {
-            Some(tb) => tb,
-            None => return Ok(()),
-        };
-
-        while self.audio_buffer.len() >= samples_per_frame {
-            // FLTP 오디오 프레임 생성
-            let mut frame = ffmpeg::frame::Audio::new(
-                ffmpeg::format::Sample::F32(ffmpeg::format::sample::Type::Planar),
-                frame_size,
-                ffmpeg::ChannelLayout::STEREO,
-            );
-            frame.set_pts(Some(self.audio_pts));
-            frame.set_rate(48000);
-            self.audio_pts += frame_size as i64;
-
-            // Deinterleave: (L,R,L,R,...) → plane0=[L,L,...], plane1=[R,R,...]
-            for ch in 0..channels {
-                let plane = frame.data_mut(ch);
-                let plane_f32 = unsafe {
-                    std::slice::from_raw_parts_mut(
-                        plane.as_mut_ptr() as *mut f32,
-                        frame_size,
-                    )
-                };
-                for i in 0..frame_size {
-                    plane_f32[i] = self.audio_buffer[i * channels + ch];
-                }
-            }
-
-            self.audio_buffer.drain(..samples_per_frame);
-
-            // 인코더에 프레임 전송
-            audio_enc.send_frame(&frame)
-                .map_err(|e| format!("Failed to send audio frame: {}", e))?;
-
-            // 인코딩된 패킷 수신 → 출력에 기록
-            let mut packet = ffmpeg::Packet::empty();
-            while audio_enc.receive_packet(&mut packet).is_ok() {
-                packet.set_stream(audio_stream_idx);
-                packet.rescale_ts(
-                    audio_tb,
-                    self.output_ctx.stream(audio_stream_idx)
-                        .ok_or("Audio stream not found")?
-                        .time_base(),
-                );
-                packet.write_interleaved(&mut self.output_ctx)
-                    .map_err(|e| format!("Failed to write audio packet: {}", e))?;
-            }
+        match self.audio.as_mut() {
+            Some(audio) => audio.encode_samples(&mut self.output_ctx, samples),
+            None => Ok(()),
         }
-
-        Ok(())
     }
 
     /// 인코딩 완료 (flush + trailer)
     pub fn finish(&mut self) -> Result<(), String> {
         eprintln!("[ENCODER] finish 호출 (비디오 {}프레임, 오디오 {}샘플)",
-            self.frame_count, self.audio_pts);
+            self.frame_count, self.audio.as_ref().map_or(0, |a| a.pts));
+
+        // 비디오 flush (구간 결합 출력은 인코더 없음)
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.send_eof()
+                .map_err(|e| format!("Failed to send video EOF: {}", e))?;
+            self.receive_and_write_video_packets()?;
+            eprintln!("[ENCODER] 비디오 flush 완료");
+        }
 
-        // 비디오 flush
-        self.encoder.send_eof()
-            .map_err(|e| format!("Failed to send video EOF: {}", e))?;
-        self.receive_and_write_video_packets()?;
-        eprintln!("[ENCODER] 비디오 flush 완료");
+        // 2-pass 1차: 인코더가 flush 때 채운 통계 (libvpx/libaom) → 파일
+        if let (Some(stats_path), Some(encoder)) = (&self.first_pass_stats, &self.encoder) {
+            let stats = unsafe {
+                let out = (*encoder.as_ptr()).stats_out;
+                if out.is_null() {
+                    Vec::new()
+                } else {
+                    std::ffi::CStr::from_ptr(out).to_bytes().to_vec()
+                }
+            };
+            if stats.is_empty() {
+                return Err("2-pass 1차 통계가 비어 있습니다".to_string());
+            }
+            std::fs::write(stats_path, stats)
+                .map_err(|e| format!("2-pass 통계 기록 실패: {}", e))?;
+        }
 
         // 오디오 flush (잔여 버퍼 + EOF)
-        if let Some(mut audio_enc) = self.audio_encoder.take() {
-            // 잔여 샘플을 0으로 패딩하여 마지막 프레임 완성
-            let channels = self.audio_channels as usize;
-            let remaining = self.audio_buffer.len() / channels;
-            if remaining > 0 {
-                let pad = (self.audio_frame_size - remaining) * channels;
-                self.audio_buffer.extend(std::iter::repeat(0.0f32).take(pad));
-                self.flush_audio_buffer(&mut audio_enc)?;
-            }
-
-            // 오디오 EOF
-            audio_enc.send_eof()
-                .map_err(|e| format!("Failed to send audio EOF: {}", e))?;
-
-            // 잔여 오디오 패킷 기록 (unwrap 제거 — init_audio 실패 시 안전)
-            let (audio_stream_idx, audio_tb) = match (self.audio_stream_index, self.audio_time_base) {
-                (Some(idx), Some(tb)) => (idx, tb),
-                _ => return Err("Audio stream not initialized".to_string()),
-            };
-            let mut packet = ffmpeg::Packet::empty();
-            while audio_enc.receive_packet(&mut packet).is_ok() {
-                packet.set_stream(audio_stream_idx);
-                packet.rescale_ts(
-                    audio_tb,
-                    self.output_ctx.stream(audio_stream_idx)
-                        .ok_or("Audio stream not found")?
-                        .time_base(),
-                );
-                packet.write_interleaved(&mut self.output_ctx)
-                    .map_err(|e| format!("Failed to write audio packet: {}", e))?;
-            }
-
-            self.audio_encoder = Some(audio_enc);
+        if let Some(audio) = self.audio.as_mut() {
+            audio.finish(&mut self.output_ctx)?;
             eprintln!("[ENCODER] 오디오 flush 완료");
         }
 
@@ -616,8 +2211,21 @@ impl VideoEncoder {
 
     /// 비디오 패킷 수신 → 출력 파일에 기록
     fn receive_and_write_video_packets(&mut self) -> Result<(), String> {
+        let Some(encoder) = self.encoder.as_mut() else {
+            return Ok(());
+        };
         let mut packet = ffmpeg::Packet::empty();
-        while self.encoder.receive_packet(&mut packet).is_ok() {
+        while encoder.receive_packet(&mut packet).is_ok() {
+            if let Some(smart) = self.smart.as_mut() {
+                // 스마트 렌더: Annex B → 출력 스트림(avcC) 형식, dts는 복사 패킷과 같은 지연
+                let pts = packet.pts().unwrap_or(0);
+                let mut converted = ffmpeg::Packet::copy(&annexb_to_length_prefixed(packet.data().unwrap_or(&[])));
+                converted.set_flags(packet.flags());
+                converted.set_pts(Some(pts));
+                converted.set_dts(Some(smart.next_dts(pts - smart.dts_delay)));
+                converted.set_duration(1);
+                packet = converted;
+            }
             packet.set_stream(self.video_stream_index);
             packet.rescale_ts(
                 self.time_base,
@@ -631,8 +2239,663 @@ impl VideoEncoder {
         Ok(())
     }
 
+    /// 비디오 인코더 (구간 결합 출력이면 에러)
+    fn video_encoder(&mut self) -> Result<&mut ffmpeg::encoder::Video, String> {
+        self.encoder.as_mut().ok_or_else(|| "비디오 인코더가 없습니다".to_string())
+    }
+
     /// 너비 반환
     pub fn width(&self) -> u32 { self.width }
     /// 높이 반환
     pub fn height(&self) -> u32 { self.height }
 }
+
+/// 코덱 파라미터의 확장 데이터 (H.264 avcC 등, 없으면 빈 슬라이스)
+fn codec_extradata(parameters: &codec::Parameters) -> &[u8] {
+    unsafe {
+        let par = parameters.as_ptr();
+        if (*par).extradata.is_null() || (*par).extradata_size <= 0 {
+            &[]
+        } else {
+            std::slice::from_raw_parts((*par).extradata, (*par).extradata_size as usize)
+        }
+    }
+}
+
+/// 스마트 렌더 복사 후보 소스 조사 (코덱/해상도/픽셀 포맷 + 패킷 스캔으로 GOP 경계)
+/// 디코딩 없이 패킷 헤더만 읽으므로 소스 길이보다 훨씬 빠름
+/// fps: Export fps (재정렬 지연을 프레임 수로 환산)
+pub fn probe_copy_source(path: &Path, fps: f64) -> Result<CopySource, String> {
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+    let mut input_ctx = ffmpeg::format::input(&path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    let (stream_index, time_base, mut source) = {
+        let stream = input_ctx.streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or("No video stream found")?;
+        let parameters = stream.parameters();
+        let (width, height, yuv420p_square) = unsafe {
+            let par = parameters.as_ptr();
+            let sar = (*par).sample_aspect_ratio;
+            (
+                (*par).width as u32,
+                (*par).height as u32,
+                (*par).format == ffmpeg::ffi::AVPixelFormat::AV_PIX_FMT_YUV420P as i32
+                    && (sar.num == 0 || sar.num == sar.den),
+            )
+        };
+        let source = CopySource {
+            is_h264: parameters.id() == codec::Id::H264,
+            width,
+            height,
+            fps: f64::from(stream.avg_frame_rate()),
+            yuv420p_square,
+            length_prefixed: avcc_parameter_sets(codec_extradata(&parameters)).is_some(),
+            gop_boundaries_ms: Vec::new(),
+            reorder_delay: 0,
+        };
+        (stream.index(), stream.time_base(), source)
+    };
+
+    // 패킷 스캔: 키프레임 + 스트림 끝 = GOP 경계 (PacketCopy와 같은 ms 변환)
+    let mut first_key = true;
+    let mut end_ts = None;
+    for (stream, packet) in input_ctx.packets() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        let Some(pts) = packet.pts() else { continue };
+        if packet.is_key() {
+            source.gop_boundaries_ms.push(ts_to_ms(pts, time_base));
+            if first_key {
+                let dts = packet.dts().unwrap_or(pts);
+                source.reorder_delay = ((pts - dts) as f64 * f64::from(time_base) * fps).round().max(0.0) as i64;
+                first_key = false;
+            }
+        }
+        let packet_end = pts + packet.duration();
+        end_ts = Some(end_ts.map_or(packet_end, |e: i64| e.max(packet_end)));
+    }
+    if let Some(end_ts) = end_ts {
+        source.gop_boundaries_ms.push(ts_to_ms(end_ts, time_base));
+    }
+    source.gop_boundaries_ms.sort_unstable();
+    source.gop_boundaries_ms.dedup();
+
+    Ok(source)
+}
+
+/// 병렬 Export 구간 파일의 재정렬 지연 (프레임, 패킷 헤더만 스캔)
+/// 디코딩 순서 n번째 패킷의 상대 pts가 n보다 작은 최대 차이 → 모든 구간에 같은 dts 지연 적용
+pub fn probe_segment_delay(path: &Path, fps: f64) -> Result<i64, String> {
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+    let mut input_ctx = ffmpeg::format::input(&path)
+        .map_err(|e| format!("Failed to open segment file: {}", e))?;
+    let (stream_index, time_base) = {
+        let stream = input_ctx.streams()
+            .best(ffmpeg::media::Type::Video)
+            .ok_or("No video stream found")?;
+        (stream.index(), stream.time_base())
+    };
+
+    let scale = f64::from(time_base) * fps;
+    let mut first_pts = None;
+    let mut decoded: i64 = 0;
+    let mut delay: i64 = 0;
+    for (stream, packet) in input_ctx.packets() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        let Some(pts) = packet.pts() else { continue };
+        let start = *first_pts.get_or_insert(pts);
+        let frame = ((pts - start) as f64 * scale).round() as i64;
+        delay = delay.max(decoded - frame);
+        decoded += 1;
+    }
+    Ok(delay)
+}
+
+/// 스트림 time_base 타임스탬프 → ms (decoder.rs와 같은 원본 시간 기준)
+fn ts_to_ms(ts: i64, time_base: ffmpeg::Rational) -> i64 {
+    ts * 1000 * i64::from(time_base.numerator()) / i64::from(time_base.denominator())
+}
+
+/// 복사 구간 1개의 원본 패킷 읽기 상태 (VideoEncoder::copy_packets에 반복 전달)
+pub struct PacketCopy {
+    input_ctx: ffmpeg::format::context::Input,
+    stream_index: usize,
+    time_base: ffmpeg::Rational,
+    source_start_ms: i64,
+    source_end_ms: i64,
+    /// 구간 첫 키프레임 앞에 붙일 원본 SPS/PPS (기록 후 None)
+    parameter_sets: Option<Vec<u8>>,
+    /// 시작 키프레임 (pts, dts)
+    first_ts: Option<(i64, i64)>,
+    /// until_frame을 넘어 다음 호출로 미룬 패킷
+    pending: Option<ffmpeg::Packet>,
+    /// 출력 시작 프레임 (첫 copy_packets 호출 시 확정)
+    base_frame: Option<i64>,
+    /// 기록한 프레임 수 (최대 상대 pts + 1)
+    frames: i64,
+    /// 기록한 패킷 수
+    packets: i64,
+    /// 디코딩 순서 슬롯을 패킷 수로 계산 (구간 파일: 1패킷 = 1프레임, Matroska dts 반올림/누락 무관)
+    count_slots: bool,
+    finished: bool,
+}
+
+impl PacketCopy {
+    /// 복사 소스 열기 + 시작 키프레임 위치로 seek
+    /// source_start_ms / source_end_ms: probe_copy_source의 GOP 경계 (SmartSegment::Copy)
+    pub fn open(path: &Path, source_start_ms: i64, source_end_ms: i64) -> Result<Self, String> {
+        let mut input_ctx = ffmpeg::format::input(&path)
+            .map_err(|e| format!("Failed to open copy source: {}", e))?;
+        let (stream_index, time_base, parameter_sets) = {
+            let stream = input_ctx.streams()
+                .best(ffmpeg::media::Type::Video)
+                .ok_or("No video stream found")?;
+            let parameter_sets = avcc_parameter_sets(codec_extradata(&stream.parameters()))
+                .ok_or("avcC 확장 데이터가 없는 소스는 복사할 수 없습니다")?;
+            (stream.index(), stream.time_base(), parameter_sets)
+        };
+
+        // AV_TIME_BASE(μs) 단위 seek → 시작 키프레임 이하의 가장 가까운 키프레임
+        let timestamp_us = source_start_ms * 1000;
+        input_ctx.seek(timestamp_us, ..timestamp_us)
+            .map_err(|e| format!("Copy source seek failed: {}", e))?;
+
+        Ok(Self {
+            input_ctx,
+            stream_index,
+            time_base,
+            source_start_ms,
+            source_end_ms,
+            parameter_sets: Some(parameter_sets),
+            first_ts: None,
+            pending: None,
+            base_frame: None,
+            frames: 0,
+            packets: 0,
+            count_slots: false,
+            finished: false,
+        })
+    }
+
+    /// 병렬 Export 구간 파일 열기 (파일 전체 복사, 출력과 같은 코덱 설정이라 SPS/PPS 삽입 없음)
+    pub fn open_segment(path: &Path) -> Result<Self, String> {
+        let input_ctx = ffmpeg::format::input(&path)
+            .map_err(|e| format!("Failed to open segment file: {}", e))?;
+        let (stream_index, time_base) = {
+            let stream = input_ctx.streams()
+                .best(ffmpeg::media::Type::Video)
+                .ok_or("No video stream found")?;
+            (stream.index(), stream.time_base())
+        };
+
+        Ok(Self {
+            input_ctx,
+            stream_index,
+            time_base,
+            source_start_ms: i64::MIN,
+            source_end_ms: i64::MAX,
+            parameter_sets: None,
+            first_ts: None,
+            pending: None,
+            base_frame: None,
+            frames: 0,
+            packets: 0,
+            count_slots: true,
+            finished: false,
+        })
+    }
+
+    /// 복사한 프레임 수 (copy_packets가 true를 반환한 뒤 확정)
+    pub fn copied_frames(&self) -> i64 {
+        self.frames
+    }
+
+    /// 다음 복사 대상 패킷 (디코딩 순서, 구간 끝이면 None)
+    /// - 시작 키프레임 이전 패킷, 시작 키프레임보다 앞에 표시되는 leading 프레임은 건너뜀
+    /// - 끝 GOP 경계 키프레임 또는 스트림 끝에서 종료
+    fn next_packet(&mut self) -> Option<ffmpeg::Packet> {
+        if let Some(packet) = self.pending.take() {
+            return Some(packet);
+        }
+        if self.finished {
+            return None;
+        }
+
+        for (stream, packet) in self.input_ctx.packets() {
+            if stream.index() != self.stream_index {
+                continue;
+            }
+            let Some(pts) = packet.pts() else { continue };
+            let pts_ms = ts_to_ms(pts, self.time_base);
+
+            match self.first_ts {
+                None => {
+                    if !packet.is_key() || pts_ms < self.source_start_ms {
+                        continue;
+                    }
+                    self.first_ts = Some((pts, packet.dts().unwrap_or(pts)));
+                }
+                Some((first_pts, _)) => {
+                    if packet.is_key() && pts_ms >= self.source_end_ms {
+                        self.finished = true;
+                        return None;
+                    }
+                    if pts < first_pts {
+                        continue;
+                    }
+                }
+            }
+            return Some(packet);
+        }
+
+        self.finished = true;
+        None
+    }
+}
+
+/// RGBA 프레임 1장 → 이미지 파일 데이터 (PNG / JPEG / TIFF, 먹서 없이 패킷 그대로)
+/// - pixel_format: resolve_pixel_format으로 확정된 값
+/// - crf: JPEG 품질 (H.264 CRF 스케일, map_crf로 qscale 변환), PNG/TIFF는 무시
+pub fn encode_still_image(
+    rgba_data: &[u8],
+    width: u32,
+    height: u32,
+    image_codec: VideoCodec,
+    pixel_format: OutputPixelFormat,
+    crf: u32,
+) -> Result<Vec<u8>, String> {
+    if !image_codec.is_image() {
+        return Err(format!("{:?}는 이미지 코덱이 아닙니다", image_codec));
+    }
+    let expected_size = (width * height * 4) as usize;
+    if width == 0 || height == 0 || rgba_data.len() != expected_size {
+        return Err(format!(
+            "Invalid frame data size: got {}, expected {} ({}x{}x4)",
+            rgba_data.len(), expected_size, width, height
+        ));
+    }
+
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+    let (codec, codec_name) = VideoEncoder::find_video_encoder(image_codec, EncoderType::Software)?;
+    let pixel = image_codec.encoder_pixel(pixel_format);
+
+    let mut encoder = codec::context::Context::new_with_codec(codec)
+        .encoder()
+        .video()
+        .map_err(|e| format!("Failed to get video encoder: {}", e))?;
+    encoder.set_width(width);
+    encoder.set_height(height);
+    encoder.set_format(pixel);
+    encoder.set_time_base(ffmpeg::Rational::new(1, 25));
+
+    let mut opts = ffmpeg::Dictionary::new();
+    apply_intermediate_options(image_codec, &codec_name, 0, image_codec.map_crf(crf), &mut opts);
+    let mut encoder = encoder.open_as_with(codec, opts)
+        .map_err(|e| format!("Failed to open encoder: {}", e))?;
+
+    // RGBA 데이터 → ffmpeg Video 프레임
+    let mut src_frame = ffmpeg::frame::Video::new(Pixel::RGBA, width, height);
+    {
+        let linesize = src_frame.stride(0);
+        let dst = src_frame.data_mut(0);
+        let row_size = width as usize * 4;
+        for y in 0..height as usize {
+            dst[y * linesize..y * linesize + row_size]
+                .copy_from_slice(&rgba_data[y * row_size..(y + 1) * row_size]);
+        }
+    }
+
+    // RGBA → 출력 픽셀 포맷 (스틸은 1장뿐이라 항상 고품질 변환)
+    let mut scaler = scaling::Context::get(
+        Pixel::RGBA,
+        width,
+        height,
+        pixel,
+        width,
+        height,
+        scaling::Flags::BICUBIC | scaling::Flags::ACCURATE_RND | scaling::Flags::FULL_CHR_H_INT,
+    )
+    .map_err(|e| format!("Failed to create scaler: {}", e))?;
+    let mut out_frame = ffmpeg::frame::Video::empty();
+    scaler.run(&src_frame, &mut out_frame)
+        .map_err(|e| format!("Scaler failed: {}", e))?;
+    out_frame.set_pts(Some(0));
+
+    encoder.send_frame(&out_frame)
+        .map_err(|e| format!("Failed to send frame: {}", e))?;
+    encoder.send_eof()
+        .map_err(|e| format!("Failed to send video EOF: {}", e))?;
+
+    let mut packet = ffmpeg::Packet::empty();
+    encoder.receive_packet(&mut packet)
+        .map_err(|e| format!("Failed to encode still image: {}", e))?;
+    packet.data()
+        .map(|d| d.to_vec())
+        .ok_or_else(|| "Empty still image packet".to_string())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// 테스트용 영상 파일 생성 (FFV1/MKV, 단색 RGBA 프레임 frames장 — 렌더러/Export 테스트 소스)
+    pub(crate) fn write_test_video(path: &Path, (width, height): (u32, u32), fps: f64, frames: i64, rgba: [u8; 4]) -> Result<(), String> {
+        let settings = VideoSettings {
+            codec: VideoCodec::Ffv1,
+            encoder_type: EncoderType::Software,
+            ..VideoSettings::default()
+        };
+        let mut encoder = VideoEncoder::new(&path.to_string_lossy(), Container::Mkv, width, height, fps, 23, settings)?;
+        encoder.write_header()?;
+        let frame = rgba.repeat((width * height) as usize);
+        for _ in 0..frames {
+            encoder.encode_frame(&frame, width, height)?;
+        }
+        encoder.finish()
+    }
+
+    #[test]
+    fn test_smart_render_mp4_uses_avc3() {
+        let dir = std::env::temp_dir();
+        let source = dir.join(format!("vortex_smart_src_{}.mp4", std::process::id()));
+        let output = dir.join(format!("vortex_smart_out_{}.mp4", std::process::id()));
+        let settings = VideoSettings { encoder_type: EncoderType::Software, ..VideoSettings::default() };
+        let frame = [128u8, 64, 32, 255].repeat(64 * 36);
+
+        let mut encoder = VideoEncoder::new(&source.to_string_lossy(), Container::Mp4, 64, 36, 25.0, 23, settings).unwrap();
+        encoder.write_header().unwrap();
+        for _ in 0..5 {
+            encoder.encode_frame(&frame, 64, 36).unwrap();
+        }
+        encoder.finish().unwrap();
+
+        // 재인코딩 구간만 있는 스마트 렌더 출력 → 샘플 엔트리 avc3
+        let mut encoder = VideoEncoder::new(&output.to_string_lossy(), Container::Mp4, 64, 36, 25.0, 23, settings).unwrap();
+        encoder.enable_smart_render(&source, 0).unwrap();
+        encoder.write_header().unwrap();
+        for _ in 0..5 {
+            encoder.encode_frame(&frame, 64, 36).unwrap();
+        }
+        encoder.finish().unwrap();
+        drop(encoder);
+
+        let input = ffmpeg::format::input(&output).unwrap();
+        let stream = input.streams().best(ffmpeg::media::Type::Video).unwrap();
+        let codec_tag = unsafe { (*stream.parameters().as_ptr()).codec_tag };
+        assert_eq!(codec_tag, u32::from_le_bytes(*b"avc3"));
+
+        std::fs::remove_file(&source).ok();
+        std::fs::remove_file(&output).ok();
+    }
+
+    #[test]
+    fn test_resolve_pixel_format_profiles() {
+        use OutputPixelFormat as F;
+        // ProRes: 422 계열 10bit 4:2:2, 4444 계열 4:4:4 (+알파)
+        let prores = |profile: ProResProfile, requested, alpha| {
+            VideoCodec::ProRes.resolve_pixel_format(profile as u32, requested, alpha)
+        };
+        assert_eq!(prores(ProResProfile::Auto, F::Auto, false), Ok(F::Yuv422p10));
+        assert_eq!(prores(ProResProfile::Proxy, F::Yuv422p10, false), Ok(F::Yuv422p10));
+        assert_eq!(prores(ProResProfile::P4444, F::Auto, false), Ok(F::Yuv444p10));
+        assert_eq!(prores(ProResProfile::P4444Xq, F::Auto, true), Ok(F::Yuva444p10));
+        assert!(prores(ProResProfile::Hq, F::Auto, true).is_err());
+        assert!(prores(ProResProfile::Hq, F::Yuv420p, false).is_err());
+
+        // DNxHR: LB/SQ/HQ 8bit 4:2:2, HQX 10bit 4:2:2, 444 10bit 4:4:4, 알파 없음
+        let dnxhr = |profile: DnxhrProfile, requested| {
+            VideoCodec::Dnxhr.resolve_pixel_format(profile as u32, requested, false)
+        };
+        assert_eq!(dnxhr(DnxhrProfile::Auto, F::Auto), Ok(F::Yuv422p));
+        assert_eq!(dnxhr(DnxhrProfile::Lb, F::Auto), Ok(F::Yuv422p));
+        assert_eq!(dnxhr(DnxhrProfile::Hqx, F::Auto), Ok(F::Yuv422p10));
+        assert_eq!(dnxhr(DnxhrProfile::P444, F::Yuv444p10), Ok(F::Yuv444p10));
+        assert!(dnxhr(DnxhrProfile::Sq, F::Yuv422p10).is_err());
+        assert!(VideoCodec::Dnxhr.resolve_pixel_format(0, F::Auto, true).is_err());
+
+        // FFV1: 기본 4:4:4 10bit, 명시 포맷/알파 그대로
+        let ffv1 = |requested, alpha| VideoCodec::Ffv1.resolve_pixel_format(0, requested, alpha);
+        assert_eq!(ffv1(F::Auto, false), Ok(F::Yuv444p10));
+        assert_eq!(ffv1(F::Auto, true), Ok(F::Yuva444p10));
+        assert_eq!(ffv1(F::Yuv420p, false), Ok(F::Yuv420p));
+        assert_eq!(ffv1(F::Yuv420p, true), Ok(F::Yuva420p));
+    }
+
+    #[test]
+    fn test_validate_pixel_formats() {
+        use OutputPixelFormat as F;
+        // 코덱이 인코딩할 수 없는 픽셀 포맷
+        assert!(VideoCodec::H264.resolve_pixel_format(0, F::Rgb24, false).is_err());
+        assert!(VideoCodec::H264.resolve_pixel_format(0, F::Rgba, false).is_err());
+        assert!(VideoCodec::Hevc.resolve_pixel_format(0, F::Yuva420p, false).is_err());
+        assert!(VideoCodec::Av1.resolve_pixel_format(0, F::Yuv444p, false).is_err());
+        assert!(VideoCodec::Png.resolve_pixel_format(0, F::Yuv420p, false).is_err());
+        assert!(VideoCodec::Ffv1.resolve_pixel_format(0, F::Rgb24, false).is_err());
+        assert!(VideoCodec::Jpeg.resolve_pixel_format(0, F::Yuv420p10, false).is_err());
+        // VP9 알파: libvpx는 yuva420p만 (4:4:4 + 알파 → yuva444p10 거부)
+        assert_eq!(VideoCodec::Vp9.resolve_pixel_format(0, F::Auto, true), Ok(F::Yuva420p));
+        assert!(VideoCodec::Vp9.resolve_pixel_format(0, F::Yuv444p, true).is_err());
+        assert!(VideoCodec::Vp9.resolve_pixel_format(0, F::Yuva444p10, false).is_err());
+        assert_eq!(VideoCodec::Vp9.resolve_pixel_format(0, F::Yuv444p10, false), Ok(F::Yuv444p10));
+        assert_eq!(VideoCodec::H264.resolve_pixel_format(0, F::Yuv422p10, false), Ok(F::Yuv422p10));
+        assert_eq!(VideoCodec::Tiff.resolve_pixel_format(0, F::Auto, true), Ok(F::Rgba));
+
+        // Export 설정 검증 단계에서 거부
+        let mut config = crate::encoding::exporter::ExportConfig::new("out.mp4", 1920, 1080, 30.0, 23);
+        config.pixel_format = F::Rgb24 as u32;
+        assert!(config.validate().unwrap_err().contains("Rgb24"));
+        let mut config = crate::encoding::exporter::ExportConfig::new("out.webm", 1920, 1080, 30.0, 23);
+        config.video_codec = VideoCodec::Vp9 as u32;
+        config.pixel_format = F::Yuv444p as u32;
+        config.alpha = true;
+        assert!(config.validate().is_err());
+        config.pixel_format = F::Auto as u32;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rate_settings_validate() {
+        let rate = |mode, bitrate, max_bitrate| RateSettings { mode, bitrate, max_bitrate, buffer_size: 0 };
+        // CRF: 비트레이트 값 무시
+        assert!(rate(RateControl::Crf, 0, 0).validate(VideoCodec::H264).is_ok());
+
+        // 필수 비트레이트 0
+        assert!(rate(RateControl::CappedCrf, 0, 0).validate(VideoCodec::H264).is_err());
+        assert!(rate(RateControl::TwoPass, 0, 8_000_000).validate(VideoCodec::H264).is_err());
+        assert!(rate(RateControl::Cbr, 0, 0).validate(VideoCodec::Hevc).is_err());
+
+        // FFI에서 음수 kbps가 u32로 넘어온 값 (-1 → 4294967295kbps)
+        let negative = (-1i32) as u32 as usize * 1000;
+        assert!(rate(RateControl::Cbr, negative, 0).validate(VideoCodec::H264).is_err());
+        assert!(rate(RateControl::CappedCrf, 0, negative).validate(VideoCodec::H264).is_err());
+        let buffer = RateSettings { buffer_size: negative, ..rate(RateControl::TwoPass, 8_000_000, 0) };
+        assert!(buffer.validate(VideoCodec::H264).is_err());
+
+        // 최대 비트레이트 < 목표 비트레이트
+        assert!(rate(RateControl::CappedCrf, 8_000_000, 4_000_000).validate(VideoCodec::Hevc).is_err());
+        assert!(rate(RateControl::CappedCrf, 4_000_000, 8_000_000).validate(VideoCodec::Hevc).is_ok());
+        assert!(rate(RateControl::CappedCrf, 0, 8_000_000).validate(VideoCodec::Hevc).is_ok());
+        assert!(rate(RateControl::TwoPass, 8_000_000, 6_000_000).validate(VideoCodec::Vp9).is_err());
+        assert!(rate(RateControl::TwoPass, 8_000_000, 0).validate(VideoCodec::Vp9).is_ok());
+
+        // 인트라 전용 코덱: CRF만
+        assert!(rate(RateControl::Cbr, 8_000_000, 0).validate(VideoCodec::ProRes).is_err());
+    }
+
+    #[test]
+    fn test_vbv_buffer() {
+        let rate = |mode, buffer_size| RateSettings { mode, bitrate: 6_000_000, max_bitrate: 8_000_000, buffer_size };
+        // CBR: 목표 비트레이트 1초분, 그 외: 최대 비트레이트 2초분
+        assert_eq!(rate(RateControl::Cbr, 0).vbv_buffer(), 6_000_000);
+        assert_eq!(rate(RateControl::CappedCrf, 0).vbv_buffer(), 16_000_000);
+        assert_eq!(rate(RateControl::TwoPass, 0).vbv_buffer(), 16_000_000);
+        // 명시값 우선
+        assert_eq!(rate(RateControl::Cbr, 3_000_000).vbv_buffer(), 3_000_000);
+        assert_eq!(rate(RateControl::CappedCrf, 12_000_000).vbv_buffer(), 12_000_000);
+    }
+
+    #[test]
+    fn test_bitrate_options_per_encoder() {
+        let stats = Path::new("/tmp/vortex_2pass.log");
+        let plan = |mode, codec_name, pass| {
+            let rate = RateSettings { mode, bitrate: 6_000_000, max_bitrate: 8_000_000, buffer_size: 0 };
+            let mut opts = ffmpeg::Dictionary::new();
+            let plan = plan_rate_control(rate, EncoderPreset::Balanced, codec_name, 30, 5_000_000, pass, &mut opts);
+            (plan, opts)
+        };
+        let single = |bit_rate, limits| RatePlan { bit_rate, limits, pass_number: 0 };
+        let capped = Some((0, 8_000_000, 16_000_000));
+        let cbr = Some((6_000_000, 6_000_000, 6_000_000));
+
+        // CRF: 인코더 고유 품질 옵션, bit_rate 0 (고정 품질 없는 AMF만 근사 비트레이트)
+        for (name, key) in [
+            ("libx264", "crf"), ("libx265", "crf"), ("libvpx-vp9", "crf"), ("libaom-av1", "crf"),
+            ("hevc_nvenc", "cq"), ("h264_qsv", "global_quality"),
+        ] {
+            let (p, opts) = plan(RateControl::Crf, name, EncoderPass::Single);
+            assert_eq!(p, single(0, None), "{}", name);
+            assert_eq!(opts.get(key), Some("30"), "{}", name);
+        }
+        assert_eq!(plan(RateControl::Crf, "h264_amf", EncoderPass::Single).0, single(5_000_000, None));
+
+        // CRF 상한: 품질 옵션 + maxrate/bufsize (libvpx/libaom은 b:v=max → Constrained Quality)
+        for (name, bit_rate) in [("libx264", 0), ("hevc_nvenc", 0), ("libvpx-vp9", 8_000_000), ("libaom-av1", 8_000_000)] {
+            let (p, opts) = plan(RateControl::CappedCrf, name, EncoderPass::Single);
+            assert_eq!(p, single(bit_rate, capped), "{}", name);
+            assert!(opts.get("crf").or(opts.get("cq")).is_some(), "{}", name);
+        }
+        assert_eq!(plan(RateControl::CappedCrf, "hevc_amf", EncoderPass::Single).0, single(6_000_000, capped));
+
+        // CBR: minrate = maxrate = bitrate, 품질 옵션 없음
+        for (name, key, value) in [
+            ("libx264", "nal-hrd", "cbr"),
+            ("libx265", "x265-params", "log-level=error:strict-cbr=1"),
+            ("h264_nvenc", "rc", "cbr"),
+            ("av1_amf", "rc", "cbr"),
+        ] {
+            let (p, opts) = plan(RateControl::Cbr, name, EncoderPass::Single);
+            assert_eq!(p, single(6_000_000, cbr), "{}", name);
+            assert_eq!(opts.get(key), Some(value), "{}", name);
+        }
+        for (name, key) in [("libvpx-vp9", "crf"), ("libaom-av1", "crf"), ("hevc_qsv", "global_quality")] {
+            let (p, opts) = plan(RateControl::Cbr, name, EncoderPass::Single);
+            assert_eq!(p, single(6_000_000, cbr), "{}", name);
+            assert_eq!(opts.get(key), None, "{}", name);
+        }
+
+        // 2-pass: libx264/x265는 통계 파일 옵션, libvpx/libaom은 stats_in/out 메모리 (옵션 없음)
+        let (p, opts) = plan(RateControl::TwoPass, "libx264", EncoderPass::First(stats));
+        assert_eq!(p, RatePlan { bit_rate: 6_000_000, limits: capped, pass_number: 1 });
+        assert_eq!(opts.get("stats"), Some("/tmp/vortex_2pass.log"));
+        assert_eq!(opts.get("crf"), None);
+        let (p, opts) = plan(RateControl::TwoPass, "libx265", EncoderPass::Second(stats));
+        assert_eq!(p.pass_number, 2);
+        assert_eq!(opts.get("x265-params"), Some("log-level=error:pass=2:stats='/tmp/vortex_2pass.log'"));
+        let (p, opts) = plan(RateControl::TwoPass, "libvpx-vp9", EncoderPass::First(stats));
+        assert_eq!(p.pass_number, 1);
+        assert_eq!(opts.get("stats"), None);
+        // 하드웨어: 단일 패스 VBR
+        let (p, opts) = plan(RateControl::TwoPass, "av1_nvenc", EncoderPass::Single);
+        assert_eq!(p, single(6_000_000, capped));
+        assert_eq!((opts.get("rc"), opts.get("multipass")), (Some("vbr"), Some("fullres")));
+        let (_, opts) = plan(RateControl::TwoPass, "hevc_amf", EncoderPass::Single);
+        assert_eq!(opts.get("rc"), Some("vbr_peak"));
+    }
+
+    #[test]
+    fn test_map_crf_boundaries() {
+        // (코덱, [(입력 CRF, 코덱 스케일)]) — 0 / 중간 / 51 / 범위 초과
+        let table: [(VideoCodec, [(u32, u32); 4]); 6] = [
+            (VideoCodec::H264, [(0, 0), (23, 23), (51, 51), (70, 51)]),
+            (VideoCodec::Hevc, [(0, 5), (23, 28), (46, 51), (51, 51)]),
+            (VideoCodec::Av1, [(0, 7), (23, 35), (45, 62), (51, 63)]),
+            (VideoCodec::Vp9, [(0, 3), (23, 31), (48, 62), (70, 63)]),
+            (VideoCodec::Jpeg, [(0, 2), (23, 15), (51, 31), (70, 31)]),
+            (VideoCodec::ProRes, [(0, 0), (23, 23), (51, 51), (70, 51)]),
+        ];
+        for (codec, cases) in table {
+            for (crf, expected) in cases {
+                assert_eq!(codec.map_crf(crf), expected, "{:?} crf={}", codec, crf);
+            }
+        }
+    }
+
+    #[test]
+    fn test_preset_options_per_encoder() {
+        // (인코더, 품질 옵션 키, Balanced 속도 옵션, 고정 품질 지원)
+        let table = [
+            ("libx264", "crf", ("preset", "medium"), true),
+            ("libx265", "crf", ("preset", "medium"), true),
+            ("libsvtav1", "crf", ("preset", "8"), true),
+            ("libaom-av1", "crf", ("cpu-used", "6"), true),
+            ("libvpx-vp9", "crf", ("cpu-used", "3"), true),
+            ("h264_nvenc", "cq", ("preset", "p4"), true),
+            ("av1_nvenc", "cq", ("preset", "p4"), true),
+            ("hevc_qsv", "global_quality", ("preset", "medium"), true),
+            ("vp9_qsv", "global_quality", ("preset", "medium"), true),
+        ];
+        for (name, quality_key, (speed_key, speed), constant_quality) in table {
+            let mut opts = ffmpeg::Dictionary::new();
+            assert_eq!(EncoderPreset::Balanced.apply(name, Some(30), &mut opts), constant_quality, "{}", name);
+            assert_eq!(opts.get(quality_key), Some("30"), "{}", name);
+            assert_eq!(opts.get(speed_key), Some(speed), "{}", name);
+
+            // 목표 비트레이트 모드: 속도 옵션만
+            let mut opts = ffmpeg::Dictionary::new();
+            EncoderPreset::Balanced.apply(name, None, &mut opts);
+            assert_eq!(opts.get(quality_key), None, "{}", name);
+            assert_eq!(opts.get(speed_key), Some(speed), "{}", name);
+        }
+
+        let mut opts = ffmpeg::Dictionary::new();
+        assert!(EncoderPreset::Fast.apply("h264_nvenc", Some(23), &mut opts));
+        assert_eq!(opts.get("rc"), Some("vbr"));
+        assert_eq!(opts.get("preset"), Some("p2"));
+
+        let mut opts = ffmpeg::Dictionary::new();
+        assert!(EncoderPreset::Quality.apply("libx265", Some(23), &mut opts));
+        assert_eq!(opts.get("preset"), Some("slow"));
+        assert_eq!(opts.get("x265-params"), Some("log-level=error"));
+
+        // AMF / generic 인코더: 고정 품질 없음 → 비트레이트로 제어
+        let mut opts = ffmpeg::Dictionary::new();
+        assert!(!EncoderPreset::Quality.apply("hevc_amf", Some(23), &mut opts));
+        assert_eq!(opts.get("quality"), Some("quality"));
+        assert!(!EncoderPreset::Balanced.apply("mpeg4", Some(23), &mut ffmpeg::Dictionary::new()));
+    }
+
+    #[test]
+    fn test_encoder_fallback_order() {
+        let select = |codec, encoder_type, installed: &[&str]| {
+            VideoEncoder::select_video_encoder(codec, encoder_type, |name| installed.contains(&name))
+        };
+        let all_h264 = ["h264_nvenc", "h264_qsv", "h264_amf", "libx264"];
+
+        // Auto: NVENC → QSV → AMF → 소프트웨어
+        assert_eq!(select(VideoCodec::H264, EncoderType::Auto, &all_h264), Some("h264_nvenc"));
+        assert_eq!(select(VideoCodec::H264, EncoderType::Auto, &all_h264[1..]), Some("h264_qsv"));
+        assert_eq!(select(VideoCodec::H264, EncoderType::Auto, &all_h264[2..]), Some("h264_amf"));
+        assert_eq!(select(VideoCodec::H264, EncoderType::Auto, &all_h264[3..]), Some("libx264"));
+        assert_eq!(select(VideoCodec::H264, EncoderType::Auto, &[]), None);
+
+        // 하드웨어 지정: 다른 하드웨어가 있어도 건너뛰고 소프트웨어로 폴백
+        assert_eq!(select(VideoCodec::Hevc, EncoderType::Qsv, &["hevc_qsv", "libx265"]), Some("hevc_qsv"));
+        assert_eq!(select(VideoCodec::Hevc, EncoderType::Qsv, &["hevc_nvenc", "libx265"]), Some("libx265"));
+        // GPU가 지원하지 않는 코덱 (VP9 NVENC 없음)
+        assert_eq!(select(VideoCodec::Vp9, EncoderType::Nvenc, &["libvpx-vp9"]), Some("libvpx-vp9"));
+        // 소프트웨어 지정: 하드웨어 무시
+        assert_eq!(select(VideoCodec::H264, EncoderType::Software, &all_h264), Some("libx264"));
+
+        // 소프트웨어 인코더 우선순위: SVT-AV1 → libaom
+        assert_eq!(select(VideoCodec::Av1, EncoderType::Auto, &["libaom-av1", "libsvtav1"]), Some("libsvtav1"));
+        assert_eq!(select(VideoCodec::Av1, EncoderType::Software, &["libaom-av1"]), Some("libaom-av1"));
+        assert_eq!(select(VideoCodec::ProRes, EncoderType::Auto, &["prores"]), Some("prores"));
+    }
+}
@@ -1,24 +1,406 @@
 // Export 작업 관리 - 백그라운드 스레드, 진행률, 취소
-// ExportJob: 타임라인 → MP4 파일 내보내기 전체 흐름
-// 비디오 (H.264) + 오디오 (AAC) 동시 인코딩
+// ExportJob: 타임라인 → MP4/MOV/MKV/WebM 파일 또는 이미지 시퀀스 내보내기 전체 흐름
+// 오디오 전용 컨테이너 (WAV/FLAC/MP3/M4A)는 Renderer 없이 AudioMixer 믹스만 인코딩
+// 비디오 (H.264/HEVC/AV1/VP9) + 오디오 (AAC/Opus/MP3/FLAC/PCM) 동시 인코딩 + 소프트 자막 스트림 먹싱
+// 구간 Export (in/out) + 구간 배치 Export (구간마다 파일 1개)
+// 스마트 렌더: 편집되지 않은 단일 클립 구간은 원본 패킷 복사, 컷/트랜지션/이펙트 주변만 재인코딩
+// 2-pass: 1차 패스(비디오만, null 먹서)로 통계 수집 → 2차 패스에서 목표 비트레이트로 인코딩
+// 병렬 구간 Export: 워커 스레드마다 Renderer + 인코더로 구간 파일 인코딩 → 패킷 결합 + 오디오/자막 먹싱
 
-use crate::encoding::encoder::{VideoEncoder, EncoderType};
+use crate::encoding::container::{
+    image_codec_from_path, image_sequence_pattern, region_output_path, validate_codecs, Container,
+};
+use crate::encoding::encoder::{
+    detect_available_encoders, probe_copy_source, probe_segment_delay, AudioEncoder, EncoderPass, PacketCopy, RateControl, RateSettings, VideoEncoder, VideoSettings, AudioCodec, AudioSettings, EncoderType, EncoderPreset, OutputPixelFormat, SubtitleCodec, VideoCodec,
+};
 use crate::encoding::audio_mixer::AudioMixer;
+use crate::encoding::parallel::{plan_parallel_segments, resolve_workers, ParallelSegment};
+use crate::encoding::preset::validate_with_encoders;
+use crate::encoding::smart_render::{plan_smart_render, SmartSegment};
 use crate::rendering::Renderer;
-use crate::subtitle::overlay::{SubtitleOverlayList, blend_overlay_rgba, yuv420p_to_rgba, rgba_to_yuv420p};
+use crate::subtitle::cue::{SubtitleCue, SubtitleTrack};
+use crate::subtitle::overlay::SubtitleOverlayList;
 use crate::timeline::Timeline;
-use std::path::Path;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU32, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
+/// 배치 Export 구간 (타임라인 ms, 구간마다 파일 1개)
+#[derive(Debug, Clone)]
+pub struct ExportRegion {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// 파일명 접미사 (None이면 구간 번호 01, 02, ...)
+    pub name: Option<String>,
+}
+
 /// Export 설정
+#[derive(Clone)]
 pub struct ExportConfig {
     pub output_path: String,
     pub width: u32,
     pub height: u32,
     pub fps: f64,
     pub crf: u32,
+    /// 0=CRF, 1=CRF 상한 (max_bitrate), 2=2-pass 목표 비트레이트, 3=CBR (인트라 전용 코덱은 CRF만)
+    pub rate_control: u32,
+    pub video_bitrate: u32, // kbps, 2-pass/CBR 목표
+    pub max_bitrate: u32,   // kbps, CRF 상한 필수 / 2-pass는 0이면 제한 없음
+    pub buffer_size: u32,   // kbit, VBV 버퍼 (0=CBR은 1초분, 그 외 max_bitrate 2초분)
     pub encoder_type: u32,  // 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+    /// 0=H.264, 1=HEVC, 2=AV1, 3=VP9, 4=ProRes, 5=DNxHR, 6=FFV1, 7=PNG, 8=JPEG, 9=TIFF
+    /// 이미지 시퀀스 컨테이너에서 이미지 코덱이 아니면 확장자 기준 (기본 PNG)
+    pub video_codec: u32,
+    /// ProRes: 0=Auto(422 HQ), 1=Proxy, 2=LT, 3=422, 4=422 HQ, 5=4444, 6=4444 XQ
+    /// DNxHR: 0=Auto(HQ), 1=LB, 2=SQ, 3=HQ, 4=HQX, 5=444 / 그 외 코덱은 무시
+    pub codec_profile: u32,
+    /// 0=Auto(코덱/프로필 기준), 1=YUV420P, 2=YUV422P, 3=YUV444P, 4=YUV420P10, 5=YUV422P10, 6=YUV444P10
+    /// 7=YUVA420P, 8=YUVA444P10, 9=RGB24, 10=RGBA
+    pub pixel_format: u32,
+    /// 알파 채널 Export (ProRes 4444 / VP9 / FFV1 / PNG / TIFF, 빈 영역 투명)
+    pub alpha: bool,
+    pub preset: u32,        // 0=Balanced, 1=Fast, 2=Quality
+    pub container: u32,     // 0=Auto(확장자 기준), 1=MP4, 2=MOV, 3=MKV, 4=WebM, 5=이미지 시퀀스, 6=WAV, 7=FLAC, 8=MP3, 9=M4A
+    pub audio_codec: u32,   // 0=Auto(컨테이너 기준), 1=AAC, 2=Opus, 3=MP3, 4=FLAC, 5=PCM 16bit, 6=PCM 24bit
+    pub audio_sample_rate: u32, // Hz, 0=48000
+    pub audio_channels: u32,    // 1=모노, 2=스테레오, 6=5.1, 0=스테레오
+    pub audio_bitrate: u32,     // kbps, 0=채널당 96kbps (무손실 코덱은 무시)
+    /// false면 오디오 트랙 없이 비디오만 Export
+    pub include_audio: bool,
+    /// 소프트 자막 트랙 (트랙마다 자막 스트림 1개, 비어 있으면 자막 스트림 없음)
+    pub subtitle_tracks: Vec<SubtitleTrack>,
+    pub subtitle_codec: u32, // 0=Auto(확장자 기준), 1=mov_text, 2=SRT, 3=ASS, 4=WebVTT
+    /// Export 구간 (타임라인 ms, range_end_ms 0이면 타임라인 끝까지)
+    pub range_start_ms: i64,
+    pub range_end_ms: i64,
+    /// 구간 배치 Export (비어 있지 않으면 구간마다 "{파일명}_{이름|번호}.{확장자}", range 무시)
+    pub regions: Vec<ExportRegion>,
+    /// 스마트 렌더 (편집되지 않은 단일 클립 구간은 원본 패킷 복사, H.264 YUV420P → MP4/MOV/MKV만)
+    /// 복사 구간은 원본 비트레이트 그대로이므로 CRF 모드에서만 적용
+    pub smart_render: bool,
+    /// 병렬 구간 Export (소프트웨어 인코더 + CRF/CRF 상한만, 스마트 렌더를 켜면 스마트 렌더 우선)
+    pub parallel_export: bool,
+    /// 병렬 Export 워커 수 (0=자동, 코어 4개당 1개)
+    pub parallel_workers: u32,
+}
+
+impl ExportConfig {
+    /// 기본 설정 (H.264 자동 인코더, Balanced 프리셋, 자막 없음)
+    pub fn new(output_path: impl Into<String>, width: u32, height: u32, fps: f64, crf: u32) -> Self {
+        Self {
+            output_path: output_path.into(),
+            width,
+            height,
+            fps,
+            crf,
+            rate_control: 0,
+            video_bitrate: 0,
+            max_bitrate: 0,
+            buffer_size: 0,
+            encoder_type: 0,
+            video_codec: 0,
+            codec_profile: 0,
+            pixel_format: 0,
+            alpha: false,
+            preset: 0,
+            container: 0,
+            audio_codec: 0,
+            audio_sample_rate: 0,
+            audio_channels: 0,
+            audio_bitrate: 0,
+            include_audio: true,
+            subtitle_tracks: Vec::new(),
+            subtitle_codec: 0,
+            range_start_ms: 0,
+            range_end_ms: 0,
+            regions: Vec::new(),
+            smart_render: false,
+            parallel_export: false,
+            parallel_workers: 0,
+        }
+    }
+
+    /// Export 구간 확정 (타임라인 길이로 클램프, 빈 구간이면 에러)
+    pub fn resolved_range(&self, duration_ms: i64) -> Result<(i64, i64), String> {
+        let start = self.range_start_ms.max(0);
+        let end = if self.range_end_ms <= 0 {
+            duration_ms
+        } else {
+            self.range_end_ms.min(duration_ms)
+        };
+        if start >= end {
+            return Err(format!(
+                "Export 구간이 비어 있습니다 ({}~{}ms, 타임라인 길이 {}ms)",
+                self.range_start_ms, self.range_end_ms, duration_ms
+            ));
+        }
+        Ok((start, end))
+    }
+
+    /// 구간별 Export 설정 (배치 Export, 출력 파일명에 구간 이름/번호 추가)
+    pub fn region_configs(&self) -> Vec<ExportConfig> {
+        self.regions.iter().enumerate()
+            .map(|(i, region)| ExportConfig {
+                output_path: region_output_path(&self.output_path, i, region.name.as_deref()),
+                range_start_ms: region.start_ms,
+                range_end_ms: region.end_ms,
+                regions: Vec::new(),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    /// 컨테이너 확정 (Auto면 출력 파일 확장자 기준)
+    pub fn resolved_container(&self) -> Container {
+        Container::from_u32(self.container).resolve(&self.output_path)
+    }
+
+    /// 비디오 인코더 설정 확정 (픽셀 포맷이 코덱 프로필과 맞지 않으면 에러)
+    pub fn video_settings(&self) -> Result<VideoSettings, String> {
+        let mut codec = VideoCodec::from_u32(self.video_codec);
+        if self.resolved_container() == Container::ImageSequence && !codec.is_image() {
+            codec = image_codec_from_path(&self.output_path).unwrap_or(VideoCodec::Png);
+        }
+        let pixel_format = codec.resolve_pixel_format(
+            self.codec_profile,
+            OutputPixelFormat::from_u32(self.pixel_format),
+            self.alpha,
+        )?;
+        Ok(VideoSettings {
+            codec,
+            encoder_type: EncoderType::from_u32(self.encoder_type),
+            preset: EncoderPreset::from_u32(self.preset),
+            codec_profile: self.codec_profile,
+            pixel_format,
+            rate: RateSettings {
+                mode: RateControl::from_u32(self.rate_control),
+                bitrate: self.video_bitrate as usize * 1000,
+                max_bitrate: self.max_bitrate as usize * 1000,
+                buffer_size: self.buffer_size as usize * 1000,
+            },
+            threads: 0,
+        })
+    }
+
+    /// 오디오 인코더 설정 확정 (오디오 트랙을 만들지 않으면 None)
+    pub fn audio_settings(&self) -> Option<AudioSettings> {
+        let container = self.resolved_container();
+        if !self.include_audio || !container.has_audio() {
+            return None;
+        }
+        let sample_rate = if self.audio_sample_rate == 0 { 48000 } else { self.audio_sample_rate };
+        let channels = if self.audio_channels == 0 { 2 } else { self.audio_channels };
+        let bitrate_kbps = if self.audio_bitrate == 0 { 96 * channels } else { self.audio_bitrate };
+        Some(AudioSettings {
+            codec: AudioCodec::from_u32(self.audio_codec).resolve(container),
+            sample_rate,
+            channels,
+            bitrate: bitrate_kbps as usize * 1000,
+        })
+    }
+
+    /// 컨테이너/코덱 조합 검증 (인코더를 열기 전에 호출 → write_header에서 실패하지 않도록)
+    pub fn validate(&self) -> Result<(), String> {
+        let ranges = std::iter::once((self.range_start_ms, self.range_end_ms))
+            .chain(self.regions.iter().map(|r| (r.start_ms, r.end_ms)));
+        for (start, end) in ranges {
+            if start < 0 || (end != 0 && end <= start) {
+                return Err(format!("잘못된 Export 구간: {}~{}ms", start, end));
+            }
+        }
+        let container = self.resolved_container();
+        // 오디오 전용 컨테이너는 비디오 설정 무시
+        let video = if container.is_audio_only() {
+            None
+        } else {
+            if self.width == 0 || self.height == 0 || self.fps <= 0.0 {
+                return Err("비디오 해상도/fps가 설정되지 않았습니다".to_string());
+            }
+            let video = self.video_settings()?;
+            video.rate.validate(video.codec)?;
+            Some(video)
+        };
+        // VP9 알파는 Matroska BlockAdditional로만 저장됨
+        if video.is_some_and(|v| v.codec == VideoCodec::Vp9 && v.pixel_format.has_alpha())
+            && !matches!(container, Container::WebM | Container::Mkv)
+        {
+            return Err("VP9 알파 채널은 WebM/MKV 컨테이너에서만 지원됩니다".to_string());
+        }
+        let subtitle = if self.subtitle_tracks.is_empty() {
+            None
+        } else {
+            Some(SubtitleCodec::from_u32(self.subtitle_codec).resolve(container))
+        };
+        let audio = self.audio_settings();
+        if let Some(audio) = &audio {
+            audio.validate()?;
+        }
+        validate_codecs(container, video.map(|v| v.codec), audio.map(|a| a.codec), subtitle)
+    }
+}
+
+/// 진행률 기록 (배치 Export는 구간 index/count의 진행률을 전체 0~100으로 환산)
+#[derive(Clone, Copy)]
+struct ProgressScope<'a> {
+    progress: &'a AtomicU32,
+    index: u32,
+    count: u32,
+    /// 구간 진행률 중 이 단계가 차지하는 범위 (병렬 Export: 렌더링 0~90, 결합 90~100)
+    span: (u32, u32),
+}
+
+impl ProgressScope<'_> {
+    fn store(&self, pct: u32) {
+        let (from, to) = self.span;
+        let pct = from + pct * (to - from) / 100;
+        self.progress.store((self.index * 100 + pct) / self.count, Ordering::SeqCst);
+    }
+
+    /// 단계 범위 from~to% (현재 범위 기준)
+    fn within(self, from: u32, to: u32) -> Self {
+        let (start, end) = self.span;
+        let width = end - start;
+        Self { span: (start + from * width / 100, start + to * width / 100), ..self }
+    }
+
+    /// 여러 패스 중 pass번째 범위 (2-pass: 1차 앞 절반, 2차 뒤 절반)
+    fn pass(self, pass: u32, passes: u32) -> Self {
+        Self { index: self.index * passes + pass, count: self.count * passes, ..self }
+    }
+}
+
+/// 2-pass 통계 파일 (Export가 끝나면 인코더 부산물까지 삭제)
+struct TwoPassLog {
+    stats: PathBuf,
+    /// 1차 패스 출력 (null 먹서라 빈 파일)
+    null_output: String,
+}
+
+impl TwoPassLog {
+    fn new() -> Self {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+        let base = std::env::temp_dir().join(format!(
+            "vortex_2pass_{}_{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::SeqCst)
+        ));
+        Self {
+            stats: base.with_extension("log"),
+            null_output: base.with_extension("null").to_string_lossy().to_string(),
+        }
+    }
+}
+
+impl Drop for TwoPassLog {
+    fn drop(&mut self) {
+        // libx264: .mbtree (+ 기록 중 .temp), libx265: .cutree
+        let stats = self.stats.to_string_lossy();
+        for suffix in ["", ".temp", ".mbtree", ".mbtree.temp", ".cutree"] {
+            let _ = std::fs::remove_file(format!("{}{}", stats, suffix));
+        }
+        let _ = std::fs::remove_file(&self.null_output);
+    }
+}
+
+/// 병렬 Export 구간 파일 (결합이 끝나거나 실패/취소되면 삭제)
+struct SegmentFiles {
+    /// (출력 프레임 구간, 임시 파일 경로)
+    segments: Vec<(ParallelSegment, String)>,
+}
+
+impl SegmentFiles {
+    fn new(plan: Vec<ParallelSegment>) -> Self {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+        let base = std::env::temp_dir().join(format!(
+            "vortex_segment_{}_{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::SeqCst)
+        ));
+        let base = base.to_string_lossy();
+        let segments = plan.into_iter()
+            .enumerate()
+            .map(|(i, segment)| (segment, format!("{}_{:03}.seg", base, i)))
+            .collect();
+        Self { segments }
+    }
+}
+
+impl Drop for SegmentFiles {
+    fn drop(&mut self) {
+        for (_, path) in &self.segments {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// 병렬 Export 워커 공유 상태 (std::thread::scope 안에서 참조로 공유)
+struct ParallelWork<'a> {
+    timeline: &'a Arc<Mutex<Timeline>>,
+    config: &'a ExportConfig,
+    subtitles: &'a Option<Arc<SubtitleOverlayList>>,
+    /// 워커 인코더 설정 (코어를 워커 수로 나눈 스레드 수)
+    video_settings: VideoSettings,
+    range_start: i64,
+    files: &'a SegmentFiles,
+    /// 다음에 가져갈 구간 번호 (작업 큐)
+    next_segment: AtomicUsize,
+    /// 인코딩을 마친 프레임 수 (전체 진행률)
+    done_frames: AtomicI64,
+    /// 워커 하나라도 실패하면 나머지 워커도 중단
+    failed: AtomicBool,
+    progress: ProgressScope<'a>,
+    cancelled: &'a AtomicBool,
+}
+
+impl ParallelWork<'_> {
+    /// 워커 1개: 전용 Renderer로 큐에서 구간을 하나씩 가져가 구간 파일 인코딩 (비디오만)
+    fn run_worker(&self) -> Result<(), String> {
+        let config = self.config;
+        let mut renderer = ExportJob::export_renderer(self.timeline, config, self.subtitles.clone(), self.video_settings);
+        let frame_duration_ms = 1000.0 / config.fps;
+        let total_frames = self.files.segments.last().map_or(1, |(s, _)| s.end_frame.max(1));
+
+        loop {
+            let index = self.next_segment.fetch_add(1, Ordering::SeqCst);
+            let Some((segment, path)) = self.files.segments.get(index) else {
+                return Ok(());
+            };
+            let mut encoder = VideoEncoder::new(
+                path,
+                config.resolved_container(),
+                config.width,
+                config.height,
+                config.fps,
+                config.crf,
+                self.video_settings,
+            ).map_err(|e| format!("구간 {} 인코더 생성 실패: {}", index + 1, e))?;
+            encoder.write_header()?;
+
+            for frame_index in segment.start_frame..segment.end_frame {
+                if self.cancelled.load(Ordering::SeqCst) || self.failed.load(Ordering::SeqCst) {
+                    let _ = encoder.finish();
+                    return Ok(());
+                }
+
+                let timestamp_ms = self.range_start + (frame_index as f64 * frame_duration_ms) as i64;
+                let frame = renderer.render_frame(timestamp_ms)
+                    .map_err(|e| format!("렌더링 실패 ({}ms): {}", timestamp_ms, e))?;
+                if frame.is_yuv {
+                    encoder.encode_frame_yuv(&frame.data, frame.width, frame.height)?;
+                } else {
+                    encoder.encode_frame(&frame.data, frame.width, frame.height)?;
+                }
+
+                let done = self.done_frames.fetch_add(1, Ordering::SeqCst) + 1;
+                self.progress.store((done * 100 / total_frames).min(99) as u32);
+            }
+            encoder.finish()?;
+            eprintln!(
+                "[EXPORT] 병렬 구간 {}/{} 완료 (frame {}~{})",
+                index + 1, self.files.segments.len(), segment.start_frame, segment.end_frame
+            );
+        }
+    }
 }
 
 /// Export 작업 핸들 (C#에서 폴링으로 상태 확인)
@@ -40,11 +422,26 @@ impl ExportJob {
     }
 
     /// Export 시작 (자막 포함)
+    /// 설정/인코더 검증에 실패하면 스레드 없이 에러로 끝난 작업 (get_error로 확인)
     pub fn start_with_subtitles(
         timeline: Arc<Mutex<Timeline>>,
         config: ExportConfig,
         subtitles: Option<SubtitleOverlayList>,
     ) -> Self {
+        Self::try_start(timeline, config, subtitles).unwrap_or_else(Self::failed)
+    }
+
+    /// 설정 + 설치된 인코더 검증 후 Export 시작 (검증 실패 시 Err, 스레드를 시작하지 않음)
+    pub fn try_start(
+        timeline: Arc<Mutex<Timeline>>,
+        config: ExportConfig,
+        subtitles: Option<SubtitleOverlayList>,
+    ) -> Result<Self, String> {
+        if let Err(msg) = validate_with_encoders(&config, detect_available_encoders()) {
+            eprintln!("[EXPORT] 설정 검증 실패: {}", msg);
+            return Err(msg);
+        }
+
         let progress = Arc::new(AtomicU32::new(0));
         let cancelled = Arc::new(AtomicBool::new(false));
         let finished = Arc::new(AtomicBool::new(false));
@@ -56,7 +453,7 @@ impl ExportJob {
         let e = error.clone();
 
         std::thread::spawn(move || {
-            let result = Self::export_thread(timeline, &config, &p, &c, subtitles.as_ref());
+            let result = Self::export_all(timeline, &config, &p, &c, subtitles.map(Arc::new));
             match result {
                 Ok(()) => {
                     p.store(100, Ordering::SeqCst);
@@ -72,7 +469,33 @@ impl ExportJob {
             f.store(true, Ordering::SeqCst);
         });
 
-        Self { progress, cancelled, finished, error }
+        Ok(Self { progress, cancelled, finished, error })
+    }
+
+    /// 시작 전에 실패한 작업 (완료 + 에러 상태)
+    pub fn failed(msg: String) -> Self {
+        Self {
+            progress: Arc::new(AtomicU32::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            finished: Arc::new(AtomicBool::new(true)),
+            error: Arc::new(Mutex::new(Some(msg))),
+        }
+    }
+
+    /// 시작 시간이 until_ms 이하인 자막 cue를 트랙별로 기록
+    /// soft_subtitles: (자막 스트림 인덱스, 정렬된 cue 목록, 다음 기록할 cue 위치)
+    fn write_pending_cues(
+        encoder: &mut VideoEncoder,
+        soft_subtitles: &mut [(usize, Vec<SubtitleCue>, usize)],
+        until_ms: i64,
+    ) -> Result<(), String> {
+        for (index, cues, next) in soft_subtitles.iter_mut() {
+            while let Some(cue) = cues.get(*next).filter(|c| c.start_ms <= until_ms) {
+                encoder.write_subtitle_cue(*index, cue)?;
+                *next += 1;
+            }
+        }
+        Ok(())
     }
 
     /// 비ASCII 경로(한글 등) 안전 처리
@@ -127,19 +550,52 @@ impl ExportJob {
         Ok(())
     }
 
+    /// 단일 Export 또는 구간 배치 Export (구간 순서대로, 하나라도 실패하면 중단)
+    fn export_all(
+        timeline: Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        progress: &AtomicU32,
+        cancelled: &AtomicBool,
+        subtitles: Option<Arc<SubtitleOverlayList>>,
+    ) -> Result<(), String> {
+        if config.regions.is_empty() {
+            let scope = ProgressScope { progress, index: 0, count: 1, span: (0, 100) };
+            return Self::export_thread(timeline, config, scope, cancelled, subtitles);
+        }
+
+        // 전체 설정 검증 먼저 (첫 구간만 만들어지고 실패하지 않도록)
+        config.validate()?;
+        let region_configs = config.region_configs();
+        let count = region_configs.len() as u32;
+        for (i, region_config) in region_configs.iter().enumerate() {
+            eprintln!(
+                "[EXPORT] 구간 {}/{}: {}~{}ms → {}",
+                i + 1, count, region_config.range_start_ms, region_config.range_end_ms, region_config.output_path
+            );
+            let scope = ProgressScope { progress, index: i as u32, count, span: (0, 100) };
+            Self::export_thread(timeline.clone(), region_config, scope, cancelled, subtitles.clone())
+                .map_err(|e| format!("구간 {} ({}) 실패: {}", i + 1, region_config.output_path, e))?;
+        }
+        Ok(())
+    }
+
     /// Export 메인 루프 (백그라운드 스레드)
     fn export_thread(
         timeline: Arc<Mutex<Timeline>>,
         config: &ExportConfig,
-        progress: &AtomicU32,
+        progress: ProgressScope,
         cancelled: &AtomicBool,
-        subtitles: Option<&SubtitleOverlayList>,
+        subtitles: Option<Arc<SubtitleOverlayList>>,
     ) -> Result<(), String> {
         eprintln!(
             "[EXPORT] 시작: {}x{} @ {}fps, CRF={}, 출력={}",
             config.width, config.height, config.fps, config.crf, config.output_path
         );
 
+        // 컨테이너/코덱 조합 검증 (파일을 만들기 전에 실패)
+        config.validate()?;
+        let container = config.resolved_container();
+
         // 0. 출력 디렉토리 생성
         let output_path = Path::new(&config.output_path);
         if let Some(parent) = output_path.parent() {
@@ -157,143 +613,517 @@ impl ExportJob {
             return Err("타임라인이 비어있습니다".to_string());
         }
 
-        eprintln!("[EXPORT] 타임라인 길이: {}ms", duration_ms);
+        let (range_start, range_end) = config.resolved_range(duration_ms)?;
+        eprintln!("[EXPORT] 타임라인 길이: {}ms, 구간: {}~{}ms", duration_ms, range_start, range_end);
+
+        // 오디오 전용: 비디오 프레임 렌더링 없이 믹스만 인코딩
+        if container.is_audio_only() {
+            return Self::export_audio_thread(timeline, config, container, (range_start, range_end), progress, cancelled);
+        }
+        let video_settings = config.video_settings()?;
 
         // 2. Export용 전용 Renderer + AudioMixer 생성
-        let mut renderer = Renderer::new_for_export(
-            timeline.clone(),
-            config.width,
-            config.height,
-        );
+        let mut renderer = Self::export_renderer(&timeline, config, subtitles.clone(), video_settings);
         let mut audio_mixer = AudioMixer::new();
+        let frame_duration_ms = 1000.0 / config.fps;
+        let total_frames = (((range_end - range_start) as f64) / frame_duration_ms).ceil() as i64;
 
-        // 3. 비ASCII 경로 처리
-        let (encoder_path, needs_move) = Self::safe_encoder_path(&config.output_path);
+        // 2-1. 2-pass 1차 패스 (소프트웨어 인코더만, 진행률 앞 절반)
+        let two_pass_log = if VideoEncoder::needs_first_pass(&video_settings) {
+            let log = TwoPassLog::new();
+            eprintln!("[EXPORT] 2-pass 1차 패스 (통계: {})", log.stats.display());
+            Self::first_pass(&mut renderer, config, video_settings, (range_start, range_end), progress.pass(0, 2), cancelled, &log)?;
+            Some(log)
+        } else {
+            None
+        };
+        let (progress, pass) = match &two_pass_log {
+            Some(log) => (progress.pass(1, 2), EncoderPass::Second(&log.stats)),
+            None => (progress, EncoderPass::Single),
+        };
+
+        // 2-2. 병렬 구간 Export: 워커 스레드가 구간 파일 인코딩 (진행률 0~90%, 결합은 90~100%)
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let (workers, worker_threads) = resolve_workers(config.parallel_workers, cores);
+        let parallel_plan = if config.parallel_export
+            && !config.smart_render
+            && VideoEncoder::supports_parallel_segments(&video_settings)
+        {
+            plan_parallel_segments(total_frames, config.fps, workers)
+        } else {
+            None
+        };
+        let (progress, segment_files) = match parallel_plan {
+            Some(plan) => {
+                let files = SegmentFiles::new(plan);
+                eprintln!(
+                    "[EXPORT] 병렬 구간 Export: 구간 {}개, 워커 {}개 × 인코더 {}스레드",
+                    files.segments.len(), workers, worker_threads
+                );
+                let work = ParallelWork {
+                    timeline: &timeline,
+                    config,
+                    subtitles: &subtitles,
+                    video_settings: VideoSettings { threads: worker_threads as u32, ..video_settings },
+                    range_start,
+                    files: &files,
+                    next_segment: AtomicUsize::new(0),
+                    done_frames: AtomicI64::new(0),
+                    failed: AtomicBool::new(false),
+                    progress: progress.within(0, 90),
+                    cancelled,
+                };
+                Self::render_parallel(&work, workers)?;
+                (progress.within(90, 100), Some(files))
+            }
+            None => (progress, None),
+        };
+
+        // 3. 비ASCII 경로 처리 (이미지 시퀀스는 파일명 패턴으로 직접 기록)
+        let (encoder_path, needs_move) = if container == Container::ImageSequence {
+            (image_sequence_pattern(&config.output_path), false)
+        } else {
+            Self::safe_encoder_path(&config.output_path)
+        };
 
         // 4. VideoEncoder 생성 (인코더 타입 전달)
-        let enc_type = EncoderType::from_u32(config.encoder_type);
-        let (mut encoder, encoder_path, needs_move) = match VideoEncoder::new(
-            &encoder_path,
-            config.width,
-            config.height,
-            config.fps,
-            config.crf,
-            enc_type,
-        ) {
+        // 병렬 Export 결합: 구간 파일 패킷만 복사 → 비디오 인코더 없이 먹서만 생성
+        // 구간 파일은 모두 같은 설정 → 가장 큰 재정렬 지연으로 dts를 맞춰 이어 붙임
+        let concat_source = match &segment_files {
+            Some(files) => {
+                let mut dts_delay = 0;
+                for (_, path) in &files.segments {
+                    dts_delay = dts_delay.max(probe_segment_delay(Path::new(path), config.fps)?);
+                }
+                let first_segment = files.segments.first().map(|(_, path)| Path::new(path))
+                    .ok_or("병렬 Export 구간이 없습니다")?;
+                Some((first_segment, dts_delay))
+            }
+            None => None,
+        };
+        let open_encoder = |path: &str| match concat_source {
+            Some((first_segment, dts_delay)) => VideoEncoder::for_segment_concat(
+                path,
+                container,
+                (config.width, config.height),
+                config.fps,
+                first_segment,
+                dts_delay,
+            ),
+            None => VideoEncoder::with_pass(
+                path,
+                container,
+                (config.width, config.height),
+                config.fps,
+                config.crf,
+                video_settings,
+                pass,
+            ),
+        };
+        let (mut encoder, encoder_path, needs_move) = match open_encoder(&encoder_path) {
             Ok(enc) => (enc, encoder_path, needs_move),
             Err(e) if needs_move => {
                 eprintln!("[EXPORT] 안전 경로 실패 ({}), 원본 경로로 재시도", e);
-                let enc = VideoEncoder::new(
-                    &config.output_path,
-                    config.width,
-                    config.height,
-                    config.fps,
-                    config.crf,
-                    enc_type,
-                ).map_err(|e2| format!("인코더 생성 실패: {} (재시도: {})", e, e2))?;
+                let enc = open_encoder(&config.output_path)
+                    .map_err(|e2| format!("인코더 생성 실패: {} (재시도: {})", e, e2))?;
                 (enc, config.output_path.clone(), false)
             }
             Err(e) => return Err(format!("인코더 생성 실패: {}", e)),
         };
 
-        // 5. AAC 오디오 인코더 초기화 (48kHz stereo, 192kbps)
-        match encoder.init_audio(48000, 2, 192000) {
-            Ok(()) => eprintln!("[EXPORT] 오디오 인코더 초기화 성공"),
-            Err(e) => {
-                // 오디오 인코더 실패해도 비디오만이라도 Export 계속
-                eprintln!("[EXPORT] 오디오 인코더 초기화 실패 (비디오만 Export): {}", e);
+        // 5. 오디오 인코더 초기화 (오디오 제외 / 이미지 시퀀스는 오디오 없음)
+        // 요청한 오디오 사양을 만들 수 없으면 비디오만 내보내지 않고 실패 처리
+        let has_audio = match config.audio_settings() {
+            Some(audio_settings) => {
+                encoder.init_audio(audio_settings)
+                    .map_err(|e| format!("오디오 인코더 초기화 실패: {}", e))?;
+                eprintln!("[EXPORT] 오디오 인코더 초기화 성공");
+                true
+            }
+            None => false,
+        };
+
+        // 5-1. 소프트 자막 스트림 (실패해도 자막 없이 Export 계속)
+        // 구간과 겹치는 cue만, 구간 시작 기준 시간으로 이동
+        let subtitle_codec = SubtitleCodec::from_u32(config.subtitle_codec).resolve(container);
+        let mut soft_subtitles: Vec<(usize, Vec<SubtitleCue>, usize)> = Vec::new();
+        for track in &config.subtitle_tracks {
+            let mut track = track.clone();
+            track.cues.retain(|c| c.start_ms < range_end && c.end_ms > range_start);
+            for cue in &mut track.cues {
+                cue.start_ms = cue.start_ms.max(range_start) - range_start;
+                cue.end_ms = cue.end_ms.min(range_end) - range_start;
+            }
+            track.normalize();
+            if track.cues.is_empty() {
+                continue;
+            }
+            match encoder.add_subtitle_stream(&track.language, track.title.as_deref(), subtitle_codec) {
+                Ok(index) => soft_subtitles.push((index, track.cues, 0)),
+                Err(e) => eprintln!("[EXPORT] 자막 스트림 추가 실패 (자막 없이 계속): {}", e),
             }
         }
 
-        // 6. 헤더 작성 (비디오+오디오 스트림 모두 등록 후)
+        // 5-2. 스마트 렌더 계획 (H.264 YUV420P → MP4/MOV/MKV, 원본 avcC를 그대로 쓸 수 있는 컨테이너)
+        // 소스 파일 스캔 동안 프리뷰가 멈추지 않도록 Timeline 복사본으로 계획
+        let smart_plan = if config.smart_render
+            && video_settings.rate.mode == RateControl::Crf
+            && video_settings.codec == VideoCodec::H264
+            && video_settings.pixel_format == OutputPixelFormat::Yuv420p
+            && matches!(container, Container::Mp4 | Container::Mov | Container::Mkv)
+        {
+            let tl = timeline.lock()
+                .map_err(|e| format!("Timeline lock failed: {}", e))?
+                .clone();
+            let burn_in = subtitles.as_deref().or(tl.subtitles.as_deref());
+            plan_smart_render(
+                &tl,
+                burn_in,
+                (range_start, range_end),
+                (config.width, config.height, config.fps),
+                total_frames,
+                |path| probe_copy_source(path, config.fps)
+                    .map_err(|e| eprintln!("[EXPORT] 복사 소스 조사 실패 (재인코딩): {}: {}", path.display(), e))
+                    .ok(),
+            )
+        } else {
+            None
+        };
+        let segments = match (smart_plan, &segment_files) {
+            // 구간 파일 결합 (for_segment_concat으로 생성한 출력)
+            (_, Some(files)) => {
+                files.segments.iter()
+                    .map(|(segment, path)| SmartSegment::Copy {
+                        start_frame: segment.start_frame,
+                        end_frame: segment.end_frame,
+                        file_path: PathBuf::from(path),
+                        source_start_ms: 0,
+                        source_end_ms: 0,
+                    })
+                    .collect()
+            }
+            (Some(plan), None) => {
+                encoder.enable_smart_render(&plan.stream_source, plan.dts_delay)
+                    .map_err(|e| format!("스마트 렌더 초기화 실패: {}", e))?;
+                let copy_frames: i64 = plan.segments.iter()
+                    .map(|s| match s {
+                        SmartSegment::Copy { start_frame, end_frame, .. } => end_frame - start_frame,
+                        SmartSegment::Render { .. } => 0,
+                    })
+                    .sum();
+                eprintln!(
+                    "[EXPORT] 스마트 렌더: 구간 {}개, 복사 {}/{}프레임",
+                    plan.segments.len(), copy_frames, total_frames
+                );
+                plan.segments
+            }
+            (None, None) => vec![SmartSegment::Render { start_frame: 0, end_frame: total_frames }],
+        };
+
+        // 6. 헤더 작성 (비디오+오디오+자막 스트림 모두 등록 후)
         encoder.write_header()?;
 
-        // 7. 프레임 단위로 렌더링 → 인코딩
-        let frame_duration_ms = 1000.0 / config.fps;
-        let total_frames = ((duration_ms as f64) / frame_duration_ms).ceil() as i64;
+        // 7. 구간별로 렌더링 → 인코딩 또는 원본 패킷 복사 (병렬 Export는 구간 파일 패킷 복사)
         let mut frame_index: i64 = 0;
 
         eprintln!("[EXPORT] 총 프레임: {}", total_frames);
 
-        loop {
-            // 취소 확인
-            if cancelled.load(Ordering::SeqCst) {
-                eprintln!("[EXPORT] 취소됨 (frame {}/{})", frame_index, total_frames);
-                let _ = encoder.finish();
-                if needs_move {
-                    let _ = std::fs::remove_file(&encoder_path);
+        for segment in &segments {
+            match segment {
+                SmartSegment::Render { end_frame, .. } => {
+                    while frame_index < *end_frame {
+                        // 취소 확인
+                        if cancelled.load(Ordering::SeqCst) {
+                            eprintln!("[EXPORT] 취소됨 (frame {}/{})", frame_index, total_frames);
+                            return Self::abort_cancelled(&mut encoder, &encoder_path, needs_move);
+                        }
+
+                        let timestamp_ms = range_start + (frame_index as f64 * frame_duration_ms) as i64;
+
+                        // 시작 시간이 지난 자막 cue 기록 (비디오 pts 순서에 맞춰 인터리빙)
+                        Self::write_pending_cues(&mut encoder, &mut soft_subtitles, timestamp_ms - range_start)?;
+
+                        // 비디오 프레임 렌더링
+                        let frame = renderer.render_frame(timestamp_ms)
+                            .map_err(|e| format!("렌더링 실패 ({}ms): {}", timestamp_ms, e))?;
+
+                        if frame_index == 0 {
+                            eprintln!(
+                                "[EXPORT] 첫 프레임: rendered={}x{}, encoder={}x{}, data={}bytes",
+                                frame.width, frame.height,
+                                encoder.width(), encoder.height(),
+                                frame.data.len()
+                            );
+                        }
+
+                        // 자막은 Renderer가 번인 (프리뷰와 동일 경로, 자막 없는 프레임은 변환 손실 없음)
+                        if frame.is_yuv {
+                            encoder.encode_frame_yuv(&frame.data, frame.width, frame.height)?;
+                        } else {
+                            encoder.encode_frame(&frame.data, frame.width, frame.height)?;
+                        }
+
+                        // 오디오 믹싱 + 인코딩
+                        if has_audio {
+                            Self::encode_frame_audio(&timeline, &mut audio_mixer, &mut encoder, timestamp_ms, frame_duration_ms)?;
+                        }
+
+                        // 진행률 업데이트
+                        let pct = ((frame_index + 1) * 100 / total_frames).min(99) as u32;
+                        progress.store(pct);
+
+                        frame_index += 1;
+
+                        // 매 300프레임(~10초)마다 로그
+                        if frame_index % 300 == 0 {
+                            eprintln!("[EXPORT] 진행: {}/{} ({}%)", frame_index, total_frames, pct);
+                        }
+                    }
+                }
+                SmartSegment::Copy { end_frame, file_path, source_start_ms, source_end_ms, .. } => {
+                    // 1초 분량씩 패킷 복사 → 같은 구간 자막/오디오 기록 (먹서 인터리빙 버퍼 최소화)
+                    let chunk_frames = config.fps.ceil() as i64;
+                    let base_frame = frame_index;
+                    let mut copy = if segment_files.is_some() {
+                        PacketCopy::open_segment(file_path)?
+                    } else {
+                        PacketCopy::open(file_path, *source_start_ms, *source_end_ms)?
+                    };
+                    loop {
+                        if cancelled.load(Ordering::SeqCst) {
+                            eprintln!("[EXPORT] 취소됨 (frame {}/{})", frame_index, total_frames);
+                            return Self::abort_cancelled(&mut encoder, &encoder_path, needs_move);
+                        }
+
+                        // 마지막 조각은 끝 GOP 경계까지 (복사 프레임 수는 계획과 ±1 다를 수 있음)
+                        let until_frame = if frame_index + chunk_frames >= *end_frame {
+                            i64::MAX
+                        } else {
+                            frame_index + chunk_frames
+                        };
+                        let done = encoder.copy_packets(&mut copy, until_frame)?;
+                        let written_until = if done { base_frame + copy.copied_frames() } else { until_frame };
+
+                        while frame_index < written_until {
+                            let timestamp_ms = range_start + (frame_index as f64 * frame_duration_ms) as i64;
+                            Self::write_pending_cues(&mut encoder, &mut soft_subtitles, timestamp_ms - range_start)?;
+                            if has_audio {
+                                Self::encode_frame_audio(&timeline, &mut audio_mixer, &mut encoder, timestamp_ms, frame_duration_ms)?;
+                            }
+                            frame_index += 1;
+                        }
+                        progress.store((frame_index * 100 / total_frames).min(99) as u32);
+
+                        if done {
+                            break;
+                        }
+                    }
                 }
-                return Err("Export가 취소되었습니다".to_string());
             }
+        }
+
+        // 8. 남은 자막 cue 기록 → 인코딩 완료 (flush + trailer)
+        Self::write_pending_cues(&mut encoder, &mut soft_subtitles, i64::MAX)?;
+        encoder.finish()?;
+
+        // 9. 임시 파일을 최종 경로로 이동 (비ASCII 경로)
+        if needs_move {
+            eprintln!("[EXPORT] 임시 파일 이동: {} → {}", encoder_path, config.output_path);
+            Self::move_file(&encoder_path, &config.output_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Export용 Renderer 생성 (자막 목록 지정 + 출력 픽셀 포맷에 맞는 합성 결과 형식)
+    fn export_renderer(
+        timeline: &Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        subtitles: Option<Arc<SubtitleOverlayList>>,
+        video_settings: VideoSettings,
+    ) -> Renderer {
+        let mut renderer = Renderer::new_for_export(
+            timeline.clone(),
+            config.width,
+            config.height,
+        );
+        // 전달된 자막 목록이 있으면 Timeline 자막 대신 사용 (없으면 프리뷰와 동일한 Timeline 자막)
+        if subtitles.is_some() {
+            renderer.set_subtitle_override(subtitles);
+        }
+        // 4:2:0 이외 출력: 합성 결과를 RGBA로 받아 인코더에서 한 번만 변환 (크로마 손실 방지)
+        if video_settings.pixel_format.has_alpha() {
+            renderer.set_export_alpha(true);
+        } else if video_settings.pixel_format != OutputPixelFormat::Yuv420p {
+            renderer.set_export_rgba(true);
+        }
+        renderer
+    }
+
+    /// 병렬 구간 렌더링 (워커 스레드 workers개, 모두 끝날 때까지 대기)
+    /// 실패한 워커가 있으면 첫 에러 반환, 취소되면 구간 파일은 SegmentFiles drop에서 삭제
+    fn render_parallel(work: &ParallelWork, workers: usize) -> Result<(), String> {
+        let workers = workers.min(work.files.segments.len());
+        let results: Vec<Result<(), String>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| scope.spawn(|| {
+                    let result = work.run_worker();
+                    if result.is_err() {
+                        work.failed.store(true, Ordering::SeqCst);
+                    }
+                    result
+                }))
+                .collect();
+            handles.into_iter()
+                .map(|h| h.join().unwrap_or_else(|_| Err("병렬 Export 워커 스레드 패닉".to_string())))
+                .collect()
+        });
+        results.into_iter().collect::<Result<(), String>>()?;
 
-            let timestamp_ms = (frame_index as f64 * frame_duration_ms) as i64;
-            if timestamp_ms >= duration_ms {
-                break;
+        if work.cancelled.load(Ordering::SeqCst) {
+            eprintln!("[EXPORT] 취소됨 (병렬 구간 렌더링)");
+            return Err("Export가 취소되었습니다".to_string());
+        }
+        eprintln!("[EXPORT] 병렬 구간 렌더링 완료 ({}프레임)", work.done_frames.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    /// 2-pass 1차 패스 (비디오만 렌더링 → null 먹서, 통계 파일만 남김)
+    /// renderer: 2차 패스와 공유 (디코더 캐시 재사용)
+    fn first_pass(
+        renderer: &mut Renderer,
+        config: &ExportConfig,
+        video_settings: VideoSettings,
+        (range_start, range_end): (i64, i64),
+        progress: ProgressScope,
+        cancelled: &AtomicBool,
+        log: &TwoPassLog,
+    ) -> Result<(), String> {
+        let mut encoder = VideoEncoder::with_pass(
+            &log.null_output,
+            config.resolved_container(),
+            (config.width, config.height),
+            config.fps,
+            config.crf,
+            video_settings,
+            EncoderPass::First(&log.stats),
+        ).map_err(|e| format!("1차 패스 인코더 생성 실패: {}", e))?;
+        encoder.write_header()?;
+
+        let frame_duration_ms = 1000.0 / config.fps;
+        let total_frames = (((range_end - range_start) as f64) / frame_duration_ms).ceil() as i64;
+        for frame_index in 0..total_frames {
+            if cancelled.load(Ordering::SeqCst) {
+                eprintln!("[EXPORT] 취소됨 (1차 패스 frame {}/{})", frame_index, total_frames);
+                let _ = encoder.finish();
+                return Err("Export가 취소되었습니다".to_string());
             }
 
-            // 비디오 프레임 렌더링
+            let timestamp_ms = range_start + (frame_index as f64 * frame_duration_ms) as i64;
             let frame = renderer.render_frame(timestamp_ms)
                 .map_err(|e| format!("렌더링 실패 ({}ms): {}", timestamp_ms, e))?;
-
-            if frame_index == 0 {
-                eprintln!(
-                    "[EXPORT] 첫 프레임: rendered={}x{}, encoder={}x{}, data={}bytes",
-                    frame.width, frame.height,
-                    encoder.width(), encoder.height(),
-                    frame.data.len()
-                );
+            if frame.is_yuv {
+                encoder.encode_frame_yuv(&frame.data, frame.width, frame.height)?;
+            } else {
+                encoder.encode_frame(&frame.data, frame.width, frame.height)?;
             }
+            progress.store(((frame_index + 1) * 100 / total_frames).min(99) as u32);
+        }
+        encoder.finish()?;
+        eprintln!("[EXPORT] 2-pass 1차 패스 완료 ({}프레임)", total_frames);
+        Ok(())
+    }
 
-            // 자막 오버레이 합성 (있을 때만 RGBA 경로)
-            if let Some(overlay) = subtitles.and_then(|s| s.get_active(timestamp_ms)) {
-                // 자막 프레임: YUV→RGBA 변환 → 알파 블렌딩 → RGBA 인코딩
-                let mut rgba = if frame.is_yuv {
-                    yuv420p_to_rgba(&frame.data, frame.width, frame.height)
-                } else {
-                    frame.data.clone()
-                };
-                blend_overlay_rgba(&mut rgba, frame.width, frame.height, overlay);
-                // RGBA→YUV420P 변환 후 인코딩 (YUV 직접 경로 유지)
-                let yuv = rgba_to_yuv420p(&rgba, frame.width, frame.height);
-                encoder.encode_frame_yuv(&yuv, frame.width, frame.height)?;
-            } else {
-                // 자막 없는 프레임: 기존 직접 경로 (변환 손실 없음)
-                if frame.is_yuv {
-                    encoder.encode_frame_yuv(&frame.data, frame.width, frame.height)?;
-                } else {
-                    encoder.encode_frame(&frame.data, frame.width, frame.height)?;
+    /// 프레임 1개 구간의 오디오 믹싱 + 인코딩 (렌더링/복사 구간 공통)
+    fn encode_frame_audio(
+        timeline: &Mutex<Timeline>,
+        audio_mixer: &mut AudioMixer,
+        encoder: &mut VideoEncoder,
+        timestamp_ms: i64,
+        frame_duration_ms: f64,
+    ) -> Result<(), String> {
+        let audio_clips = {
+            let tl = timeline.lock()
+                .map_err(|e| format!("Timeline lock failed: {}", e))?;
+            tl.get_all_audio_sources_at_time(timestamp_ms)
+        };
+        let audio_samples = audio_mixer.mix_range(
+            &audio_clips,
+            timestamp_ms,
+            frame_duration_ms,
+        );
+        encoder.encode_audio_samples(&audio_samples)
+    }
+
+    /// 취소된 Export 정리 (인코더 마무리 + 임시 파일 삭제)
+    fn abort_cancelled(encoder: &mut VideoEncoder, encoder_path: &str, needs_move: bool) -> Result<(), String> {
+        let _ = encoder.finish();
+        if needs_move {
+            let _ = std::fs::remove_file(encoder_path);
+        }
+        Err("Export가 취소되었습니다".to_string())
+    }
+
+    /// 오디오 전용 Export (WAV/FLAC/MP3/M4A) — Renderer 없이 AudioMixer 출력만 인코딩
+    /// range: 확정된 Export 구간 (타임라인 ms)
+    fn export_audio_thread(
+        timeline: Arc<Mutex<Timeline>>,
+        config: &ExportConfig,
+        container: Container,
+        range: (i64, i64),
+        progress: ProgressScope,
+        cancelled: &AtomicBool,
+    ) -> Result<(), String> {
+        let (range_start, range_end) = range;
+        let audio_settings = config.audio_settings()
+            .ok_or("오디오 전용 Export에서 오디오를 제외할 수 없습니다")?;
+
+        // 비ASCII 경로 처리 (실패 시 원본 경로로 재시도)
+        let (encoder_path, needs_move) = Self::safe_encoder_path(&config.output_path);
+        let (mut encoder, encoder_path, needs_move) =
+            match AudioEncoder::new(&encoder_path, container, audio_settings) {
+                Ok(enc) => (enc, encoder_path, needs_move),
+                Err(e) if needs_move => {
+                    eprintln!("[EXPORT] 안전 경로 실패 ({}), 원본 경로로 재시도", e);
+                    let enc = AudioEncoder::new(&config.output_path, container, audio_settings)
+                        .map_err(|e2| format!("인코더 생성 실패: {} (재시도: {})", e, e2))?;
+                    (enc, config.output_path.clone(), false)
                 }
+                Err(e) => return Err(format!("인코더 생성 실패: {}", e)),
+            };
+        encoder.write_header()?;
+
+        // 20ms 단위 믹스 (48kHz에서 960샘플, 정수 ms라 구간 누적 오차 없음)
+        const CHUNK_MS: i64 = 20;
+        let mut audio_mixer = AudioMixer::new();
+        let mut timestamp_ms = range_start;
+
+        while timestamp_ms < range_end {
+            if cancelled.load(Ordering::SeqCst) {
+                eprintln!("[EXPORT] 취소됨 ({}ms/{}ms)", timestamp_ms, range_end);
+                let _ = encoder.finish();
+                if needs_move {
+                    let _ = std::fs::remove_file(&encoder_path);
+                }
+                return Err("Export가 취소되었습니다".to_string());
             }
 
-            // 오디오 믹싱 + 인코딩
+            let chunk_ms = CHUNK_MS.min(range_end - timestamp_ms);
             let audio_clips = {
                 let tl = timeline.lock()
                     .map_err(|e| format!("Timeline lock failed: {}", e))?;
                 tl.get_all_audio_sources_at_time(timestamp_ms)
             };
-            let audio_samples = audio_mixer.mix_range(
-                &audio_clips,
-                timestamp_ms,
-                frame_duration_ms,
-            );
+            let audio_samples = audio_mixer.mix_range(&audio_clips, timestamp_ms, chunk_ms as f64);
             encoder.encode_audio_samples(&audio_samples)?;
 
-            // 진행률 업데이트
-            let pct = ((frame_index + 1) * 100 / total_frames).min(99) as u32;
-            progress.store(pct, Ordering::SeqCst);
-
-            frame_index += 1;
-
-            // 매 300프레임(~10초)마다 로그
-            if frame_index % 300 == 0 {
-                eprintln!("[EXPORT] 진행: {}/{} ({}%)", frame_index, total_frames, pct);
-            }
+            timestamp_ms += chunk_ms;
+            let pct = ((timestamp_ms - range_start) * 100 / (range_end - range_start)).min(99) as u32;
+            progress.store(pct);
         }
 
-        // 8. 인코딩 완료 (flush + trailer)
         encoder.finish()?;
 
-        // 9. 임시 파일을 최종 경로로 이동 (비ASCII 경로)
         if needs_move {
             eprintln!("[EXPORT] 임시 파일 이동: {} → {}", encoder_path, config.output_path);
             Self::move_file(&encoder_path, &config.output_path)?;
@@ -322,3 +1152,72 @@ impl ExportJob {
         self.error.lock().ok().and_then(|e| e.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeline::{GeneratorKind, GeneratorParams};
+
+    #[test]
+    fn test_two_pass_stats_handoff() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(64, 36, 25.0)));
+        {
+            let mut tl = timeline.lock().unwrap();
+            let track_id = tl.add_video_track();
+            tl.add_generator_clip(track_id, GeneratorParams::new(GeneratorKind::SolidColor), 0, 1000).unwrap();
+        }
+
+        // libx264: 통계 파일 직접 기록, libvpx-vp9: stats_out 메모리 → finish에서 파일로
+        for (codec, extension) in [(VideoCodec::H264, "mp4"), (VideoCodec::Vp9, "webm")] {
+            let output = std::env::temp_dir().join(format!("vortex_2pass_test_{}.{}", std::process::id(), extension));
+            let mut config = ExportConfig::new(output.to_string_lossy(), 64, 36, 25.0, 23);
+            config.video_codec = codec as u32;
+            config.encoder_type = EncoderType::Software as u32;
+            config.rate_control = RateControl::TwoPass as u32;
+            config.video_bitrate = 500;
+            config.include_audio = false;
+            config.validate().unwrap();
+            let video_settings = config.video_settings().unwrap();
+            assert!(VideoEncoder::needs_first_pass(&video_settings), "{:?}", codec);
+
+            let progress = AtomicU32::new(0);
+            let scope = ProgressScope { progress: &progress, index: 0, count: 1, span: (0, 100) };
+            let log = TwoPassLog::new();
+            let mut renderer = ExportJob::export_renderer(&timeline, &config, None, video_settings);
+            ExportJob::first_pass(&mut renderer, &config, video_settings, (0, 1000), scope, &AtomicBool::new(false), &log)
+                .unwrap();
+            let stats_len = std::fs::metadata(&log.stats).map_or(0, |m| m.len());
+            assert!(stats_len > 0, "{:?} 1차 통계 없음", codec);
+
+            // 2차: 1차 통계로 인코딩
+            let mut encoder = VideoEncoder::with_pass(
+                &config.output_path,
+                config.resolved_container(),
+                (64, 36),
+                25.0,
+                23,
+                video_settings,
+                EncoderPass::Second(&log.stats),
+            )
+            .unwrap();
+            encoder.write_header().unwrap();
+            for frame_index in 0..25 {
+                let frame = renderer.render_frame(frame_index * 40).unwrap();
+                if frame.is_yuv {
+                    encoder.encode_frame_yuv(&frame.data, frame.width, frame.height).unwrap();
+                } else {
+                    encoder.encode_frame(&frame.data, frame.width, frame.height).unwrap();
+                }
+            }
+            encoder.finish().unwrap();
+            let output_len = std::fs::metadata(&output).map_or(0, |m| m.len());
+            let _ = std::fs::remove_file(&output);
+            assert!(output_len > 0, "{:?} 2차 출력 없음", codec);
+
+            // Export가 끝나면 통계 파일 삭제
+            let stats = log.stats.clone();
+            drop(log);
+            assert!(!stats.exists());
+        }
+    }
+}
@@ -1,7 +1,17 @@
 // 인코딩 & 내보내기 모듈
-// H.264 비디오 + AAC 오디오 → MP4 컨테이너
+// H.264/HEVC/AV1/VP9 비디오 + AAC/Opus/MP3/FLAC/PCM 오디오 → MP4/MOV/MKV/WebM 컨테이너
+// 오디오 전용 Export → WAV/FLAC/MP3/M4A
+// 이미지 시퀀스 / 스틸 프레임 → PNG/JPEG/TIFF
+// 스마트 렌더: 편집되지 않은 구간은 원본 패킷 복사
+// 병렬 구간 Export: GOP 단위 구간을 워커 스레드로 인코딩 후 무손실 결합
+// 이름 있는 Export 프리셋 (기본 제공 + JSON/TOML 파일)
 
+pub mod container;
 pub mod encoder;
 pub mod exporter;
+pub mod still;
+pub mod smart_render;
+pub mod parallel;
+pub mod preset;
 pub mod audio_decoder;
 pub mod audio_mixer;
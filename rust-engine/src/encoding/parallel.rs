@@ -0,0 +1,106 @@
+// 병렬 구간 Export - 타임라인을 GOP 단위 구간으로 나눠 워커 스레드마다 별도 Renderer/인코더로 인코딩
+// 구간 파일은 출력과 같은 컨테이너/코덱 설정 → 결합 단계에서 패킷 그대로 이어 붙임 (재인코딩 없음)
+// 구간마다 새 인코더 → 구간 시작이 항상 키프레임 (닫힌 GOP 경계)
+
+/// 구간 경계 단위 (초, 구간 길이는 이 GOP 길이의 배수)
+const GOP_SECONDS: f64 = 2.0;
+/// 구간 최소 길이 (GOP 수, 인코더 생성/lookahead 비용 대비)
+const MIN_SEGMENT_GOPS: i64 = 5;
+/// 워커당 구간 수 (구간마다 렌더링 비용이 달라도 작업 큐로 부하 분산)
+const SEGMENTS_PER_WORKER: i64 = 4;
+/// 자동 워커 수 상한 (워커마다 Renderer 디코더 + 인코더 lookahead 메모리)
+const MAX_AUTO_WORKERS: usize = 8;
+
+/// 병렬 Export 구간 1개 (출력 프레임 번호, end 제외)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelSegment {
+    pub start_frame: i64,
+    pub end_frame: i64,
+}
+
+/// GOP 길이 (프레임)
+pub fn gop_frames(fps: f64) -> i64 {
+    (fps * GOP_SECONDS).round().max(1.0) as i64
+}
+
+/// 워커 수 + 워커당 인코더 스레드 수 확정
+/// requested: 0=자동 (코어 4개당 워커 1개, 2~8개), 그 외 지정한 워커 수
+pub fn resolve_workers(requested: u32, cores: usize) -> (usize, usize) {
+    let workers = match requested {
+        0 => (cores / 4).clamp(2, MAX_AUTO_WORKERS),
+        n => n as usize,
+    };
+    (workers, (cores / workers).max(1))
+}
+
+/// 구간 계획: GOP 배수 길이로 균등 분할 (마지막 구간만 GOP 배수가 아닐 수 있음)
+/// 워커가 2개 미만이거나 구간을 2개 이상 만들 수 없으면 None (단일 스레드 Export)
+pub fn plan_parallel_segments(total_frames: i64, fps: f64, workers: usize) -> Option<Vec<ParallelSegment>> {
+    if workers < 2 || total_frames <= 0 {
+        return None;
+    }
+    let gop = gop_frames(fps);
+    let total_gops = (total_frames + gop - 1) / gop;
+    let count = (workers as i64 * SEGMENTS_PER_WORKER).min(total_gops / MIN_SEGMENT_GOPS);
+    if count < 2 {
+        return None;
+    }
+
+    let boundary = |i: i64| (i * total_gops / count * gop).min(total_frames);
+    Some((0..count)
+        .map(|i| ParallelSegment { start_frame: boundary(i), end_frame: boundary(i + 1) })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segments_cover_range_on_gop_boundaries() {
+        // 25fps → GOP 50프레임, 10분 + 7프레임
+        let total = 25 * 600 + 7;
+        let segments = plan_parallel_segments(total, 25.0, 4).unwrap();
+        assert_eq!(segments.len(), 16);
+        assert_eq!(segments[0].start_frame, 0);
+        assert_eq!(segments.last().unwrap().end_frame, total);
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].end_frame, pair[1].start_frame);
+        }
+        for segment in &segments {
+            assert_eq!(segment.start_frame % 50, 0);
+            assert!(segment.end_frame - segment.start_frame >= 50 * MIN_SEGMENT_GOPS);
+        }
+    }
+
+    #[test]
+    fn short_timeline_limits_segment_count() {
+        // 29.97fps → GOP 60프레임, 30초 = GOP 15개 → 최소 5 GOP씩 3구간
+        let segments = plan_parallel_segments(899, 29.97, 8).unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                ParallelSegment { start_frame: 0, end_frame: 300 },
+                ParallelSegment { start_frame: 300, end_frame: 600 },
+                ParallelSegment { start_frame: 600, end_frame: 899 },
+            ]
+        );
+    }
+
+    #[test]
+    fn too_short_or_single_worker_is_sequential() {
+        // 15초 = GOP 8개 → 구간 1개
+        assert_eq!(plan_parallel_segments(375, 25.0, 4), None);
+        assert_eq!(plan_parallel_segments(25 * 600, 25.0, 1), None);
+        assert_eq!(plan_parallel_segments(0, 25.0, 4), None);
+    }
+
+    #[test]
+    fn worker_count_splits_cores() {
+        assert_eq!(resolve_workers(0, 32), (8, 4));
+        assert_eq!(resolve_workers(0, 64), (8, 8));
+        assert_eq!(resolve_workers(0, 4), (2, 2));
+        assert_eq!(resolve_workers(3, 16), (3, 5));
+        assert_eq!(resolve_workers(6, 4), (6, 1));
+    }
+}
@@ -0,0 +1,505 @@
+// Export 프리셋 — 이름 있는 설정 묶음 (YouTube 1080p / Instagram Reel / Broadcast ProRes / Web Proxy)
+// 프리셋 → 출력 경로만 빠진 ExportConfig 전체 (해상도·fps·코덱·컨테이너·비트레이트 제어·오디오)
+// 사용자 프리셋: JSON / TOML 파일 (serde 역직렬화, 키 = ExportConfig 필드 이름)
+// Export 시작 전 detect_available_encoders 결과로 인코더 설치 여부 검증
+
+use crate::encoding::encoder::{EncoderType, ENCODER_NAMES};
+use crate::encoding::exporter::ExportConfig;
+use serde::Deserialize;
+use std::path::Path;
+
+/// 문자열 값 → FFI u32 매핑 (배열 인덱스 = ExportConfig 값)
+const VIDEO_CODECS: [&str; 10] = ["h264", "hevc", "av1", "vp9", "prores", "dnxhr", "ffv1", "png", "jpeg", "tiff"];
+const ENCODER_TYPES: [&str; 5] = ["auto", "software", "nvenc", "qsv", "amf"];
+const SPEED_PRESETS: [&str; 3] = ["balanced", "fast", "quality"];
+const PIXEL_FORMATS: [&str; 11] = [
+    "auto", "yuv420p", "yuv422p", "yuv444p", "yuv420p10", "yuv422p10", "yuv444p10",
+    "yuva420p", "yuva444p10", "rgb24", "rgba",
+];
+const CONTAINERS: [&str; 10] = ["auto", "mp4", "mov", "mkv", "webm", "image_sequence", "wav", "flac", "mp3", "m4a"];
+const AUDIO_CODECS: [&str; 7] = ["auto", "aac", "opus", "mp3", "flac", "pcm16", "pcm24"];
+const RATE_CONTROLS: [&str; 4] = ["crf", "capped_crf", "two_pass", "cbr"];
+
+/// 이름 있는 Export 프리셋
+#[derive(Clone)]
+pub struct ExportPreset {
+    pub name: String,
+    /// 출력 경로를 제외한 전체 설정 (output_path는 비어 있음)
+    pub config: ExportConfig,
+}
+
+impl ExportPreset {
+    /// 출력 경로를 채운 Export 설정
+    pub fn config_for(&self, output_path: impl Into<String>) -> ExportConfig {
+        ExportConfig { output_path: output_path.into(), ..self.config.clone() }
+    }
+}
+
+/// 기본 제공 프리셋
+pub fn builtin_presets() -> Vec<ExportPreset> {
+    let preset = |name: &str, config: ExportConfig| ExportPreset { name: name.to_string(), config };
+    vec![
+        // H.264 CRF 18 + 최대 12Mbps (YouTube 1080p SDR 권장 상한), AAC 384kbps
+        preset("YouTube 1080p", ExportConfig {
+            rate_control: 1,
+            max_bitrate: 12_000,
+            container: 1,
+            audio_codec: 1,
+            audio_bitrate: 384,
+            ..ExportConfig::new("", 1920, 1080, 30.0, 18)
+        }),
+        // 세로 영상, 업로드 후 재압축되므로 2-pass 6Mbps로 파일 크기 고정
+        preset("Instagram Reel 1080x1920", ExportConfig {
+            rate_control: 2,
+            video_bitrate: 6_000,
+            container: 1,
+            audio_codec: 1,
+            audio_bitrate: 128,
+            ..ExportConfig::new("", 1080, 1920, 30.0, 20)
+        }),
+        // ProRes 422 HQ (4:2:2 10bit) + PCM 24bit, 29.97fps MOV 납품
+        preset("Broadcast ProRes", ExportConfig {
+            video_codec: 4,
+            codec_profile: 4,
+            container: 2,
+            audio_codec: 6,
+            ..ExportConfig::new("", 1920, 1080, 30000.0 / 1001.0, 18)
+        }),
+        // 540p 빠른 인코딩 (검토/공유용 프록시)
+        preset("Web Proxy", ExportConfig {
+            preset: 1,
+            container: 1,
+            audio_codec: 1,
+            audio_bitrate: 96,
+            ..ExportConfig::new("", 960, 540, 30.0, 28)
+        }),
+    ]
+}
+
+/// 프리셋 목록 (기본 제공 + 파일에서 읽은 사용자 프리셋, 같은 이름은 덮어씀)
+pub struct PresetRegistry {
+    presets: Vec<ExportPreset>,
+}
+
+impl Default for PresetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresetRegistry {
+    /// 기본 제공 프리셋으로 시작
+    pub fn new() -> Self {
+        Self { presets: builtin_presets() }
+    }
+
+    pub fn presets(&self) -> &[ExportPreset] {
+        &self.presets
+    }
+
+    /// 이름으로 찾기 (대소문자 무시)
+    pub fn get(&self, name: &str) -> Option<&ExportPreset> {
+        let name = name.trim();
+        self.presets.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// 추가 (같은 이름이 있으면 그 자리에서 교체)
+    pub fn insert(&mut self, preset: ExportPreset) {
+        match self.presets.iter_mut().find(|p| p.name.eq_ignore_ascii_case(&preset.name)) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    /// 프리셋 파일 읽기 (UTF-8, BOM 허용 / 형식은 확장자 → 내용 순으로 결정)
+    /// 반환: 추가/교체한 프리셋 수 (하나라도 잘못되면 아무것도 추가하지 않음)
+    pub fn load_file(&mut self, path: &Path) -> Result<usize, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("프리셋 파일 읽기 실패 ({}): {}", path.display(), e))?;
+        let format = PresetFormat::from_path(path).unwrap_or_else(|| PresetFormat::detect(&content));
+        self.load_str(&content, format)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// 프리셋 문자열 읽기
+    pub fn load_str(&mut self, content: &str, format: PresetFormat) -> Result<usize, String> {
+        let presets = parse_presets(content, format)?;
+        let count = presets.len();
+        for preset in presets {
+            self.insert(preset);
+        }
+        Ok(count)
+    }
+}
+
+/// 프리셋 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PresetFormat {
+    Json,
+    Toml,
+}
+
+impl PresetFormat {
+    /// 확장자로 판단 (.json / .toml)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(PresetFormat::Json),
+            "toml" => Some(PresetFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// 내용으로 추정 ('{' 또는 '['로 시작하면 JSON, "[[preset]]"·주석 등은 TOML)
+    pub fn detect(content: &str) -> Self {
+        let head = content.trim_start_matches('\u{FEFF}').trim_start();
+        if head.starts_with('{') || (head.starts_with('[') && !head.starts_with("[[")) {
+            PresetFormat::Json
+        } else {
+            PresetFormat::Toml
+        }
+    }
+}
+
+/// 프리셋 파싱 (JSON: 객체 배열 또는 {"presets": [...]}, TOML: [[preset]] 테이블 배열)
+pub fn parse_presets(content: &str, format: PresetFormat) -> Result<Vec<ExportPreset>, String> {
+    let content = content.trim_start_matches('\u{FEFF}');
+    let entries = match format {
+        PresetFormat::Json => json_entries(content)?,
+        PresetFormat::Toml => toml::from_str::<TomlPresets>(content)
+            .map_err(|e| format!("TOML {}", e))?
+            .preset,
+    };
+    if entries.is_empty() {
+        return Err("프리셋이 없습니다".to_string());
+    }
+    entries.into_iter()
+        .enumerate()
+        .map(|(i, entry)| entry.into_preset().map_err(|e| format!("프리셋 {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// 설정 검증 + 필요한 비디오 인코더가 설치되어 있는지 확인 (ExportJob::start 시작 단계, UI 사전 확인)
+/// available: detect_available_encoders 비트마스크
+/// 하드웨어 인코더가 없어도 같은 코덱의 소프트웨어 인코더가 있으면 통과 (VideoEncoder가 폴백)
+pub fn validate_with_encoders(config: &ExportConfig, available: u32) -> Result<(), String> {
+    config.validate()?;
+    if config.resolved_container().is_audio_only() {
+        return Ok(());
+    }
+
+    let video = config.video_settings()?;
+    let hardware: Vec<&str> = match video.encoder_type {
+        EncoderType::Software => Vec::new(),
+        EncoderType::Auto => [EncoderType::Nvenc, EncoderType::Qsv, EncoderType::Amf]
+            .iter()
+            .filter_map(|t| video.codec.hardware_encoder(*t))
+            .collect(),
+        hw => video.codec.hardware_encoder(hw).into_iter().collect(),
+    };
+    let candidates: Vec<&str> = hardware.into_iter()
+        .chain(video.codec.software_encoders().iter().copied())
+        .collect();
+    let is_available = |name: &str| match ENCODER_NAMES.iter().position(|n| *n == name) {
+        Some(bit) => available & (1 << bit) != 0,
+        None => true, // 감지 대상이 아닌 FFmpeg 내장 인코더 (prores, png, mjpeg, tiff)
+    };
+    if candidates.iter().copied().any(is_available) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{:?} 인코더가 설치되어 있지 않습니다 (필요: {})",
+            video.codec,
+            candidates.join(" / ")
+        ))
+    }
+}
+
+/// JSON 프리셋 → 프리셋 항목 (항목마다 역직렬화해 에러에 프리셋 번호 표시)
+fn json_entries(content: &str) -> Result<Vec<PresetEntry>, String> {
+    use serde_json::Value;
+    let root: Value = serde_json::from_str(content).map_err(|e| format!("JSON {}", e))?;
+    let items = match root {
+        Value::Array(items) => items,
+        Value::Object(mut fields) => match fields.remove("presets") {
+            Some(Value::Array(items)) => items,
+            _ => return Err("JSON 최상위 객체에 presets 배열이 없습니다".to_string()),
+        },
+        _ => return Err("JSON 최상위는 프리셋 배열이어야 합니다".to_string()),
+    };
+    items.into_iter()
+        .enumerate()
+        .map(|(i, item)| serde_json::from_value(item).map_err(|e| format!("프리셋 {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// TOML 프리셋 파일 ([[preset]] 또는 [[presets]] 테이블 배열만)
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TomlPresets {
+    #[serde(default, alias = "presets")]
+    preset: Vec<PresetEntry>,
+}
+
+/// 선택값: 이름("hevc", 대소문자 무시) 또는 FFI 번호(1)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Choice {
+    Index(u32),
+    Name(String),
+}
+
+impl Choice {
+    /// names 배열 인덱스 (= ExportConfig 값)
+    fn resolve(&self, key: &str, names: &[&str]) -> Result<u32, String> {
+        let index = match self {
+            Choice::Name(name) => names.iter().position(|n| n.eq_ignore_ascii_case(name.trim())),
+            Choice::Index(i) => Some(*i as usize).filter(|i| *i < names.len()),
+        };
+        index
+            .map(|i| i as u32)
+            .ok_or_else(|| format!("{}: {} 중 하나여야 합니다", key, names.join(" / ")))
+    }
+}
+
+/// 프리셋 파일의 프리셋 1개 (name 필수, 없는 키는 기본값, 모르는 키는 에러)
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PresetEntry {
+    name: String,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    rate_control: Choice,
+    video_bitrate: u32,
+    max_bitrate: u32,
+    buffer_size: u32,
+    encoder_type: Choice,
+    video_codec: Choice,
+    codec_profile: u32,
+    pixel_format: Choice,
+    alpha: bool,
+    preset: Choice,
+    container: Choice,
+    audio_codec: Choice,
+    audio_sample_rate: u32,
+    audio_channels: u32,
+    audio_bitrate: u32,
+    include_audio: bool,
+    smart_render: bool,
+    parallel_export: bool,
+    parallel_workers: u32,
+}
+
+impl Default for PresetEntry {
+    fn default() -> Self {
+        let config = ExportConfig::new("", 1920, 1080, 30.0, 23);
+        Self {
+            name: String::new(),
+            width: config.width,
+            height: config.height,
+            fps: config.fps,
+            crf: config.crf,
+            rate_control: Choice::Index(config.rate_control),
+            video_bitrate: config.video_bitrate,
+            max_bitrate: config.max_bitrate,
+            buffer_size: config.buffer_size,
+            encoder_type: Choice::Index(config.encoder_type),
+            video_codec: Choice::Index(config.video_codec),
+            codec_profile: config.codec_profile,
+            pixel_format: Choice::Index(config.pixel_format),
+            alpha: config.alpha,
+            preset: Choice::Index(config.preset),
+            container: Choice::Index(config.container),
+            audio_codec: Choice::Index(config.audio_codec),
+            audio_sample_rate: config.audio_sample_rate,
+            audio_channels: config.audio_channels,
+            audio_bitrate: config.audio_bitrate,
+            include_audio: config.include_audio,
+            smart_render: config.smart_render,
+            parallel_export: config.parallel_export,
+            parallel_workers: config.parallel_workers,
+        }
+    }
+}
+
+impl PresetEntry {
+    /// 선택값 이름 → FFI 번호, 값 범위 검증
+    fn into_preset(self) -> Result<ExportPreset, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("이름(name)이 없습니다".to_string());
+        }
+        if !(self.fps > 0.0 && self.fps.is_finite()) {
+            return Err("fps: 양수여야 합니다".to_string());
+        }
+        let config = ExportConfig {
+            width: self.width,
+            height: self.height,
+            fps: self.fps,
+            crf: self.crf,
+            rate_control: self.rate_control.resolve("rate_control", &RATE_CONTROLS)?,
+            video_bitrate: self.video_bitrate,
+            max_bitrate: self.max_bitrate,
+            buffer_size: self.buffer_size,
+            encoder_type: self.encoder_type.resolve("encoder_type", &ENCODER_TYPES)?,
+            video_codec: self.video_codec.resolve("video_codec", &VIDEO_CODECS)?,
+            codec_profile: self.codec_profile,
+            pixel_format: self.pixel_format.resolve("pixel_format", &PIXEL_FORMATS)?,
+            alpha: self.alpha,
+            preset: self.preset.resolve("preset", &SPEED_PRESETS)?,
+            container: self.container.resolve("container", &CONTAINERS)?,
+            audio_codec: self.audio_codec.resolve("audio_codec", &AUDIO_CODECS)?,
+            audio_sample_rate: self.audio_sample_rate,
+            audio_channels: self.audio_channels,
+            audio_bitrate: self.audio_bitrate,
+            include_audio: self.include_audio,
+            smart_render: self.smart_render,
+            parallel_export: self.parallel_export,
+            parallel_workers: self.parallel_workers,
+            ..ExportConfig::new("", 0, 0, 0.0, 0)
+        };
+        Ok(ExportPreset { name, config })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_presets_validate() {
+        for preset in builtin_presets() {
+            let config = preset.config_for("out.mp4");
+            assert!(config.validate().is_ok(), "{}", preset.name);
+        }
+        let registry = PresetRegistry::new();
+        let reel = registry.get("instagram reel 1080x1920").unwrap();
+        assert_eq!((reel.config.width, reel.config.height), (1080, 1920));
+        assert_eq!(reel.config.rate_control, 2);
+        assert!(registry.get("Unknown").is_none());
+    }
+
+    #[test]
+    fn test_parse_json_presets() {
+        let json = r#"{
+            "presets": [
+                { "name": "Vimeo 4K", "width": 3840, "height": 2160, "fps": 29.97,
+                  "video_codec": "hevc", "rate_control": "two_pass", "video_bitrate": 40000,
+                  "container": "mp4", "audio_codec": 1, "include_audio": true },
+                { "name": "Web Proxy", "crf": 30, "preset": "fast" }
+            ]
+        }"#;
+        let mut registry = PresetRegistry::new();
+        let builtin_count = registry.presets().len();
+        assert_eq!(registry.load_str(json, PresetFormat::Json), Ok(2));
+        // 같은 이름은 교체, 새 이름만 추가
+        assert_eq!(registry.presets().len(), builtin_count + 1);
+
+        let vimeo = registry.get("Vimeo 4K").unwrap();
+        assert_eq!((vimeo.config.width, vimeo.config.height), (3840, 2160));
+        assert_eq!(vimeo.config.video_codec, 1);
+        assert_eq!(vimeo.config.rate_control, 2);
+        assert_eq!(vimeo.config.video_bitrate, 40000);
+        assert_eq!(vimeo.config.audio_codec, 1);
+        assert!((vimeo.config.fps - 29.97).abs() < 1e-9);
+
+        let proxy = registry.get("web proxy").unwrap();
+        assert_eq!((proxy.config.crf, proxy.config.preset), (30, 1));
+        // 파일에 없는 키는 기본값 (기본 제공 프리셋 값이 아님)
+        assert_eq!(proxy.config.width, 1920);
+    }
+
+    #[test]
+    fn test_parse_toml_presets() {
+        let toml = "\
+# 방송 납품용
+[[preset]]
+name = \"Broadcast DNxHR\" # HQX 10bit
+video_codec = \"dnxhr\"
+codec_profile = 4
+container = 'mov'
+audio_codec = \"pcm24\"
+fps = 25.0
+parallel_export = true
+
+[[preset]]
+name = \"CBR 1080p\"
+rate_control = \"cbr\"
+video_bitrate = 8_000
+buffer_size = 8000
+smart_render = false
+";
+        let presets = parse_presets(toml, PresetFormat::Toml).unwrap();
+        assert_eq!(presets.len(), 2);
+        assert_eq!(presets[0].name, "Broadcast DNxHR");
+        assert_eq!(presets[0].config.video_codec, 5);
+        assert_eq!(presets[0].config.container, 2);
+        assert_eq!(presets[0].config.audio_codec, 6);
+        assert_eq!(presets[0].config.fps, 25.0);
+        assert!(presets[0].config.parallel_export);
+        assert_eq!(presets[1].config.rate_control, 3);
+        assert_eq!(presets[1].config.video_bitrate, 8000);
+        assert!(!presets[1].config.smart_render);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // 모르는 키 / 잘못된 선택값 / 이름 없음 / 문법 오류
+        assert!(parse_presets(r#"[{"name": "A", "bitrate": 1}]"#, PresetFormat::Json).is_err());
+        assert!(parse_presets(r#"[{"name": "A", "video_codec": "h263"}]"#, PresetFormat::Json).is_err());
+        assert!(parse_presets(r#"[{"width": 1280}]"#, PresetFormat::Json).is_err());
+        assert!(parse_presets(r#"[{"name": "A", "width": 12.5}]"#, PresetFormat::Json).is_err());
+        assert!(parse_presets(r#"[{"name": "A",}]"#, PresetFormat::Json).is_err());
+        assert!(parse_presets("name = \"A\"", PresetFormat::Toml).is_err());
+        assert!(parse_presets("[output]\nname = \"A\"", PresetFormat::Toml).is_err());
+
+        // 하나라도 실패하면 아무것도 추가하지 않음
+        let mut registry = PresetRegistry::new();
+        let count = registry.presets().len();
+        let json = r#"[{"name": "Good"}, {"name": "Bad", "fps": 0}]"#;
+        assert!(registry.load_str(json, PresetFormat::Json).is_err());
+        assert_eq!(registry.presets().len(), count);
+    }
+
+    #[test]
+    fn test_json_strings() {
+        let json = r#"[{"name": "한글 \"4K\" \\ 프리셋"}]"#;
+        let presets = parse_presets(json, PresetFormat::Json).unwrap();
+        assert_eq!(presets[0].name, "한글 \"4K\" \\ 프리셋");
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(PresetFormat::detect("\u{FEFF}  [ {\"name\": \"A\"} ]"), PresetFormat::Json);
+        assert_eq!(PresetFormat::detect("{\"presets\": []}"), PresetFormat::Json);
+        assert_eq!(PresetFormat::detect("[[preset]]\nname = \"A\""), PresetFormat::Toml);
+        assert_eq!(PresetFormat::detect("# 주석\n[[preset]]"), PresetFormat::Toml);
+        assert_eq!(PresetFormat::from_path(Path::new("presets.TOML")), Some(PresetFormat::Toml));
+        assert_eq!(PresetFormat::from_path(Path::new("presets.txt")), None);
+    }
+
+    #[test]
+    fn test_validate_with_encoders() {
+        let bit = |name: &str| 1u32 << ENCODER_NAMES.iter().position(|n| *n == name).unwrap();
+        let youtube = PresetRegistry::new().get("YouTube 1080p").unwrap().config_for("out.mp4");
+
+        assert!(validate_with_encoders(&youtube, bit("libx264")).is_ok());
+        // 하드웨어만 있어도 Auto면 통과
+        assert!(validate_with_encoders(&youtube, bit("h264_nvenc")).is_ok());
+        assert!(validate_with_encoders(&youtube, bit("libx265")).is_err());
+
+        // 소프트웨어 강제면 하드웨어 인코더는 무시
+        let software = ExportConfig { encoder_type: 1, ..youtube.clone() };
+        assert!(validate_with_encoders(&software, bit("h264_nvenc")).is_err());
+
+        // ProRes: FFmpeg 내장 prores 폴백이 있어 감지 결과와 무관
+        let prores = PresetRegistry::new().get("Broadcast ProRes").unwrap().config_for("out.mov");
+        assert!(validate_with_encoders(&prores, 0).is_ok());
+
+        // 설정 자체 오류가 먼저
+        let broken = ExportConfig { rate_control: 3, ..youtube };
+        assert!(validate_with_encoders(&broken, u32::MAX).is_err());
+    }
+}
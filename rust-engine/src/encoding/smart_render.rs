@@ -0,0 +1,468 @@
+// 스마트 렌더 (스트림 복사) - 편집되지 않은 단일 클립 구간은 원본 패킷을 재인코딩 없이 복사
+// 컷/트랜지션/키프레임/자막 번인 주변만 Renderer → VideoEncoder로 재인코딩
+// 지원 범위: H.264 (YUV420P, avcC 4바이트 NAL 길이) 소스 → H.264 Export (MP4/MOV/MKV)
+// 복사 구간은 GOP 단위 (닫힌 GOP 가정 — 키프레임 앞 leading 프레임은 버림)
+
+use crate::subtitle::overlay::SubtitleOverlayList;
+use crate::timeline::{BlendMode, ClipType, Timeline, VideoClip};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 이보다 짧은 복사 구간은 재인코딩 (SPS 전환 비용 대비 이득 없음)
+const MIN_COPY_MS: f64 = 1000.0;
+
+/// 스트림 복사 후보 구간 (타임라인 ms, 단일 클립만 보이는 구간)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopySpan {
+    pub file_path: PathBuf,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// start_ms 시점의 원본 파일 시간 (speed 1.0이라 이후 1:1)
+    pub source_start_ms: i64,
+}
+
+/// 복사 후보 소스 정보 (encoder::probe_copy_source 결과)
+#[derive(Debug, Clone)]
+pub struct CopySource {
+    pub is_h264: bool,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    /// YUV420P 8bit + 정사각 픽셀 (Export Renderer 출력과 같은 형태)
+    pub yuv420p_square: bool,
+    /// avcC 확장 데이터 + 4바이트 NAL 길이 (SPS/PPS를 패킷 앞에 다시 넣을 수 있음)
+    pub length_prefixed: bool,
+    /// GOP 경계 (원본 ms, 오름차순 — 키프레임 + 스트림 끝)
+    pub gop_boundaries_ms: Vec<i64>,
+    /// 첫 키프레임의 pts - dts (프레임 수, B-프레임 재정렬 지연)
+    pub reorder_delay: i64,
+}
+
+impl CopySource {
+    /// Export 설정과 그대로 이어 붙일 수 있는지 (코덱/해상도/fps 일치)
+    pub fn matches(&self, width: u32, height: u32, fps: f64) -> bool {
+        self.is_h264
+            && self.yuv420p_square
+            && self.length_prefixed
+            && self.width == width
+            && self.height == height
+            && (self.fps - fps).abs() < 0.01
+    }
+}
+
+/// Export 구간 분할 (출력 프레임 번호 기준, end 제외)
+#[derive(Debug, Clone, PartialEq)]
+pub enum SmartSegment {
+    /// Renderer → VideoEncoder 재인코딩
+    Render { start_frame: i64, end_frame: i64 },
+    /// 원본 패킷 복사 (source_start_ms 키프레임 ~ source_end_ms 키프레임 직전)
+    Copy {
+        start_frame: i64,
+        end_frame: i64,
+        file_path: PathBuf,
+        source_start_ms: i64,
+        source_end_ms: i64,
+    },
+}
+
+/// 스마트 렌더 계획 (구간 목록 + 출력 스트림 설정)
+#[derive(Debug, Clone)]
+pub struct SmartPlan {
+    pub segments: Vec<SmartSegment>,
+    /// 출력 스트림 코덱 파라미터(avcC)를 가져올 소스 (첫 복사 구간 파일)
+    pub stream_source: PathBuf,
+    /// 모든 패킷 dts를 pts보다 앞당길 프레임 수 (복사 소스 재정렬 지연 최댓값)
+    pub dts_delay: i64,
+}
+
+/// 원본 그대로 출력되는 클립인지 (트랜스폼/불투명도/블렌드/속도/키프레임 없음)
+/// 클립 이펙트는 프리뷰 Renderer 전용이라 Export 결과에 영향 없음
+fn is_untouched(clip: &VideoClip) -> bool {
+    clip.clip_type == ClipType::Video
+        && clip.sequence_fps.is_none()
+        && clip.speed == 1.0
+        && clip.keyframes.is_empty()
+        && clip.transform.is_identity()
+        && clip.blend_mode == BlendMode::Normal
+        && clip.opacity >= 1.0
+}
+
+/// 구간 목록에서 cut 구간 제거
+fn subtract(pieces: Vec<(i64, i64)>, cut: (i64, i64)) -> Vec<(i64, i64)> {
+    pieces.into_iter()
+        .flat_map(|(s, e)| [(s, e.min(cut.0)), (s.max(cut.1), e)])
+        .filter(|(s, e)| s < e)
+        .collect()
+}
+
+/// Export 구간 안에서 클립 1개만 보이는 구간 (다른 클립/트랜지션/번인 자막과 겹치지 않음)
+/// subtitles: Export에서 번인할 자막 (override 또는 Timeline 자막)
+pub fn copy_spans(
+    timeline: &Timeline,
+    range: (i64, i64),
+    subtitles: Option<&SubtitleOverlayList>,
+) -> Vec<CopySpan> {
+    let visible: Vec<&VideoClip> = timeline.video_tracks.iter()
+        .filter(|t| t.enabled)
+        .flat_map(|t| t.clips.iter())
+        .collect();
+
+    let mut spans = Vec::new();
+    for clip in visible.iter().filter(|c| is_untouched(c)) {
+        let mut pieces = vec![(clip.start_time_ms.max(range.0), clip.end_time_ms().min(range.1))];
+        // 같은 트랙의 겹침 = 트랜지션, 다른 트랙 = 합성
+        let others = visible.iter()
+            .filter(|c| c.id != clip.id)
+            .map(|c| (c.start_time_ms, c.end_time_ms()));
+        let overlays = subtitles.into_iter()
+            .flat_map(|list| list.overlays.iter())
+            .map(|o| (o.start_ms, o.end_ms));
+        for cut in others.chain(overlays) {
+            pieces = subtract(pieces, cut);
+        }
+        spans.extend(pieces.into_iter().map(|(start_ms, end_ms)| CopySpan {
+            file_path: clip.file_path.clone(),
+            start_ms,
+            end_ms,
+            source_start_ms: clip.trim_start_ms + (start_ms - clip.start_time_ms),
+        }));
+    }
+    spans.sort_by_key(|s| s.start_ms);
+    spans
+}
+
+/// 복사 후보 구간 → 프레임 단위 구간 분할
+/// - 구간 안의 첫 GOP 경계 ~ 마지막 GOP 경계만 복사, 나머지는 재인코딩
+/// - copies: (구간, 원본 GOP 경계 ms) — 시간순 정렬
+pub fn plan_segments(
+    total_frames: i64,
+    range_start_ms: i64,
+    frame_duration_ms: f64,
+    copies: &[(CopySpan, &[i64])],
+) -> Vec<SmartSegment> {
+    let to_frame = |t: i64| {
+        (((t - range_start_ms) as f64 / frame_duration_ms).round() as i64).clamp(0, total_frames)
+    };
+    let min_frames = (MIN_COPY_MS / frame_duration_ms).ceil() as i64;
+
+    let mut segments = Vec::new();
+    let mut cursor = 0;
+    for (span, boundaries) in copies {
+        // 원본 시간 → 타임라인 시간
+        let offset = span.start_ms - span.source_start_ms;
+        let mut inside = boundaries.iter()
+            .map(|b| b + offset)
+            .filter(|t| *t >= span.start_ms && *t <= span.end_ms);
+        let (Some(first), Some(last)) = (inside.next(), inside.next_back()) else {
+            continue;
+        };
+
+        let (start_frame, end_frame) = (to_frame(first), to_frame(last));
+        if start_frame < cursor || end_frame - start_frame < min_frames {
+            continue;
+        }
+        if start_frame > cursor {
+            segments.push(SmartSegment::Render { start_frame: cursor, end_frame: start_frame });
+        }
+        segments.push(SmartSegment::Copy {
+            start_frame,
+            end_frame,
+            file_path: span.file_path.clone(),
+            source_start_ms: first - offset,
+            source_end_ms: last - offset,
+        });
+        cursor = end_frame;
+    }
+    if cursor < total_frames {
+        segments.push(SmartSegment::Render { start_frame: cursor, end_frame: total_frames });
+    }
+    segments
+}
+
+/// Export 구간의 스마트 렌더 계획 (복사할 구간이 없으면 None → 전체 재인코딩)
+/// - probe: 소스 파일 정보 (열 수 없으면 None → 해당 클립은 재인코딩)
+pub fn plan_smart_render(
+    timeline: &Timeline,
+    subtitles: Option<&SubtitleOverlayList>,
+    range: (i64, i64),
+    (width, height, fps): (u32, u32, f64),
+    total_frames: i64,
+    mut probe: impl FnMut(&Path) -> Option<CopySource>,
+) -> Option<SmartPlan> {
+    let spans = copy_spans(timeline, range, subtitles);
+
+    // 소스 파일마다 1회만 조사 (같은 파일을 여러 번 자른 인터뷰 편집)
+    let mut sources: HashMap<PathBuf, Option<CopySource>> = HashMap::new();
+    for span in &spans {
+        sources.entry(span.file_path.clone())
+            .or_insert_with(|| probe(&span.file_path).filter(|s| s.matches(width, height, fps)));
+    }
+
+    let copies: Vec<(CopySpan, &[i64])> = spans.into_iter()
+        .filter_map(|span| {
+            let source = sources.get(&span.file_path)?.as_ref()?;
+            Some((span, source.gop_boundaries_ms.as_slice()))
+        })
+        .collect();
+    let segments = plan_segments(total_frames, range.0, 1000.0 / fps, &copies);
+
+    let copied: Vec<&PathBuf> = segments.iter()
+        .filter_map(|s| match s {
+            SmartSegment::Copy { file_path, .. } => Some(file_path),
+            SmartSegment::Render { .. } => None,
+        })
+        .collect();
+    let stream_source = (*copied.first()?).clone();
+    let dts_delay = copied.iter()
+        .filter_map(|p| sources.get(*p)?.as_ref())
+        .map(|s| s.reorder_delay)
+        .max()
+        .unwrap_or(0);
+
+    Some(SmartPlan { segments, stream_source, dts_delay })
+}
+
+/// 다음 Annex B start code 위치 (start code 시작, NAL 시작)
+fn find_start_code(data: &[u8], from: usize) -> Option<(usize, usize)> {
+    (from..data.len().saturating_sub(2))
+        .find(|&i| data[i] == 0 && data[i + 1] == 0 && data[i + 2] == 1)
+        .map(|i| {
+            // 4바이트 start code (00 00 00 01)
+            let start = if i > from && data[i - 1] == 0 { i - 1 } else { i };
+            (start, i + 3)
+        })
+}
+
+/// Annex B 패킷 (start code 구분) → 4바이트 길이 접두 NAL (MP4/MKV avcC 스트림 형식)
+/// start code로 시작하지 않으면 이미 길이 접두 형식으로 보고 그대로 반환
+pub fn annexb_to_length_prefixed(data: &[u8]) -> Vec<u8> {
+    let mut payload = match find_start_code(data, 0) {
+        Some((0, payload)) => payload,
+        _ => return data.to_vec(),
+    };
+
+    let mut out = Vec::with_capacity(data.len() + 16);
+    loop {
+        let next = find_start_code(data, payload);
+        let nal = &data[payload..next.map_or(data.len(), |(start, _)| start)];
+        if !nal.is_empty() {
+            out.extend_from_slice(&(nal.len() as u32).to_be_bytes());
+            out.extend_from_slice(nal);
+        }
+        match next {
+            Some((_, p)) => payload = p,
+            None => break,
+        }
+    }
+    out
+}
+
+/// avcC 확장 데이터 → SPS/PPS (4바이트 길이 접두 NAL)
+/// 재인코딩 구간 뒤 복사 구간 첫 키프레임 앞에 붙여 원본 SPS/PPS를 다시 활성화
+/// avcC가 아니거나 NAL 길이가 4바이트가 아니면 None
+pub fn avcc_parameter_sets(extradata: &[u8]) -> Option<Vec<u8>> {
+    if extradata.len() < 7 || extradata[0] != 1 || extradata[4] & 0x03 != 3 {
+        return None;
+    }
+
+    let mut out = Vec::new();
+    let mut pos = 5;
+    // SPS 개수 (하위 5비트) → PPS 개수 (1바이트)
+    for count_mask in [0x1f, 0xff] {
+        let count = *extradata.get(pos)? & count_mask;
+        pos += 1;
+        for _ in 0..count {
+            let len = u16::from_be_bytes([*extradata.get(pos)?, *extradata.get(pos + 1)?]) as usize;
+            let nal = extradata.get(pos + 2..pos + 2 + len)?;
+            out.extend_from_slice(&(len as u32).to_be_bytes());
+            out.extend_from_slice(nal);
+            pos += 2 + len;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::overlay::SubtitleOverlay;
+    use crate::timeline::TransitionType;
+
+    fn source(boundaries: &[i64]) -> CopySource {
+        CopySource {
+            is_h264: true,
+            width: 1920,
+            height: 1080,
+            fps: 25.0,
+            yuv420p_square: true,
+            length_prefixed: true,
+            gop_boundaries_ms: boundaries.to_vec(),
+            reorder_delay: 2,
+        }
+    }
+
+    #[test]
+    fn test_copy_spans_single_clip() {
+        let mut timeline = Timeline::new(1920, 1080, 25.0);
+        let track = timeline.add_video_track();
+        let id = timeline.add_video_clip(track, PathBuf::from("a.mp4"), 1000, 10000, None).unwrap();
+        timeline.video_tracks[0].get_clip_by_id_mut(id).unwrap().trim_start_ms = 500;
+
+        let spans = copy_spans(&timeline, (0, 20000), None);
+        assert_eq!(spans, vec![CopySpan {
+            file_path: PathBuf::from("a.mp4"),
+            start_ms: 1000,
+            end_ms: 11000,
+            source_start_ms: 500,
+        }]);
+
+        // Export 구간으로 자름
+        let spans = copy_spans(&timeline, (3000, 8000), None);
+        assert_eq!((spans[0].start_ms, spans[0].end_ms, spans[0].source_start_ms), (3000, 8000, 2500));
+    }
+
+    #[test]
+    fn test_copy_spans_excludes_overlaps() {
+        let mut timeline = Timeline::new(1920, 1080, 25.0);
+        let base = timeline.add_video_track();
+        let upper = timeline.add_video_track();
+        // 트랜지션 (같은 트랙 5000~6000 겹침)
+        timeline.add_video_clip(base, PathBuf::from("a.mp4"), 0, 6000, None);
+        let b = timeline.add_video_clip(base, PathBuf::from("b.mp4"), 5000, 10000, None).unwrap();
+        timeline.video_tracks[0].get_clip_by_id_mut(b).unwrap().transition_type = TransitionType::Crossfade;
+        // 상위 트랙 타이틀 (8000~9000)
+        timeline.add_video_clip(upper, PathBuf::from("title.mov"), 8000, 1000, None);
+
+        // 번인 자막 (2000~3000)
+        let mut subtitles = SubtitleOverlayList::new();
+        subtitles.overlays.push(SubtitleOverlay {
+            start_ms: 2000, end_ms: 3000, x: 0, y: 0, width: 1, height: 1, rgba_data: vec![0; 4],
+        });
+
+        let spans = copy_spans(&timeline, (0, 15000), Some(&subtitles));
+        let ranges: Vec<(i64, i64)> = spans.iter().map(|s| (s.start_ms, s.end_ms)).collect();
+        assert_eq!(ranges, vec![(0, 2000), (3000, 5000), (6000, 8000), (9000, 15000)]);
+        assert_eq!(spans[2].source_start_ms, 1000); // b.mp4: 6000 - 5000
+    }
+
+    #[test]
+    fn test_copy_spans_skips_modified_clips() {
+        let mut timeline = Timeline::new(1920, 1080, 25.0);
+        let track = timeline.add_video_track();
+        let fast = timeline.add_video_clip(track, PathBuf::from("a.mp4"), 0, 5000, None).unwrap();
+        let faded = timeline.add_video_clip(track, PathBuf::from("b.mp4"), 5000, 5000, None).unwrap();
+        let scaled = timeline.add_video_clip(track, PathBuf::from("c.mp4"), 10000, 5000, None).unwrap();
+        let clips = &mut timeline.video_tracks[0];
+        clips.get_clip_by_id_mut(fast).unwrap().speed = 2.0;
+        clips.get_clip_by_id_mut(faded).unwrap().opacity = 0.5;
+        clips.get_clip_by_id_mut(scaled).unwrap().transform.scale_x = 1.2;
+
+        assert!(copy_spans(&timeline, (0, 15000), None).is_empty());
+
+        // 비활성 트랙은 화면에 없으므로 겹침으로 보지 않음
+        let mut timeline = Timeline::new(1920, 1080, 25.0);
+        let base = timeline.add_video_track();
+        let hidden = timeline.add_video_track();
+        timeline.add_video_clip(base, PathBuf::from("a.mp4"), 0, 5000, None);
+        timeline.add_video_clip(hidden, PathBuf::from("b.mp4"), 1000, 1000, None);
+        timeline.video_tracks[1].enabled = false;
+        assert_eq!(copy_spans(&timeline, (0, 5000), None).len(), 1);
+    }
+
+    #[test]
+    fn test_plan_segments_gop_aligned() {
+        // 25fps (40ms), 타임라인 1000~11000ms = 원본 0~10000ms, 2초 GOP
+        let span = CopySpan { file_path: PathBuf::from("a.mp4"), start_ms: 1000, end_ms: 11000, source_start_ms: 0 };
+        let boundaries = [0, 2000, 4000, 6000, 8000, 10000];
+        let segments = plan_segments(300, 0, 40.0, &[(span.clone(), &boundaries[..])]);
+        assert_eq!(segments, vec![
+            SmartSegment::Render { start_frame: 0, end_frame: 25 },
+            SmartSegment::Copy {
+                start_frame: 25,
+                end_frame: 275,
+                file_path: PathBuf::from("a.mp4"),
+                source_start_ms: 0,
+                source_end_ms: 10000,
+            },
+            SmartSegment::Render { start_frame: 275, end_frame: 300 },
+        ]);
+
+        // 중간에서 시작하는 트림 → 다음 키프레임부터 복사
+        let span = CopySpan { source_start_ms: 500, ..span };
+        let segments = plan_segments(300, 0, 40.0, &[(span, &boundaries[..])]);
+        assert_eq!(segments[0], SmartSegment::Render { start_frame: 0, end_frame: 63 });
+        match &segments[1] {
+            SmartSegment::Copy { start_frame, end_frame, source_start_ms, source_end_ms, .. } => {
+                assert_eq!((*start_frame, *end_frame), (63, 263));
+                assert_eq!((*source_start_ms, *source_end_ms), (2000, 10000));
+            }
+            other => panic!("복사 구간이 아님: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_plan_segments_short_span_rendered() {
+        // GOP 경계 사이가 1초 미만이면 전부 재인코딩
+        let span = CopySpan { file_path: PathBuf::from("a.mp4"), start_ms: 0, end_ms: 1500, source_start_ms: 0 };
+        let boundaries = [0, 500, 3000];
+        let segments = plan_segments(38, 0, 40.0, &[(span, &boundaries[..])]);
+        assert_eq!(segments, vec![SmartSegment::Render { start_frame: 0, end_frame: 38 }]);
+    }
+
+    #[test]
+    fn test_plan_smart_render() {
+        let mut timeline = Timeline::new(1920, 1080, 25.0);
+        let track = timeline.add_video_track();
+        timeline.add_video_clip(track, PathBuf::from("a.mp4"), 0, 4000, None);
+        timeline.add_video_clip(track, PathBuf::from("other.mp4"), 4000, 4000, None);
+
+        let mut probed = Vec::new();
+        let plan = plan_smart_render(&timeline, None, (0, 8000), (1920, 1080, 25.0), 200, |path| {
+            probed.push(path.to_path_buf());
+            if path == Path::new("a.mp4") {
+                Some(source(&[0, 2000, 4000]))
+            } else {
+                // 해상도가 다른 소스는 재인코딩
+                Some(CopySource { width: 1280, height: 720, ..source(&[0, 4000]) })
+            }
+        }).unwrap();
+        assert_eq!(probed.len(), 2);
+        assert_eq!(plan.stream_source, PathBuf::from("a.mp4"));
+        assert_eq!(plan.dts_delay, 2);
+        assert_eq!(plan.segments.len(), 2);
+        assert_eq!(plan.segments[1], SmartSegment::Render { start_frame: 100, end_frame: 200 });
+
+        // fps가 다르면 복사 구간 없음
+        assert!(plan_smart_render(&timeline, None, (0, 8000), (1920, 1080, 30.0), 240, |_| {
+            Some(source(&[0, 2000, 4000]))
+        }).is_none());
+    }
+
+    #[test]
+    fn test_annexb_to_length_prefixed() {
+        let annexb = [0, 0, 0, 1, 0x67, 0xAA, 0, 0, 1, 0x68, 0xBB, 0, 0, 0, 1, 0x65, 1, 2, 3];
+        assert_eq!(
+            annexb_to_length_prefixed(&annexb),
+            vec![0, 0, 0, 2, 0x67, 0xAA, 0, 0, 0, 2, 0x68, 0xBB, 0, 0, 0, 4, 0x65, 1, 2, 3]
+        );
+        // 이미 길이 접두 형식이면 그대로
+        let prefixed = [0, 0, 0, 2, 0x65, 0x88];
+        assert_eq!(annexb_to_length_prefixed(&prefixed), prefixed.to_vec());
+    }
+
+    #[test]
+    fn test_avcc_parameter_sets() {
+        // version 1, High@4.0, lengthSizeMinusOne=3, SPS 1개, PPS 1개
+        let avcc = [1, 0x64, 0, 0x28, 0xFF, 0xE1, 0, 3, 0x67, 1, 2, 1, 0, 2, 0x68, 9];
+        assert_eq!(
+            avcc_parameter_sets(&avcc).unwrap(),
+            vec![0, 0, 0, 3, 0x67, 1, 2, 0, 0, 0, 2, 0x68, 9]
+        );
+        // NAL 길이 2바이트 / Annex B 확장 데이터 / 잘린 데이터는 지원 안 함
+        let mut short_len = avcc;
+        short_len[4] = 0xFD;
+        assert!(avcc_parameter_sets(&short_len).is_none());
+        assert!(avcc_parameter_sets(&[0, 0, 0, 1, 0x67, 1, 2]).is_none());
+        assert!(avcc_parameter_sets(&avcc[..10]).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+// 스틸 프레임 Export - 지정 시간의 프레임 1장 → PNG / JPEG / TIFF 파일
+// 프리뷰 Renderer(renderer_render_frame, 프리뷰 해상도)와 달리 Export용 Renderer로 프로젝트 해상도 합성
+
+use crate::encoding::container::image_codec_from_path;
+use crate::encoding::encoder::{encode_still_image, OutputPixelFormat, VideoCodec};
+use crate::rendering::Renderer;
+use crate::timeline::Timeline;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// 스틸 프레임 Export (동기, 호출 스레드에서 렌더링 + 인코딩)
+/// - image_codec: PNG / JPEG / TIFF (None이면 출력 파일 확장자 기준)
+/// - crf: JPEG 품질 (H.264 CRF 스케일), PNG/TIFF는 무손실이라 무시
+/// - alpha: 빈 영역 투명 (PNG / TIFF만)
+pub fn export_still(
+    timeline: Arc<Mutex<Timeline>>,
+    timestamp_ms: i64,
+    output_path: &str,
+    image_codec: Option<VideoCodec>,
+    crf: u32,
+    alpha: bool,
+) -> Result<(), String> {
+    let image_codec = image_codec
+        .or_else(|| image_codec_from_path(output_path))
+        .ok_or_else(|| format!("이미지 형식을 알 수 없습니다 (.png/.jpg/.tif): {}", output_path))?;
+    if !image_codec.is_image() {
+        return Err(format!("{:?}는 이미지 코덱이 아닙니다", image_codec));
+    }
+    let pixel_format = image_codec.resolve_pixel_format(0, OutputPixelFormat::Auto, alpha)?;
+
+    // 프로젝트 해상도 (Timeline 설정값)
+    let (width, height, duration_ms) = {
+        let tl = timeline.lock().map_err(|e| format!("Timeline lock failed: {}", e))?;
+        (tl.width, tl.height, tl.duration_ms())
+    };
+    if timestamp_ms < 0 || timestamp_ms >= duration_ms {
+        return Err(format!("타임라인 범위 밖의 시간입니다: {}ms (길이 {}ms)", timestamp_ms, duration_ms));
+    }
+
+    let mut renderer = Renderer::new_for_export(timeline, width, height);
+    // 단일 프레임: forward decode 대신 즉시 seek
+    renderer.set_playback_mode(false);
+    if pixel_format.has_alpha() {
+        renderer.set_export_alpha(true);
+    } else {
+        renderer.set_export_rgba(true);
+    }
+
+    let frame = renderer.render_frame(timestamp_ms)
+        .map_err(|e| format!("렌더링 실패 ({}ms): {}", timestamp_ms, e))?;
+    if frame.is_yuv {
+        return Err("스틸 프레임은 RGBA 렌더링 결과가 필요합니다".to_string());
+    }
+
+    let data = encode_still_image(&frame.data, frame.width, frame.height, image_codec, pixel_format, crf)?;
+
+    if let Some(parent) = Path::new(output_path).parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("출력 디렉토리 생성 실패: {}", e))?;
+    }
+    std::fs::write(output_path, data)
+        .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+
+    eprintln!(
+        "[EXPORT] 스틸 프레임: {}ms → {} ({}x{}, {:?})",
+        timestamp_ms, output_path, frame.width, frame.height, image_codec
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_still_from_single_video_clip() {
+        let dir = std::env::temp_dir().join(format!("vortex_still_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.mkv");
+        crate::encoding::encoder::tests::write_test_video(&source, (64, 36), 25.0, 25, [200, 40, 40, 255]).unwrap();
+
+        // 이펙트/트랜스폼 없는 단일 비디오 클립 (Renderer 단일 클립 직접 경로)
+        let timeline = Arc::new(Mutex::new(Timeline::new(64, 36, 25.0)));
+        {
+            let mut tl = timeline.lock().unwrap();
+            let track_id = tl.add_video_track();
+            tl.add_video_clip(track_id, source, 0, 1000, None).unwrap();
+        }
+
+        let png = dir.join("still.png");
+        let jpeg = dir.join("still.jpg");
+        let png_result = export_still(timeline.clone(), 500, png.to_str().unwrap(), None, 23, false);
+        let jpeg_result = export_still(timeline.clone(), 500, jpeg.to_str().unwrap(), None, 23, false);
+        let out_of_range = export_still(timeline, 1000, png.to_str().unwrap(), None, 23, false);
+        let png_data = std::fs::read(&png);
+        let jpeg_data = std::fs::read(&jpeg);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        png_result.unwrap();
+        jpeg_result.unwrap();
+        assert!(png_data.unwrap().starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(jpeg_data.unwrap().starts_with(&[0xFF, 0xD8]));
+        assert!(out_of_range.is_err());
+    }
+}
@@ -1,9 +1,17 @@
 // Exporter FFI - C# P/Invoke 연동
 // Export 작업 생성/진행률/취소/파괴
 
-use crate::encoding::exporter::{ExportConfig, ExportJob};
+use crate::encoding::container::Container;
+use crate::encoding::encoder::VideoCodec;
+use crate::encoding::exporter::{ExportConfig, ExportJob, ExportRegion};
+use crate::encoding::preset::{validate_with_encoders, PresetRegistry};
+use crate::encoding::still::export_still;
 use crate::ffi::types::ErrorCode;
+use crate::subtitle::caption::{write_caption_file, CaptionFormat, CaptionOptions};
+use crate::subtitle::cue::{SubtitleCue, SubtitleTrack};
 use crate::subtitle::overlay::{SubtitleOverlay, SubtitleOverlayList};
+use crate::subtitle::parser::parse_caption_file;
+use crate::subtitle::text::TextRenderer;
 use crate::timeline::Timeline;
 use std::ffi::{c_void, c_char, CStr, CString};
 use std::sync::{Arc, Mutex};
@@ -39,14 +47,7 @@ pub extern "C" fn exporter_start(
         let timeline_clone = Arc::clone(&timeline_arc);
         let _ = Arc::into_raw(timeline_arc); // 원본 유지
 
-        let config = ExportConfig {
-            output_path: output_path_str,
-            width,
-            height,
-            fps,
-            crf,
-            encoder_type: 0, // Auto
-        };
+        let config = ExportConfig::new(output_path_str, width, height, fps, crf);
 
         // ExportJob 시작 (백그라운드 스레드)
         let job = ExportJob::start(timeline_clone, config);
@@ -201,6 +202,29 @@ pub extern "C" fn exporter_subtitle_list_add(
     ErrorCode::Success as i32
 }
 
+/// 자막 비트맵 좌표 기준 해상도 지정
+/// 프리뷰(960x540)처럼 출력 해상도가 다르면 렌더러가 위치/크기를 비례 리샘플링
+#[no_mangle]
+pub extern "C" fn exporter_subtitle_list_set_frame_size(
+    list: *mut c_void,
+    width: u32,
+    height: u32,
+) -> i32 {
+    if list.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if width == 0 || height == 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let list_ref = &mut *(list as *mut SubtitleOverlayList);
+        list_ref.frame_size = Some((width, height));
+    }
+
+    ErrorCode::Success as i32
+}
+
 /// 자막 포함 Export 시작 (v2)
 /// subtitle_list: exporter_create_subtitle_list()로 생성한 핸들 (null이면 자막 없음)
 /// 자막 목록의 소유권이 Rust로 이전됨 — 별도로 free할 필요 없음
@@ -230,14 +254,7 @@ pub extern "C" fn exporter_start_v2(
         let timeline_clone = Arc::clone(&timeline_arc);
         let _ = Arc::into_raw(timeline_arc);
 
-        let config = ExportConfig {
-            output_path: output_path_str,
-            width,
-            height,
-            fps,
-            crf,
-            encoder_type: 0, // Auto
-        };
+        let config = ExportConfig::new(output_path_str, width, height, fps, crf);
 
         // 자막 목록 소유권 이전 (null이면 None)
         let subtitles = if subtitle_list.is_null() {
@@ -285,12 +302,68 @@ pub extern "C" fn exporter_start_v3(
         let _ = Arc::into_raw(timeline_arc);
 
         let config = ExportConfig {
-            output_path: output_path_str,
-            width,
-            height,
-            fps,
-            crf,
             encoder_type,
+            ..ExportConfig::new(output_path_str, width, height, fps, crf)
+        };
+
+        let subtitles = if subtitle_list.is_null() {
+            None
+        } else {
+            Some(*Box::from_raw(subtitle_list as *mut SubtitleOverlayList))
+        };
+
+        let job = ExportJob::start_with_subtitles(timeline_clone, config, subtitles);
+        let job_box = Box::new(job);
+        *out_job = Box::into_raw(job_box) as *mut c_void;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 소프트 자막 + 번인 자막 포함 Export 시작 (v4)
+/// subtitle_list: 번인 오버레이 (null이면 Timeline 자막), 소유권 Rust로 이전
+/// soft_subtitles: exporter_create_soft_subtitles()로 만든 텍스트 자막 트랙 (null이면 없음), 소유권 Rust로 이전
+/// subtitle_codec: 0=Auto(확장자 기준), 1=mov_text, 2=SRT, 3=ASS, 4=WebVTT
+#[no_mangle]
+pub extern "C" fn exporter_start_v4(
+    timeline: *mut c_void,
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+    encoder_type: u32,
+    subtitle_list: *mut c_void,
+    soft_subtitles: *mut c_void,
+    subtitle_codec: u32,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    if timeline.is_null() || output_path.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let c_str = CStr::from_ptr(output_path);
+        let output_path_str = match c_str.to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
+        let timeline_clone = Arc::clone(&timeline_arc);
+        let _ = Arc::into_raw(timeline_arc);
+
+        let subtitle_tracks = if soft_subtitles.is_null() {
+            Vec::new()
+        } else {
+            *Box::from_raw(soft_subtitles as *mut Vec<SubtitleTrack>)
+        };
+
+        let config = ExportConfig {
+            encoder_type,
+            subtitle_tracks,
+            subtitle_codec,
+            ..ExportConfig::new(output_path_str, width, height, fps, crf)
         };
 
         let subtitles = if subtitle_list.is_null() {
@@ -307,14 +380,848 @@ pub extern "C" fn exporter_start_v3(
     ErrorCode::Success as i32
 }
 
+// ==================== Export 설정 핸들 FFI ====================
+// 설정 항목이 늘어날 때마다 exporter_start_vN을 추가하지 않도록
+// 설정 핸들을 만들어 setter로 채운 뒤 exporter_start_with_config로 시작
+
+/// Export 설정 생성 (H.264 자동 인코더, Balanced 프리셋 기본값)
+/// 반환: 설정 핸들 (exporter_start_with_config에 전달하거나 exporter_config_free로 해제), 실패 시 null
+#[no_mangle]
+pub extern "C" fn exporter_config_create(
+    output_path: *const c_char,
+    width: u32,
+    height: u32,
+    fps: f64,
+    crf: u32,
+) -> *mut c_void {
+    if output_path.is_null() || width == 0 || height == 0 || fps <= 0.0 {
+        return std::ptr::null_mut();
+    }
+
+    unsafe {
+        let output_path_str = match CStr::from_ptr(output_path).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return std::ptr::null_mut(),
+        };
+        let config = ExportConfig::new(output_path_str, width, height, fps, crf);
+        Box::into_raw(Box::new(config)) as *mut c_void
+    }
+}
+
+/// 오디오 전용 Export 설정 생성 (.wav / .flac / .mp3 / .m4a, 비디오 렌더링 없음)
+/// 코덱은 확장자 기준 (WAV=PCM 16bit, FLAC, MP3, M4A=AAC), exporter_config_set_container / set_audio로 변경
+/// 반환: 설정 핸들, 오디오 전용 확장자가 아니면 null
+#[no_mangle]
+pub extern "C" fn exporter_config_create_audio_only(output_path: *const c_char) -> *mut c_void {
+    if output_path.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    unsafe {
+        let output_path_str = match CStr::from_ptr(output_path).to_str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return std::ptr::null_mut(),
+        };
+        if !Container::from_path(&output_path_str).is_audio_only() {
+            return std::ptr::null_mut();
+        }
+        // 비디오 크기/fps는 사용하지 않음
+        let config = ExportConfig::new(output_path_str, 0, 0, 0.0, 0);
+        Box::into_raw(Box::new(config)) as *mut c_void
+    }
+}
+
+/// 비디오 코덱 설정
+/// video_codec: 0=H.264, 1=HEVC, 2=AV1, 3=VP9, 4=ProRes, 5=DNxHR, 6=FFV1
+///              7=PNG, 8=JPEG, 9=TIFF (이미지 시퀀스)
+/// encoder_type: 0=Auto, 1=Software, 2=NVENC, 3=QSV, 4=AMF
+/// preset: 0=Balanced, 1=Fast, 2=Quality
+#[no_mangle]
+pub extern "C" fn exporter_config_set_video_codec(
+    config: *mut c_void,
+    video_codec: u32,
+    encoder_type: u32,
+    preset: u32,
+) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if video_codec > 9 || encoder_type > 4 || preset > 2 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.video_codec = video_codec;
+        config_ref.encoder_type = encoder_type;
+        config_ref.preset = preset;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 코덱 프로필 / 픽셀 포맷 설정 (중간 코덱, 4:2:2·4:4:4·10bit 출력)
+/// codec_profile: ProRes 0=Auto(422 HQ), 1=Proxy, 2=LT, 3=422, 4=422 HQ, 5=4444, 6=4444 XQ
+///                DNxHR 0=Auto(HQ), 1=LB, 2=SQ, 3=HQ, 4=HQX, 5=444
+/// pixel_format: 0=Auto, 1=YUV420P, 2=YUV422P, 3=YUV444P, 4=YUV420P10, 5=YUV422P10, 6=YUV444P10,
+///               7=YUVA420P, 8=YUVA444P10, 9=RGB24, 10=RGBA
+#[no_mangle]
+pub extern "C" fn exporter_config_set_video_format(
+    config: *mut c_void,
+    codec_profile: u32,
+    pixel_format: u32,
+) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if codec_profile > 6 || pixel_format > 10 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.codec_profile = codec_profile;
+        config_ref.pixel_format = pixel_format;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 알파 채널 Export (ProRes 4444 / VP9 / FFV1 / PNG 시퀀스, 빈 영역은 투명)
+/// enabled: 0=끔, 1=켬 (pixel_format이 Auto면 알파 포함 포맷 자동 선택)
+#[no_mangle]
+pub extern "C" fn exporter_config_set_alpha(config: *mut c_void, enabled: i32) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.alpha = enabled != 0;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 스마트 렌더 (편집되지 않은 단일 클립 구간은 원본 패킷 복사, 나머지만 재인코딩)
+/// enabled: 0=끔 (기본, 전체 재인코딩), 1=켬
+/// H.264 YUV420P → MP4/MOV/MKV에서만 적용, 코덱/해상도/fps가 다른 소스는 재인코딩
+#[no_mangle]
+pub extern "C" fn exporter_config_set_smart_render(config: *mut c_void, enabled: i32) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.smart_render = enabled != 0;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 병렬 구간 Export (타임라인을 GOP 단위 구간으로 나눠 워커 스레드마다 렌더링/인코딩 후 무손실 결합)
+/// enabled: 0=끔 (기본, 단일 스레드), 1=켬 / workers: 0=자동 (코어 4개당 1개)
+/// 소프트웨어 인코더 + CRF/CRF 상한에서만 적용, 스마트 렌더를 켜면 스마트 렌더 우선
+#[no_mangle]
+pub extern "C" fn exporter_config_set_parallel(config: *mut c_void, enabled: i32, workers: u32) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.parallel_export = enabled != 0;
+        config_ref.parallel_workers = workers;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 비트레이트 제어 설정 (플랫폼 파일 크기 제한 / 방송 비트레이트 규격)
+/// mode: 0=CRF (기본, crf만 사용), 1=CRF 상한 (max_bitrate 필수), 2=2-pass 목표 비트레이트, 3=CBR
+/// bitrate_kbps: 2-pass/CBR 목표 (CRF 상한은 선택, max 이하), max_bitrate_kbps: 2-pass는 0이면 제한 없음
+/// buffer_kbits: VBV 버퍼 (0=CBR은 1초분, 그 외 max_bitrate 2초분)
+#[no_mangle]
+pub extern "C" fn exporter_config_set_rate_control(
+    config: *mut c_void,
+    mode: u32,
+    bitrate_kbps: u32,
+    max_bitrate_kbps: u32,
+    buffer_kbits: u32,
+) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if mode > 3 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.rate_control = mode;
+        config_ref.video_bitrate = bitrate_kbps;
+        config_ref.max_bitrate = max_bitrate_kbps;
+        config_ref.buffer_size = buffer_kbits;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 컨테이너 / 오디오 코덱 설정
+/// container: 0=Auto(확장자 기준), 1=MP4, 2=MOV, 3=MKV, 4=WebM, 5=이미지 시퀀스(PNG/JPEG/TIFF)
+///            6=WAV, 7=FLAC, 8=MP3, 9=M4A (오디오 전용, 비디오 렌더링 없음)
+/// audio_codec: 0=Auto(컨테이너 기준), 1=AAC, 2=Opus, 3=MP3, 4=FLAC, 5=PCM 16bit, 6=PCM 24bit
+#[no_mangle]
+pub extern "C" fn exporter_config_set_container(
+    config: *mut c_void,
+    container: u32,
+    audio_codec: u32,
+) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if container > 9 || audio_codec > 6 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.container = container;
+        config_ref.audio_codec = audio_codec;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 오디오 사양 설정 (코덱은 exporter_config_set_container에서 지정)
+/// sample_rate: Hz (0=48000), channels: 1/2/6 (0=스테레오)
+/// bitrate_kbps: 0=채널당 96kbps (FLAC/PCM은 무시)
+/// include_audio: 0이면 오디오 트랙 없이 Export
+#[no_mangle]
+pub extern "C" fn exporter_config_set_audio(
+    config: *mut c_void,
+    sample_rate: u32,
+    channels: u32,
+    bitrate_kbps: u32,
+    include_audio: i32,
+) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if !matches!(channels, 0 | 1 | 2 | 6) || (sample_rate != 0 && !(8000..=192000).contains(&sample_rate)) {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.audio_sample_rate = sample_rate;
+        config_ref.audio_channels = channels;
+        config_ref.audio_bitrate = bitrate_kbps;
+        config_ref.include_audio = include_audio != 0;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// Export 구간 설정 (타임라인 ms)
+/// end_ms: 0이면 타임라인 끝까지, start_ms=0 & end_ms=0이면 전체
+#[no_mangle]
+pub extern "C" fn exporter_config_set_range(config: *mut c_void, start_ms: i64, end_ms: i64) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if start_ms < 0 || (end_ms != 0 && end_ms <= start_ms) {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.range_start_ms = start_ms;
+        config_ref.range_end_ms = end_ms;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 배치 Export 구간 추가 (구간이 하나라도 있으면 구간마다 파일 1개, set_range 무시)
+/// name: 파일명 접미사 (UTF-8, null이면 구간 번호)
+#[no_mangle]
+pub extern "C" fn exporter_config_add_region(
+    config: *mut c_void,
+    start_ms: i64,
+    end_ms: i64,
+    name: *const c_char,
+) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if start_ms < 0 || end_ms <= start_ms {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let name = if name.is_null() {
+            None
+        } else {
+            match CStr::from_ptr(name).to_str() {
+                Ok(s) => Some(s.to_string()),
+                Err(_) => return ErrorCode::InvalidParam as i32,
+            }
+        };
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.regions.push(ExportRegion { start_ms, end_ms, name });
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 배치 Export 구간 모두 제거
+#[no_mangle]
+pub extern "C" fn exporter_config_clear_regions(config: *mut c_void) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.regions.clear();
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 컨테이너/코덱 조합 + 설치된 인코더 검증 (Export 시작 전 UI에서 확인용)
+/// out_error: 호환되지 않으면 에러 문자열 (string_free()로 해제), 문제 없으면 null
+/// 반환: Success 또는 InvalidParam
+#[no_mangle]
+pub extern "C" fn exporter_config_validate(
+    config: *mut c_void,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    if config.is_null() || out_error.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let config_ref = &*(config as *const ExportConfig);
+        *out_error = std::ptr::null_mut();
+        let available = crate::encoding::encoder::detect_available_encoders();
+        match validate_with_encoders(config_ref, available) {
+            Ok(()) => ErrorCode::Success as i32,
+            Err(msg) => {
+                if let Ok(c_str) = CString::new(msg) {
+                    *out_error = c_str.into_raw();
+                }
+                ErrorCode::InvalidParam as i32
+            }
+        }
+    }
+}
+
+/// 소프트 자막 트랙 설정 (soft_subtitles 소유권 Rust로 이전, null이면 자막 스트림 제거)
+/// subtitle_codec: 0=Auto(확장자 기준), 1=mov_text, 2=SRT, 3=ASS, 4=WebVTT
+#[no_mangle]
+pub extern "C" fn exporter_config_set_soft_subtitles(
+    config: *mut c_void,
+    soft_subtitles: *mut c_void,
+    subtitle_codec: u32,
+) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let config_ref = &mut *(config as *mut ExportConfig);
+        config_ref.subtitle_tracks = if soft_subtitles.is_null() {
+            Vec::new()
+        } else {
+            *Box::from_raw(soft_subtitles as *mut Vec<SubtitleTrack>)
+        };
+        config_ref.subtitle_codec = subtitle_codec;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// Export 설정 해제 (exporter_start_with_config에 전달하지 않고 취소할 때만 사용)
+#[no_mangle]
+pub extern "C" fn exporter_config_free(config: *mut c_void) -> i32 {
+    if config.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(config as *mut ExportConfig);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 설정 핸들로 Export 시작
+/// config: 소유권 Rust로 이전 (성공/실패 무관, 이후 사용 금지)
+/// subtitle_list: 번인 오버레이 (null이면 Timeline 자막), 소유권 Rust로 이전
+/// 설정/인코더 검증 실패: InvalidParam + out_job은 에러로 끝난 작업 (exporter_get_error로 메시지, 해제 필요)
+#[no_mangle]
+pub extern "C" fn exporter_start_with_config(
+    timeline: *mut c_void,
+    config: *mut c_void,
+    subtitle_list: *mut c_void,
+    out_job: *mut *mut c_void,
+) -> i32 {
+    if timeline.is_null() || config.is_null() || out_job.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let config = *Box::from_raw(config as *mut ExportConfig);
+
+        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
+        let timeline_clone = Arc::clone(&timeline_arc);
+        let _ = Arc::into_raw(timeline_arc);
+
+        let subtitles = if subtitle_list.is_null() {
+            None
+        } else {
+            Some(*Box::from_raw(subtitle_list as *mut SubtitleOverlayList))
+        };
+
+        let (job, code) = match ExportJob::try_start(timeline_clone, config, subtitles) {
+            Ok(job) => (job, ErrorCode::Success),
+            Err(msg) => (ExportJob::failed(msg), ErrorCode::InvalidParam),
+        };
+        *out_job = Box::into_raw(Box::new(job)) as *mut c_void;
+        code as i32
+    }
+}
+
+// ==================== Export 프리셋 FFI ====================
+
+/// 프리셋 목록 생성 (기본 제공: YouTube 1080p / Instagram Reel 1080x1920 / Broadcast ProRes / Web Proxy)
+/// 반환: 프리셋 목록 핸들 (exporter_presets_free로 해제)
+#[no_mangle]
+pub extern "C" fn exporter_presets_create() -> *mut c_void {
+    Box::into_raw(Box::new(PresetRegistry::new())) as *mut c_void
+}
+
+/// 프리셋 파일 읽기 (.json / .toml, 같은 이름은 덮어씀 — 하나라도 잘못되면 아무것도 추가하지 않음)
+/// out_error: 실패 시 에러 문자열 (string_free()로 해제), 성공 시 null
+#[no_mangle]
+pub extern "C" fn exporter_presets_load_file(
+    presets: *mut c_void,
+    path: *const c_char,
+    out_error: *mut *mut c_char,
+) -> i32 {
+    if presets.is_null() || path.is_null() || out_error.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        *out_error = std::ptr::null_mut();
+        let path_str = match CStr::from_ptr(path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let registry = &mut *(presets as *mut PresetRegistry);
+        match registry.load_file(std::path::Path::new(path_str)) {
+            Ok(_) => ErrorCode::Success as i32,
+            Err(msg) => {
+                if let Ok(c_str) = CString::new(msg) {
+                    *out_error = c_str.into_raw();
+                }
+                ErrorCode::InvalidParam as i32
+            }
+        }
+    }
+}
+
+/// 프리셋 개수
+#[no_mangle]
+pub extern "C" fn exporter_presets_count(presets: *mut c_void) -> u32 {
+    if presets.is_null() {
+        return 0;
+    }
+
+    unsafe {
+        let registry = &*(presets as *const PresetRegistry);
+        registry.presets().len() as u32
+    }
+}
+
+/// index번째 프리셋 이름 (string_free()로 해제), 범위 밖이면 null
+#[no_mangle]
+pub extern "C" fn exporter_presets_get_name(presets: *mut c_void, index: u32) -> *mut c_char {
+    if presets.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    unsafe {
+        let registry = &*(presets as *const PresetRegistry);
+        registry.presets()
+            .get(index as usize)
+            .and_then(|p| CString::new(p.name.clone()).ok())
+            .map_or(std::ptr::null_mut(), CString::into_raw)
+    }
+}
+
+/// 프리셋으로 Export 설정 생성 + 설치된 인코더로 검증 (exporter_start_with_config에 전달)
+/// out_error: 실패 시 에러 문자열 (string_free()로 해제), 성공 시 null
+/// 반환: 설정 핸들, 프리셋이 없거나 검증 실패면 null
+#[no_mangle]
+pub extern "C" fn exporter_config_create_from_preset(
+    presets: *mut c_void,
+    name: *const c_char,
+    output_path: *const c_char,
+    out_error: *mut *mut c_char,
+) -> *mut c_void {
+    if presets.is_null() || name.is_null() || output_path.is_null() || out_error.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    unsafe {
+        *out_error = std::ptr::null_mut();
+        let (name_str, output_path_str) = match (CStr::from_ptr(name).to_str(), CStr::from_ptr(output_path).to_str()) {
+            (Ok(n), Ok(p)) => (n, p),
+            _ => return std::ptr::null_mut(),
+        };
+        let registry = &*(presets as *const PresetRegistry);
+        let result = registry.get(name_str)
+            .ok_or_else(|| format!("프리셋을 찾을 수 없습니다: {}", name_str))
+            .map(|preset| preset.config_for(output_path_str))
+            .and_then(|config| {
+                let available = crate::encoding::encoder::detect_available_encoders();
+                validate_with_encoders(&config, available).map(|_| config)
+            });
+        match result {
+            Ok(config) => Box::into_raw(Box::new(config)) as *mut c_void,
+            Err(msg) => {
+                if let Ok(c_str) = CString::new(msg) {
+                    *out_error = c_str.into_raw();
+                }
+                std::ptr::null_mut()
+            }
+        }
+    }
+}
+
+/// 프리셋 목록 해제
+#[no_mangle]
+pub extern "C" fn exporter_presets_free(presets: *mut c_void) -> i32 {
+    if presets.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(presets as *mut PresetRegistry);
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 스틸 프레임 Export (동기 호출, 프로젝트 해상도로 렌더링 → 이미지 파일)
+/// image_format: 0=Auto(확장자 기준), 1=PNG, 2=JPEG, 3=TIFF
+/// quality_crf: JPEG 품질 (H.264 CRF 스케일, 낮을수록 고화질), PNG/TIFF는 무시
+/// alpha: 1이면 빈 영역 투명 (PNG/TIFF만)
+#[no_mangle]
+pub extern "C" fn exporter_export_still(
+    timeline: *mut c_void,
+    timestamp_ms: i64,
+    output_path: *const c_char,
+    image_format: u32,
+    quality_crf: u32,
+    alpha: i32,
+) -> i32 {
+    if timeline.is_null() || output_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    let image_codec = match image_format {
+        0 => None,
+        1 => Some(VideoCodec::Png),
+        2 => Some(VideoCodec::Jpeg),
+        3 => Some(VideoCodec::Tiff),
+        _ => return ErrorCode::InvalidParam as i32,
+    };
+
+    unsafe {
+        let path = match CStr::from_ptr(output_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let timeline_arc = Arc::from_raw(timeline as *const Mutex<Timeline>);
+        let timeline_clone = Arc::clone(&timeline_arc);
+        let _ = Arc::into_raw(timeline_arc);
+
+        match export_still(timeline_clone, timestamp_ms, path, image_codec, quality_crf, alpha != 0) {
+            Ok(()) => ErrorCode::Success as i32,
+            Err(e) => {
+                eprintln!("[EXPORT] 스틸 프레임 실패: {}", e);
+                ErrorCode::RenderFailed as i32
+            }
+        }
+    }
+}
+
+// ==================== 소프트 자막 (텍스트 자막 스트림) FFI ====================
+
+/// 소프트 자막 트랙 목록 생성
+/// 반환: 핸들 (exporter_start_v4에 전달하거나 exporter_free_soft_subtitles로 해제)
+#[no_mangle]
+pub extern "C" fn exporter_create_soft_subtitles() -> *mut c_void {
+    let tracks: Box<Vec<SubtitleTrack>> = Box::default();
+    Box::into_raw(tracks) as *mut c_void
+}
+
+/// 자막 트랙 추가 (언어별 1개)
+/// language: "ko"/"kor"/"en" 등, title: 트랙 이름 (null 가능)
+/// out_track_index: exporter_soft_subtitles_add_cue에 사용할 트랙 인덱스
+#[no_mangle]
+pub extern "C" fn exporter_soft_subtitles_add_track(
+    soft_subtitles: *mut c_void,
+    language: *const c_char,
+    title: *const c_char,
+    out_track_index: *mut u32,
+) -> i32 {
+    if soft_subtitles.is_null() || language.is_null() || out_track_index.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let language = match CStr::from_ptr(language).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+        let title = if title.is_null() {
+            None
+        } else {
+            match CStr::from_ptr(title).to_str() {
+                Ok(s) => Some(s),
+                Err(_) => return ErrorCode::InvalidParam as i32,
+            }
+        };
+
+        let tracks = &mut *(soft_subtitles as *mut Vec<SubtitleTrack>);
+        tracks.push(SubtitleTrack::new(language, title));
+        *out_track_index = (tracks.len() - 1) as u32;
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 자막 cue 추가 (text: UTF-8, 여러 줄은 '\n')
+#[no_mangle]
+pub extern "C" fn exporter_soft_subtitles_add_cue(
+    soft_subtitles: *mut c_void,
+    track_index: u32,
+    start_ms: i64,
+    end_ms: i64,
+    text: *const c_char,
+) -> i32 {
+    if soft_subtitles.is_null() || text.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if end_ms <= start_ms {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let text = match CStr::from_ptr(text).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let tracks = &mut *(soft_subtitles as *mut Vec<SubtitleTrack>);
+        match tracks.get_mut(track_index as usize) {
+            Some(track) => track.cues.push(SubtitleCue::new(start_ms, end_ms, text)),
+            None => return ErrorCode::InvalidParam as i32,
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 자막 파일(SRT/VTT/ASS)을 읽어 자막 트랙으로 추가 (UI 없이 Export에 먹싱)
+/// language: "ko"/"kor"/"en" 등, title: 트랙 이름 (null 가능)
+#[no_mangle]
+pub extern "C" fn exporter_soft_subtitles_add_file(
+    soft_subtitles: *mut c_void,
+    caption_path: *const c_char,
+    language: *const c_char,
+    title: *const c_char,
+    out_track_index: *mut u32,
+) -> i32 {
+    if caption_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    // 경로 검증 후 트랙 추가 (실패 시 빈 트랙을 남기지 않음)
+    let caption_path = match unsafe { CStr::from_ptr(caption_path) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return ErrorCode::InvalidParam as i32,
+    };
+
+    let result = exporter_soft_subtitles_add_track(soft_subtitles, language, title, out_track_index);
+    if result != ErrorCode::Success as i32 {
+        return result;
+    }
+
+    unsafe {
+        let tracks = &mut *(soft_subtitles as *mut Vec<SubtitleTrack>);
+        match parse_caption_file(std::path::Path::new(caption_path)) {
+            Ok(parsed) => {
+                if let Some(track) = tracks.last_mut() {
+                    track.cues = parsed.cues;
+                }
+            }
+            Err(e) => {
+                eprintln!("[EXPORT] {}", e);
+                tracks.pop(); // 빈 트랙 남기지 않음
+                return ErrorCode::InvalidParam as i32;
+            }
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 자막 트랙을 사이드카 자막 파일로 저장 (SRT / WebVTT / ASS)
+/// format: 0=SRT, 1=WebVTT, 2=ASS
+/// max_line_chars: 한 줄 최대 글자 수 (0=줄바꿈 안 함), max_lines: cue당 최대 줄 수 (0이면 2)
+/// max_duration_ms: cue당 최대 표시 시간 (0=분할 안 함)
+#[no_mangle]
+pub extern "C" fn exporter_soft_subtitles_write_file(
+    soft_subtitles: *mut c_void,
+    track_index: u32,
+    output_path: *const c_char,
+    format: u32,
+    max_line_chars: u32,
+    max_lines: u32,
+    max_duration_ms: i64,
+) -> i32 {
+    if soft_subtitles.is_null() || output_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let format = match CaptionFormat::from_u32(format) {
+        Some(f) => f,
+        None => return ErrorCode::InvalidParam as i32,
+    };
+
+    unsafe {
+        let path = match CStr::from_ptr(output_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let tracks = &*(soft_subtitles as *const Vec<SubtitleTrack>);
+        let track = match tracks.get(track_index as usize) {
+            Some(t) => t,
+            None => return ErrorCode::InvalidParam as i32,
+        };
+
+        let opts = CaptionOptions {
+            max_line_chars: max_line_chars as usize,
+            max_lines: if max_lines == 0 { 2 } else { max_lines as usize },
+            max_duration_ms: max_duration_ms.max(0),
+            ..Default::default()
+        };
+
+        match write_caption_file(std::path::Path::new(path), &track.cues, format, &opts) {
+            Ok(()) => ErrorCode::Success as i32,
+            Err(e) => {
+                eprintln!("[EXPORT] {}", e);
+                ErrorCode::Io as i32
+            }
+        }
+    }
+}
+
+/// 소프트 자막 트랙 목록 해제 (Export에 전달하지 않고 취소할 때만 사용)
+#[no_mangle]
+pub extern "C" fn exporter_free_soft_subtitles(soft_subtitles: *mut c_void) -> i32 {
+    if soft_subtitles.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    unsafe {
+        let _ = Box::from_raw(soft_subtitles as *mut Vec<SubtitleTrack>);
+    }
+
+    ErrorCode::Success as i32
+}
+
 /// 사용 가능한 인코더 탐지 (비트마스크 반환)
 /// bit 0 = libx264 (1), bit 1 = NVENC (2), bit 2 = QSV (4), bit 3 = AMF (8)
+/// bit 4~7 = libx265 / hevc_nvenc / hevc_qsv / hevc_amf
+/// bit 8~12 = libsvtav1 / libaom-av1 / av1_nvenc / av1_qsv / av1_amf
+/// bit 13~14 = libvpx-vp9 / vp9_qsv
+/// bit 15~17 = prores_ks / dnxhd / ffv1
 #[no_mangle]
 pub extern "C" fn exporter_detect_encoders() -> u32 {
     crate::encoding::encoder::detect_available_encoders()
 }
 
-/// 자막 오버레이 목록 해제 (Export에 전달하지 않고 취소할 때만 사용)
+/// 자막 파일(SRT/VTT/ASS)을 읽어 번인 오버레이로 래스터라이즈 후 추가
+/// font_path: TTF/OTF 폰트 (ASS 폰트 이름 대신 사용), width/height: 출력 프레임 해상도
+#[no_mangle]
+pub extern "C" fn exporter_subtitle_list_add_file(
+    list: *mut c_void,
+    caption_path: *const c_char,
+    font_path: *const c_char,
+    width: u32,
+    height: u32,
+) -> i32 {
+    if list.is_null() || caption_path.is_null() || font_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if width == 0 || height == 0 {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let (caption_path, font_path) = match (CStr::from_ptr(caption_path).to_str(), CStr::from_ptr(font_path).to_str()) {
+            (Ok(c), Ok(f)) => (c, f),
+            _ => return ErrorCode::InvalidParam as i32,
+        };
+
+        let list_ref = &mut *(list as *mut SubtitleOverlayList);
+        // 기존 오버레이와 좌표 기준 해상도가 다르면 섞을 수 없음
+        if list_ref.frame_size.is_some_and(|size| size != (width, height)) {
+            return ErrorCode::InvalidParam as i32;
+        }
+
+        let parsed = match parse_caption_file(std::path::Path::new(caption_path)) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("[EXPORT] {}", e);
+                return ErrorCode::InvalidParam as i32;
+            }
+        };
+
+        let play_res = parsed.play_res.unwrap_or((width, height));
+        let rendered = TextRenderer::new().render_cues(
+            &parsed.cues,
+            play_res,
+            std::path::Path::new(font_path),
+            width,
+            height,
+        );
+        match rendered {
+            Ok(rendered) => {
+                list_ref.overlays.extend(rendered.overlays);
+                list_ref.frame_size = rendered.frame_size;
+            }
+            Err(e) => {
+                eprintln!("[EXPORT] 자막 렌더링 실패: {}", e);
+                return ErrorCode::Io as i32;
+            }
+        }
+    }
+
+    ErrorCode::Success as i32
+}
+
+/// 자막 오버레이 목록 해제 (Export/Timeline에 전달하지 않고 취소할 때만 사용)
 #[no_mangle]
 pub extern "C" fn exporter_free_subtitle_list(list: *mut c_void) -> i32 {
     if list.is_null() {
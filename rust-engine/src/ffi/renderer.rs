@@ -1,7 +1,7 @@
 // Renderer FFI - C# 연동
 
 use crate::rendering::{Renderer, PlaybackEngine};
-use crate::timeline::Timeline;
+use crate::timeline::{FitMode, Timeline};
 use crate::ffmpeg::Decoder;
 use crate::ffi::types::ErrorCode;
 use std::ffi::{c_void, c_char, CStr};
@@ -223,6 +223,40 @@ pub extern "C" fn renderer_set_clip_effects(
     }
 }
 
+/// 클립 블렌드 모드 + 불투명도 설정 (C# Inspector에서 호출)
+/// blend_mode: 0=Normal, 1=Multiply, 2=Screen, 3=Overlay, 4=Add, 5=Subtract, 6=Darken, 7=Lighten
+/// opacity: 0.0 ~ 1.0
+#[no_mangle]
+pub extern "C" fn renderer_set_clip_blend_mode(
+    renderer: *mut c_void,
+    clip_id: u64,
+    blend_mode: u32,
+    opacity: f32,
+) -> i32 {
+    if renderer.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+    if !opacity.is_finite() {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let renderer_mutex = &*(renderer as *const Mutex<Renderer>);
+        // 이펙트와 달리 Timeline에 저장되는 값이므로 busy여도 무시하지 않고 대기
+        match renderer_mutex.lock() {
+            Ok(mut r) => {
+                use crate::timeline::BlendMode;
+                if r.set_clip_blend_mode(clip_id, BlendMode::from_u32(blend_mode), opacity) {
+                    ErrorCode::Success as i32
+                } else {
+                    ErrorCode::InvalidParam as i32
+                }
+            }
+            Err(_) => ErrorCode::RenderFailed as i32,
+        }
+    }
+}
+
 /// 렌더링된 프레임 데이터 해제
 #[no_mangle]
 pub extern "C" fn renderer_free_frame_data(data: *mut u8, size: usize) -> i32 {
@@ -305,8 +339,8 @@ pub extern "C" fn generate_video_thumbnail(
 
         let path = PathBuf::from(file_path_str);
 
-        // 임시 Decoder 생성 (단일 프레임이므로 960x540 기본 해상도)
-        let mut decoder = match Decoder::open(&path) {
+        // 임시 Decoder 생성 (썸네일 해상도로 직접 디코딩, 클립 기본값과 같은 Fit)
+        let mut decoder = match Decoder::open_with_fit(&path, thumb_width, thumb_height, FitMode::default()) {
             Ok(d) => d,
             Err(e) => {
                 eprintln!("generate_video_thumbnail: Failed to open: {}", e);
@@ -6,6 +6,7 @@
 
 use crate::ffmpeg::decoder::{Decoder, DecodeResult};
 use crate::ffi::types::ErrorCode;
+use crate::timeline::FitMode;
 use std::ffi::{c_char, CStr};
 use std::path::PathBuf;
 
@@ -20,6 +21,7 @@ pub struct ThumbnailSession {
 /// - out_session: 세션 핸들 (caller가 소유, thumbnail_session_destroy로 해제)
 /// - out_duration_ms: 비디오 총 길이 (ms)
 /// - out_fps: 비디오 FPS
+/// NOTE: 클립 기본값과 같은 Fit (레터박스) — 다른 fit_mode는 thumbnail_session_create_with_fit 사용
 #[no_mangle]
 pub extern "C" fn thumbnail_session_create(
     file_path: *const c_char,
@@ -28,6 +30,30 @@ pub extern "C" fn thumbnail_session_create(
     out_session: *mut *mut ThumbnailSession,
     out_duration_ms: *mut i64,
     out_fps: *mut f64,
+) -> i32 {
+    thumbnail_session_create_with_fit(
+        file_path,
+        thumb_width,
+        thumb_height,
+        FitMode::default() as u32,
+        out_session,
+        out_duration_ms,
+        out_fps,
+    )
+}
+
+/// 썸네일 세션 생성 (종횡비 맞춤 방식 지정)
+/// - fit_mode: 0=Fit, 1=Fill, 2=Stretch, 3=BlurFill (클립 fit_mode와 동일하게 전달)
+/// - 나머지 인자는 thumbnail_session_create와 동일
+#[no_mangle]
+pub extern "C" fn thumbnail_session_create_with_fit(
+    file_path: *const c_char,
+    thumb_width: u32,
+    thumb_height: u32,
+    fit_mode: u32,
+    out_session: *mut *mut ThumbnailSession,
+    out_duration_ms: *mut i64,
+    out_fps: *mut f64,
 ) -> i32 {
     if file_path.is_null() || out_session.is_null()
         || out_duration_ms.is_null() || out_fps.is_null()
@@ -45,7 +71,8 @@ pub extern "C" fn thumbnail_session_create(
         let path = PathBuf::from(file_path_str);
 
         // 썸네일 해상도로 직접 디코딩 (960x540 거치지 않음)
-        let mut decoder = match Decoder::open_with_resolution(&path, thumb_width, thumb_height) {
+        let fit_mode = FitMode::from_u32(fit_mode);
+        let mut decoder = match Decoder::open_with_fit(&path, thumb_width, thumb_height, fit_mode) {
             Ok(d) => d,
             Err(e) => {
                 eprintln!("thumbnail_session_create: Failed to open decoder: {}", e);
@@ -70,6 +97,60 @@ pub extern "C" fn thumbnail_session_create(
     ErrorCode::Success as i32
 }
 
+/// 이미지 시퀀스 썸네일 세션 생성
+/// - file_pattern: "render_%05d.png" 형식 패턴
+/// - fps: 시퀀스 프레임레이트 (out_duration_ms는 프레임 수 / fps)
+/// - 나머지 인자는 thumbnail_session_create_with_fit과 동일
+#[no_mangle]
+pub extern "C" fn thumbnail_session_create_sequence(
+    file_pattern: *const c_char,
+    fps: f64,
+    thumb_width: u32,
+    thumb_height: u32,
+    fit_mode: u32,
+    out_session: *mut *mut ThumbnailSession,
+    out_duration_ms: *mut i64,
+    out_fps: *mut f64,
+) -> i32 {
+    if file_pattern.is_null() || out_session.is_null()
+        || out_duration_ms.is_null() || out_fps.is_null()
+    {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    if !(fps.is_finite() && fps > 0.0) {
+        return ErrorCode::InvalidParam as i32;
+    }
+
+    unsafe {
+        let c_str = CStr::from_ptr(file_pattern);
+        let pattern_str = match c_str.to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let pattern = PathBuf::from(pattern_str);
+
+        let fit_mode = FitMode::from_u32(fit_mode);
+        let mut decoder = match Decoder::open_sequence_with_fit(&pattern, fps, thumb_width, thumb_height, fit_mode) {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("thumbnail_session_create_sequence: Failed to open decoder: {}", e);
+                return ErrorCode::Ffmpeg as i32;
+            }
+        };
+
+        decoder.set_forward_threshold(10_000);
+
+        *out_duration_ms = decoder.duration_ms();
+        *out_fps = decoder.fps();
+
+        *out_session = Box::into_raw(Box::new(ThumbnailSession { decoder }));
+    }
+
+    ErrorCode::Success as i32
+}
+
 /// 세션에서 특정 timestamp의 썸네일 생성
 /// - 디코더가 이미 열려있으므로 파일 Open/Close 오버헤드 없음
 /// - 시간순 호출 시 forward decode 활용 (seek 최소화)
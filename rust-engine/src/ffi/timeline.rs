@@ -6,8 +6,10 @@ use std::os::raw::c_char;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-use crate::timeline::{Timeline, TransitionType};
-use super::types::{ERROR_SUCCESS, ERROR_NULL_PTR, ERROR_INVALID_PARAM};
+use crate::timeline::{Timeline, TransitionType, Keyframe, KeyframeProperty, InterpolationType, ClipTransform, FitMode, GeneratorKind, GeneratorParams};
+use super::types::{ERROR_SUCCESS, ERROR_NULL_PTR, ERROR_INVALID_PARAM, CClipTransform, CGeneratorParams, CTextStyle};
+use crate::subtitle::text::{TextAlign, TextLayer, TextStyle, VerticalAlign};
+use crate::subtitle::overlay::SubtitleOverlayList;
 
 type TimelineArc = Arc<Mutex<Timeline>>;
 
@@ -154,6 +156,328 @@ pub extern "C" fn timeline_add_video_clip(
     }
 }
 
+/// 이미지 클립 추가 (PNG/JPEG/WebP, 비디오 트랙에 배치)
+/// duration_ms: 타임라인 상 표시 시간 (임의 길이)
+#[no_mangle]
+pub extern "C" fn timeline_add_image_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    file_path: *const c_char,
+    start_time_ms: i64,
+    duration_ms: i64,
+    out_clip_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || file_path.is_null() || out_clip_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    if duration_ms <= 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let path_str = unsafe {
+        match CStr::from_ptr(file_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    };
+
+    unsafe {
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match timeline.add_image_clip(track_id, PathBuf::from(path_str), start_time_ms, duration_ms) {
+            Some(clip_id) => {
+                *out_clip_id = clip_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM, // 트랙을 찾을 수 없음
+        }
+    }
+}
+
+/// 이미지 시퀀스 클립 추가 (VFX/애니메이션 렌더 결과)
+/// file_pattern: "render_%05d.png" 형식 (printf 스타일 번호 자리)
+/// fps: 시퀀스 프레임레이트 (> 0)
+#[no_mangle]
+pub extern "C" fn timeline_add_sequence_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    file_pattern: *const c_char,
+    fps: f64,
+    start_time_ms: i64,
+    duration_ms: i64,
+    out_clip_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || file_pattern.is_null() || out_clip_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    if duration_ms <= 0 || !(fps.is_finite() && fps > 0.0) {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let pattern_str = unsafe {
+        match CStr::from_ptr(file_pattern).to_str() {
+            Ok(s) => s,
+            Err(_) => return ERROR_INVALID_PARAM,
+        }
+    };
+
+    let pattern = PathBuf::from(pattern_str);
+    if !crate::ffmpeg::sequence::is_sequence_pattern(&pattern) {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match timeline.add_sequence_clip(track_id, pattern, fps, start_time_ms, duration_ms) {
+            Some(clip_id) => {
+                *out_clip_id = clip_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM, // 트랙을 찾을 수 없음
+        }
+    }
+}
+
+/// CGeneratorParams → GeneratorParams 변환 (잘못된 종류/값이면 None)
+fn generator_params_from_c(params: &CGeneratorParams) -> Option<GeneratorParams> {
+    let kind = GeneratorKind::from_u32(params.kind)?;
+    if !params.angle_deg.is_finite() || !(params.cell_size.is_finite() && params.cell_size > 0.0) {
+        return None;
+    }
+    Some(GeneratorParams {
+        kind,
+        color_a: params.color_a.to_be_bytes(),
+        color_b: params.color_b.to_be_bytes(),
+        angle_deg: params.angle_deg,
+        cell_size: params.cell_size,
+    })
+}
+
+/// 제너레이터 클립 추가 (단색/그라데이션/컬러바/체커보드/카운트다운, 미디어 파일 없음)
+#[no_mangle]
+pub extern "C" fn timeline_add_generator_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    params: *const CGeneratorParams,
+    start_time_ms: i64,
+    duration_ms: i64,
+    out_clip_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || params.is_null() || out_clip_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    if duration_ms <= 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        let params = match generator_params_from_c(&*params) {
+            Some(p) => p,
+            None => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match timeline.add_generator_clip(track_id, params, start_time_ms, duration_ms) {
+            Some(clip_id) => {
+                *out_clip_id = clip_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM, // 트랙을 찾을 수 없음
+        }
+    }
+}
+
+/// 제너레이터 클립 파라미터 변경 (제너레이터 클립이 아니면 ERROR_INVALID_PARAM)
+#[no_mangle]
+pub extern "C" fn timeline_set_generator_params(
+    timeline: *mut std::ffi::c_void,
+    clip_id: u64,
+    params: *const CGeneratorParams,
+) -> i32 {
+    if timeline.is_null() || params.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let params = match generator_params_from_c(&*params) {
+            Some(p) => p,
+            None => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        if timeline.set_generator_params(clip_id, params) {
+            ERROR_SUCCESS
+        } else {
+            ERROR_INVALID_PARAM
+        }
+    }
+}
+
+/// 텍스트 + CTextStyle → TextLayer 변환 (null/잘못된 UTF-8/비정상 값이면 None)
+unsafe fn text_layer_from_c(text: *const c_char, style: &CTextStyle) -> Option<TextLayer> {
+    if style.font_path.is_null() {
+        return None;
+    }
+    let text = CStr::from_ptr(text).to_str().ok()?;
+    let font_path = CStr::from_ptr(style.font_path).to_str().ok()?;
+
+    let values = [
+        style.size, style.outline_width, style.shadow_offset_x, style.shadow_offset_y,
+        style.position_x, style.position_y, style.max_width, style.line_spacing,
+    ];
+    if values.iter().any(|v| !v.is_finite()) || style.size <= 0.0 {
+        return None;
+    }
+
+    Some(TextLayer {
+        text: text.to_string(),
+        style: TextStyle {
+            font_path: PathBuf::from(font_path),
+            size: style.size,
+            color: style.color.to_be_bytes(),
+            outline_color: style.outline_color.to_be_bytes(),
+            outline_width: style.outline_width.max(0.0),
+            shadow_color: style.shadow_color.to_be_bytes(),
+            shadow_offset_x: style.shadow_offset_x,
+            shadow_offset_y: style.shadow_offset_y,
+            align: TextAlign::from_u32(style.align),
+            vertical_align: VerticalAlign::from_u32(style.vertical_align),
+            position_x: style.position_x,
+            position_y: style.position_y,
+            max_width: style.max_width.clamp(0.0, 1.0),
+            line_spacing: if style.line_spacing > 0.0 { style.line_spacing } else { 1.0 },
+        },
+    })
+}
+
+/// 텍스트 클립 추가 (타이틀/자막 — 엔진이 폰트로 직접 렌더링)
+/// text: UTF-8 ('\n' 강제 줄바꿈), style.font_path: TTF/OTF 경로
+#[no_mangle]
+pub extern "C" fn timeline_add_text_clip(
+    timeline: *mut std::ffi::c_void,
+    track_id: u64,
+    text: *const c_char,
+    style: *const CTextStyle,
+    start_time_ms: i64,
+    duration_ms: i64,
+    out_clip_id: *mut u64,
+) -> i32 {
+    if timeline.is_null() || text.is_null() || style.is_null() || out_clip_id.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    if duration_ms <= 0 {
+        return ERROR_INVALID_PARAM;
+    }
+
+    unsafe {
+        let layer = match text_layer_from_c(text, &*style) {
+            Some(l) => l,
+            None => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match timeline.add_text_clip(track_id, layer, start_time_ms, duration_ms) {
+            Some(clip_id) => {
+                *out_clip_id = clip_id;
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM, // 트랙을 찾을 수 없음
+        }
+    }
+}
+
+/// 텍스트 클립 내용/스타일 변경 (텍스트 클립이 아니면 ERROR_INVALID_PARAM)
+#[no_mangle]
+pub extern "C" fn timeline_set_text_clip(
+    timeline: *mut std::ffi::c_void,
+    clip_id: u64,
+    text: *const c_char,
+    style: *const CTextStyle,
+) -> i32 {
+    if timeline.is_null() || text.is_null() || style.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let layer = match text_layer_from_c(text, &*style) {
+            Some(l) => l,
+            None => return ERROR_INVALID_PARAM,
+        };
+
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        if timeline.set_text_layer(clip_id, layer) {
+            ERROR_SUCCESS
+        } else {
+            ERROR_INVALID_PARAM
+        }
+    }
+}
+
+/// 번인 자막 목록 지정 (프리뷰 renderer_render_frame / playback_engine / Export 모두 반영)
+/// subtitle_list: exporter_create_subtitle_list()로 생성한 핸들, 성공 시 소유권이 Timeline으로 이전됨
+/// (에러 반환 시 소유권 이전 없음 → 호출자가 해제)
+/// null이면 기존 자막 제거
+#[no_mangle]
+pub extern "C" fn timeline_set_subtitle_list(
+    timeline: *mut std::ffi::c_void,
+    subtitle_list: *mut std::ffi::c_void,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        // lock 실패 시 소유권을 가져오지 않음 (호출자가 subtitle_list 해제)
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        let subtitles = if subtitle_list.is_null() {
+            None
+        } else {
+            Some(*Box::from_raw(subtitle_list as *mut SubtitleOverlayList))
+        };
+        timeline.set_subtitles(subtitles);
+    }
+
+    ERROR_SUCCESS
+}
+
 /// 오디오 클립 추가
 #[no_mangle]
 pub extern "C" fn timeline_add_audio_clip(
@@ -519,6 +843,87 @@ pub extern "C" fn timeline_set_clip_transition(
     ERROR_INVALID_PARAM
 }
 
+/// 클립 종횡비 맞춤 방식 설정 (비디오 트랙 only)
+/// fit_mode: 0=Fit(레터박스), 1=Fill(크롭), 2=Stretch, 3=BlurFill(흐린 배경)
+#[no_mangle]
+pub extern "C" fn timeline_set_clip_fit_mode(
+    timeline: *mut std::ffi::c_void,
+    clip_id: u64,
+    fit_mode: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        for track in &mut timeline.video_tracks {
+            if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+                clip.fit_mode = FitMode::from_u32(fit_mode);
+                return ERROR_SUCCESS;
+            }
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
+/// 클립 트랜스폼 설정 (비디오 트랙 only)
+/// transform: 위치/크기/회전/크롭/앵커 (CClipTransform 참조)
+#[no_mangle]
+pub extern "C" fn timeline_set_clip_transform(
+    timeline: *mut std::ffi::c_void,
+    clip_id: u64,
+    transform: *const CClipTransform,
+) -> i32 {
+    if timeline.is_null() || transform.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    unsafe {
+        let t = &*transform;
+        let values = [
+            t.position_x, t.position_y, t.scale_x, t.scale_y, t.rotation_deg,
+            t.crop_left, t.crop_top, t.crop_right, t.crop_bottom, t.anchor_x, t.anchor_y,
+        ];
+        if values.iter().any(|v| !v.is_finite()) {
+            return ERROR_INVALID_PARAM;
+        }
+
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut timeline = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        for track in &mut timeline.video_tracks {
+            if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+                clip.transform = ClipTransform {
+                    position_x: t.position_x,
+                    position_y: t.position_y,
+                    scale_x: t.scale_x,
+                    scale_y: t.scale_y,
+                    rotation_deg: t.rotation_deg,
+                    crop_left: t.crop_left.clamp(0.0, 1.0),
+                    crop_top: t.crop_top.clamp(0.0, 1.0),
+                    crop_right: t.crop_right.clamp(0.0, 1.0),
+                    crop_bottom: t.crop_bottom.clamp(0.0, 1.0),
+                    anchor_x: t.anchor_x,
+                    anchor_y: t.anchor_y,
+                };
+                return ERROR_SUCCESS;
+            }
+        }
+    }
+
+    ERROR_INVALID_PARAM
+}
+
 /// 트랙 뮤트 설정 (비디오 + 오디오 트랙 공용)
 /// muted: 0=unmute, 1=mute
 #[no_mangle]
@@ -545,3 +950,98 @@ pub extern "C" fn timeline_set_track_muted(
         }
     }
 }
+
+/// 클립 키프레임 추가 (비디오 + 오디오 클립 공용, 같은 시간 키프레임은 교체)
+/// property: 0=Opacity, 1=Volume, 2=PositionX, 3=PositionY, 4=Scale, 5=Rotation,
+///           6=Brightness, 7=Contrast, 8=Saturation, 9=Temperature
+/// time_ms: 클립 시작 기준 상대 시간
+/// interpolation: 0=Linear, 1=Bezier, 2=EaseIn, 3=EaseOut, 4=EaseInOut, 5=Hold
+/// in_handle, out_handle: 베지어 핸들 값 오프셋 (NaN = 핸들 없음)
+#[no_mangle]
+pub extern "C" fn timeline_add_clip_keyframe(
+    timeline: *mut std::ffi::c_void,
+    clip_id: u64,
+    property: u32,
+    time_ms: f64,
+    value: f64,
+    interpolation: u32,
+    in_handle: f64,
+    out_handle: f64,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let property = match KeyframeProperty::from_u32(property) {
+        Some(p) => p,
+        None => return ERROR_INVALID_PARAM,
+    };
+    if !time_ms.is_finite() || !value.is_finite() {
+        return ERROR_INVALID_PARAM;
+    }
+
+    let keyframe = Keyframe {
+        time_ms,
+        value,
+        interpolation: InterpolationType::from_u32(interpolation),
+        in_handle: if in_handle.is_nan() { None } else { Some(in_handle) },
+        out_handle: if out_handle.is_nan() { None } else { Some(out_handle) },
+    };
+
+    unsafe {
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut tl = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match tl.get_clip_keyframes_mut(clip_id) {
+            Some(keyframes) => {
+                keyframes.add(property, keyframe);
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM,
+        }
+    }
+}
+
+/// 클립 키프레임 제거
+/// property: timeline_add_clip_keyframe와 동일, u32::MAX(0xFFFFFFFF) = 모든 속성
+#[no_mangle]
+pub extern "C" fn timeline_clear_clip_keyframes(
+    timeline: *mut std::ffi::c_void,
+    clip_id: u64,
+    property: u32,
+) -> i32 {
+    if timeline.is_null() {
+        return ERROR_NULL_PTR;
+    }
+
+    let property = if property == u32::MAX {
+        None
+    } else {
+        match KeyframeProperty::from_u32(property) {
+            Some(p) => Some(p),
+            None => return ERROR_INVALID_PARAM,
+        }
+    };
+
+    unsafe {
+        let timeline_arc = &*(timeline as *const Mutex<Timeline>);
+        let mut tl = match timeline_arc.lock() {
+            Ok(t) => t,
+            Err(_) => return ERROR_INVALID_PARAM,
+        };
+
+        match tl.get_clip_keyframes_mut(clip_id) {
+            Some(keyframes) => {
+                match property {
+                    Some(p) => keyframes.clear(p),
+                    None => keyframes.clear_all(),
+                }
+                ERROR_SUCCESS
+            }
+            None => ERROR_INVALID_PARAM,
+        }
+    }
+}
@@ -3,6 +3,7 @@
 // TranscriberJob 핸들을 opaque pointer로 노출
 
 use crate::ffi::types::ErrorCode;
+use crate::subtitle::caption::{write_caption_file, CaptionFormat, CaptionOptions};
 use crate::transcription::{start_transcription, TranscriberJob};
 use std::ffi::{c_char, CStr, CString};
 use std::os::raw::c_void;
@@ -181,6 +182,54 @@ pub extern "C" fn transcriber_free_segments(ptr: *mut CTranscriptSegment, count:
     }
 }
 
+/// 세그먼트를 자막 파일로 저장 (SRT / WebVTT / ASS)
+/// format: 0=SRT, 1=WebVTT, 2=ASS
+/// max_line_chars: 한 줄 최대 글자 수 (0=줄바꿈 안 함)
+/// max_lines: cue당 최대 줄 수 (0이면 기본 2줄)
+/// max_duration_ms: cue당 최대 표시 시간 (0=분할 안 함)
+#[no_mangle]
+pub extern "C" fn transcriber_write_captions(
+    job: *mut c_void,
+    output_path: *const c_char,
+    format: u32,
+    max_line_chars: u32,
+    max_lines: u32,
+    max_duration_ms: i64,
+) -> i32 {
+    if job.is_null() || output_path.is_null() {
+        return ErrorCode::NullPointer as i32;
+    }
+
+    let format = match CaptionFormat::from_u32(format) {
+        Some(f) => f,
+        None => return ErrorCode::InvalidParam as i32,
+    };
+
+    unsafe {
+        let path = match CStr::from_ptr(output_path).to_str() {
+            Ok(s) => s,
+            Err(_) => return ErrorCode::InvalidParam as i32,
+        };
+
+        let job_ref = &*(job as *const TranscriberJob);
+        let cues: Vec<_> = job_ref.get_segments().iter().map(|s| s.to_cue()).collect();
+        let opts = CaptionOptions {
+            max_line_chars: max_line_chars as usize,
+            max_lines: if max_lines == 0 { 2 } else { max_lines as usize },
+            max_duration_ms: max_duration_ms.max(0),
+            ..Default::default()
+        };
+
+        match write_caption_file(std::path::Path::new(path), &cues, format, &opts) {
+            Ok(()) => ErrorCode::Success as i32,
+            Err(e) => {
+                eprintln!("[TRANSCRIBER] {}", e);
+                ErrorCode::Io as i32
+            }
+        }
+    }
+}
+
 /// 문자열 해제 (transcriber_get_error로 할당된 것)
 #[no_mangle]
 pub extern "C" fn transcriber_free_string(ptr: *mut c_char) {
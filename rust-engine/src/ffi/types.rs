@@ -51,3 +51,50 @@ pub struct CRenderFrame {
     pub data: *mut u8,
     pub data_len: usize,
 }
+
+/// C-compatible 제너레이터 파라미터 (timeline_add_generator_clip 입력)
+/// 색상은 0xRRGGBBAA
+#[repr(C)]
+pub struct CGeneratorParams {
+    pub kind: u32,          // 0=단색, 1=선형 그라데이션, 2=원형 그라데이션, 3=컬러바, 4=체커보드, 5=카운트다운
+    pub color_a: u32,
+    pub color_b: u32,
+    pub angle_deg: f32,     // 선형 그라데이션 방향
+    pub cell_size: f32,     // 체커보드 칸 크기 (타임라인 픽셀, > 0)
+}
+
+/// C-compatible 텍스트 스타일 (timeline_add_text_clip 입력)
+/// 색상은 0xRRGGBBAA, 크기/두께/오프셋은 타임라인 픽셀
+#[repr(C)]
+pub struct CTextStyle {
+    pub font_path: *const c_char,   // UTF-8 TTF/OTF 경로
+    pub size: f32,
+    pub color: u32,
+    pub outline_color: u32,
+    pub outline_width: f32,
+    pub shadow_color: u32,          // alpha 0 = 그림자 없음
+    pub shadow_offset_x: f32,
+    pub shadow_offset_y: f32,
+    pub align: u32,                 // 0=왼쪽, 1=가운데, 2=오른쪽
+    pub vertical_align: u32,        // 0=위, 1=가운데, 2=아래
+    pub position_x: f32,            // 0.0~1.0
+    pub position_y: f32,
+    pub max_width: f32,             // 0.0~1.0 (0 = 줄바꿈 안 함)
+    pub line_spacing: f32,
+}
+
+/// C-compatible 클립 트랜스폼 구조체 (timeline_set_clip_transform 입력)
+#[repr(C)]
+pub struct CClipTransform {
+    pub position_x: f32,    // 타임라인 픽셀 (0 = 제자리)
+    pub position_y: f32,
+    pub scale_x: f32,       // 1.0 = 캔버스 크기
+    pub scale_y: f32,
+    pub rotation_deg: f32,  // 시계 방향
+    pub crop_left: f32,     // 0.0~1.0
+    pub crop_top: f32,
+    pub crop_right: f32,
+    pub crop_bottom: f32,
+    pub anchor_x: f32,      // 0.0~1.0 (0.5 = 중앙)
+    pub anchor_y: f32,
+}
@@ -3,6 +3,9 @@
 
 use ffmpeg_next as ffmpeg;
 use std::path::Path;
+use super::fit::{self, FitLayout};
+use super::sequence;
+use crate::timeline::FitMode;
 
 /// 비디오 프레임 데이터
 #[derive(Debug, Clone)]
@@ -42,6 +45,15 @@ pub enum DecodeResult {
     EndOfStreamEmpty,
 }
 
+/// BlurFill 배경용 저해상도 스케일러
+struct BlurBackground {
+    scaler: ffmpeg::software::scaling::Context,
+    /// 저해상도 캔버스 기준 Fill 레이아웃
+    layout: FitLayout,
+    width: u32,
+    height: u32,
+}
+
 /// 비디오 디코더 (ffmpeg-next, 상태 머신 기반)
 pub struct Decoder {
     input_ctx: ffmpeg::format::context::Input,
@@ -69,6 +81,10 @@ pub struct Decoder {
     /// true: 디코더 → YUV420P → 인코더 (색공간 변환 없이 최고 품질)
     /// false: 디코더 → RGBA → 프리뷰/썸네일/인코더
     yuv_output: bool,
+    /// 스케일러 출력 → 캔버스(width x height) 배치 (종횡비 맞춤)
+    fit_layout: FitLayout,
+    /// BlurFill 모드의 배경 생성기 (그 외 모드/동일 종횡비는 None)
+    background: Option<BlurBackground>,
 }
 
 impl Decoder {
@@ -103,39 +119,72 @@ impl Decoder {
         Ok((decoder, false))  // is_hardware will be updated based on actual usage
     }
 
-    /// 비디오 파일 열기 (프리뷰용 960x540 고정 해상도)
+    /// 비디오 파일 열기 (프리뷰용 960x540 고정 해상도, 종횡비 무시)
     pub fn open(file_path: &Path) -> Result<Self, String> {
-        Self::open_internal(file_path, 960, 540, false, false)
+        Self::open_internal(file_path, 960, 540, false, false, FitMode::Stretch, None)
     }
 
-    /// 비디오 파일 열기 (커스텀 출력 해상도 지정)
+    /// 비디오 파일 열기 (커스텀 출력 해상도 지정, 종횡비 무시)
     /// 썸네일 세션에서는 직접 썸네일 크기로 디코딩하여 불필요한 다운스케일 방지
     pub fn open_with_resolution(file_path: &Path, target_width: u32, target_height: u32) -> Result<Self, String> {
-        Self::open_internal(file_path, target_width, target_height, false, false)
+        Self::open_internal(file_path, target_width, target_height, false, false, FitMode::Stretch, None)
+    }
+
+    /// 비디오 파일 열기 (RGBA, 출력 해상도 + 종횡비 맞춤 방식 지정)
+    /// 프리뷰 렌더러/썸네일 세션에서 클립의 fit_mode와 동일하게 디코딩
+    pub fn open_with_fit(file_path: &Path, target_width: u32, target_height: u32, fit_mode: FitMode) -> Result<Self, String> {
+        Self::open_internal(file_path, target_width, target_height, false, false, fit_mode, None)
     }
 
     /// Export용 고품질 디코더 (YUV420P 직접 출력 + LANCZOS 리사이즈)
     /// RGBA 변환을 건너뛰어 색공간 변환 손실 제거
-    pub fn open_for_export(file_path: &Path, target_width: u32, target_height: u32) -> Result<Self, String> {
-        Self::open_internal(file_path, target_width, target_height, true, true)
+    pub fn open_for_export(file_path: &Path, target_width: u32, target_height: u32, fit_mode: FitMode) -> Result<Self, String> {
+        Self::open_internal(file_path, target_width, target_height, true, true, fit_mode, None)
+    }
+
+    /// 이미지 시퀀스 열기 (RGBA, 프리뷰/썸네일용)
+    /// - pattern: "render_%05d.png" 형식 (printf 스타일 번호 자리)
+    /// - fps: 시퀀스 프레임레이트 (이미지 파일에는 시간 정보가 없으므로 필수)
+    pub fn open_sequence_with_fit(pattern: &Path, fps: f64, target_width: u32, target_height: u32, fit_mode: FitMode) -> Result<Self, String> {
+        Self::open_internal(pattern, target_width, target_height, false, false, fit_mode, Some(fps))
+    }
+
+    /// 이미지 시퀀스 열기 (Export용 YUV420P + LANCZOS)
+    pub fn open_sequence_for_export(pattern: &Path, fps: f64, target_width: u32, target_height: u32, fit_mode: FitMode) -> Result<Self, String> {
+        Self::open_internal(pattern, target_width, target_height, true, true, fit_mode, Some(fps))
     }
 
     /// 내부 디코더 생성
     /// - high_quality: LANCZOS(Export) vs FAST_BILINEAR(프리뷰)
     /// - yuv_output: YUV420P 직접 출력(Export) vs RGBA(프리뷰)
-    fn open_internal(file_path: &Path, target_width: u32, target_height: u32, high_quality: bool, yuv_output: bool) -> Result<Self, String> {
+    /// - fit_mode: 원본 종횡비와 출력 종횡비가 다를 때 배치 방식
+    /// - sequence_fps: Some이면 file_path를 이미지 시퀀스 패턴으로 열기
+    fn open_internal(
+        file_path: &Path,
+        target_width: u32,
+        target_height: u32,
+        high_quality: bool,
+        yuv_output: bool,
+        fit_mode: FitMode,
+        sequence_fps: Option<f64>,
+    ) -> Result<Self, String> {
         ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
 
-        // 1차 시도: 기본 오픈
-        // 2차 시도: moov atom이 파일 끝에 있는 경우 (카메라 녹화본 등) — probesize 확장
-        let input_ctx = ffmpeg::format::input(&file_path)
-            .or_else(|_| {
-                let mut opts = ffmpeg::Dictionary::new();
-                opts.set("probesize", "100000000");   // 100MB
-                opts.set("analyzeduration", "30000000"); // 30초
-                ffmpeg::format::input_with_dictionary(&file_path, opts)
-            })
-            .map_err(|e| format!("Failed to open file: {}", e))?;
+        let input_ctx = match sequence_fps {
+            Some(fps) => Self::open_sequence_input(file_path, fps)?,
+            None => {
+                // 1차 시도: 기본 오픈
+                // 2차 시도: moov atom이 파일 끝에 있는 경우 (카메라 녹화본 등) — probesize 확장
+                ffmpeg::format::input(&file_path)
+                    .or_else(|_| {
+                        let mut opts = ffmpeg::Dictionary::new();
+                        opts.set("probesize", "100000000");   // 100MB
+                        opts.set("analyzeduration", "30000000"); // 30초
+                        ffmpeg::format::input_with_dictionary(&file_path, opts)
+                    })
+                    .map_err(|e| format!("Failed to open file: {}", e))?
+            }
+        };
 
         let video_stream = input_ctx
             .streams()
@@ -181,17 +230,53 @@ impl Decoder {
             ffmpeg::format::Pixel::RGBA
         };
 
+        // 표시 종횡비 (SAR 반영 — 아나모픽 소스 대응)
+        let sar = decoder.aspect_ratio();
+        let display_width = if sar.numerator() > 0 && sar.denominator() > 0 {
+            src_width as f64 * sar.numerator() as f64 / sar.denominator() as f64
+        } else {
+            src_width as f64
+        };
+        let fit_layout = FitLayout::compute(
+            display_width, src_height as f64, decode_width, decode_height, fit_mode,
+        );
+
+        // 스케일러는 콘텐츠 영역 크기로 출력 → convert_frame에서 캔버스에 배치
         let scaler = ffmpeg::software::scaling::Context::get(
             decoder.format(),
             src_width,
             src_height,
             output_pixel_format,
-            decode_width,
-            decode_height,
+            fit_layout.scaled_width,
+            fit_layout.scaled_height,
             scaler_flags,
         )
         .map_err(|e| format!("Failed to create scaler: {}", e))?;
 
+        // BlurFill: 1/8 해상도 Fill 이미지를 배경으로 사용 (블러 비용 최소화)
+        let background = if fit_mode == FitMode::BlurFill
+            && !fit_layout.is_full_frame(decode_width, decode_height)
+        {
+            let bg_width = ((decode_width / 8) & !1).max(2);
+            let bg_height = ((decode_height / 8) & !1).max(2);
+            let layout = FitLayout::compute(
+                display_width, src_height as f64, bg_width, bg_height, FitMode::Fill,
+            );
+            let bg_scaler = ffmpeg::software::scaling::Context::get(
+                decoder.format(),
+                src_width,
+                src_height,
+                output_pixel_format,
+                layout.scaled_width,
+                layout.scaled_height,
+                ffmpeg::software::scaling::Flags::FAST_BILINEAR,
+            )
+            .map_err(|e| format!("Failed to create background scaler: {}", e))?;
+            Some(BlurBackground { scaler: bg_scaler, layout, width: bg_width, height: bg_height })
+        } else {
+            None
+        };
+
         let _frame_duration_ms = (1000.0 / fps).max(1.0) as i64;
 
         Ok(Self {
@@ -212,9 +297,30 @@ impl Decoder {
             forward_threshold_ms: 100,
             eof_timestamp_ms: None,
             yuv_output,
+            fit_layout,
+            background,
         })
     }
 
+    /// 이미지 시퀀스 입력 열기 (image2 demuxer)
+    /// 패턴 파일명은 image2가 자동 인식, framerate/start_number는 demuxer 옵션으로 전달
+    fn open_sequence_input(pattern: &Path, fps: f64) -> Result<ffmpeg::format::context::Input, String> {
+        if !(fps.is_finite() && fps > 0.0) {
+            return Err(format!("Invalid sequence fps: {}", fps));
+        }
+        if !sequence::is_sequence_pattern(pattern) {
+            return Err(format!("Not an image sequence pattern: {}", pattern.display()));
+        }
+        let start_number = sequence::find_start_number(pattern)
+            .ok_or_else(|| format!("No images match sequence pattern: {}", pattern.display()))?;
+
+        let mut opts = ffmpeg::Dictionary::new();
+        opts.set("framerate", &format!("{}", fps));
+        opts.set("start_number", &start_number.to_string());
+        ffmpeg::format::input_with_dictionary(&pattern, opts)
+            .map_err(|e| format!("Failed to open image sequence: {}", e))
+    }
+
     /// Forward decode 임계값 설정
     /// 썸네일 세션에서 호출하여 GOP 내 불필요한 seek 방지
     pub fn set_forward_threshold(&mut self, threshold_ms: i64) {
@@ -427,65 +533,94 @@ impl Decoder {
     /// 디코딩된 ffmpeg Video 프레임을 출력 형식으로 변환
     /// - yuv_output=false: RGBA (프리뷰/썸네일용)
     /// - yuv_output=true: YUV420P 직접 출력 (Export용 — 색공간 변환 손실 제거)
+    /// - fit_layout이 캔버스 전체가 아니면 검은 여백(또는 BlurFill 배경) 위에 배치
     /// bounds check 추가: FFmpeg이 손상된 프레임을 반환해도 panic 대신 Err 반환
     fn convert_frame(&mut self, raw_frame: &ffmpeg::frame::Video, timestamp_ms: i64) -> Result<Frame, String> {
         let mut scaled_frame = ffmpeg::frame::Video::empty();
         self.scaler.run(raw_frame, &mut scaled_frame)
             .map_err(|e| format!("Failed to scale frame: {}", e))?;
 
-        if self.yuv_output {
-            self.extract_yuv_frame(&scaled_frame, timestamp_ms)
+        let yuv_output = self.yuv_output;
+        let format = if yuv_output { PixelFormat::YUV420P } else { PixelFormat::RGBA };
+        let extract = |frame: &ffmpeg::frame::Video, width: u32, height: u32| {
+            if yuv_output {
+                Self::extract_yuv_frame(frame, width, height)
+            } else {
+                Self::extract_rgba_frame(frame, width, height)
+            }
+        };
+
+        let layout = self.fit_layout;
+        let content = extract(&scaled_frame, layout.scaled_width, layout.scaled_height)?;
+
+        let data = if layout.is_full_frame(self.width, self.height) {
+            content
         } else {
-            self.extract_rgba_frame(&scaled_frame, timestamp_ms)
-        }
+            let mut canvas = match self.background.as_mut() {
+                Some(bg) => {
+                    let mut small_frame = ffmpeg::frame::Video::empty();
+                    bg.scaler.run(raw_frame, &mut small_frame)
+                        .map_err(|e| format!("Failed to scale background: {}", e))?;
+                    let small = extract(&small_frame, bg.layout.scaled_width, bg.layout.scaled_height)?;
+                    let mut small_canvas = fit::black_canvas(format, bg.width, bg.height);
+                    fit::paste(&mut small_canvas, bg.width, bg.height, &small, &bg.layout, format);
+                    fit::blurred_background(&small_canvas, bg.width, bg.height, self.width, self.height, format)
+                }
+                None => fit::black_canvas(format, self.width, self.height),
+            };
+            fit::paste(&mut canvas, self.width, self.height, &content, &layout, format);
+            canvas
+        };
+
+        Ok(Frame {
+            width: self.width,
+            height: self.height,
+            format,
+            data,
+            timestamp_ms,
+        })
     }
 
-    /// RGBA 프레임 추출 (프리뷰/썸네일용)
-    fn extract_rgba_frame(&self, frame: &ffmpeg::frame::Video, timestamp_ms: i64) -> Result<Frame, String> {
-        let size = (self.width * self.height * 4) as usize;
+    /// RGBA 프레임 추출 (프리뷰/썸네일용) — stride 제거한 width x height 버퍼
+    pub(super) fn extract_rgba_frame(frame: &ffmpeg::frame::Video, width: u32, height: u32) -> Result<Vec<u8>, String> {
+        let size = (width * height * 4) as usize;
         let mut data = vec![0u8; size];
 
         let src_data = frame.data(0);
         let linesize = frame.stride(0);
 
         // 안전성 검증
-        let required_src_size = (self.height as usize - 1) * linesize + (self.width as usize * 4);
+        let required_src_size = (height as usize - 1) * linesize + (width as usize * 4);
         if src_data.len() < required_src_size {
             return Err(format!(
                 "Frame data too small: got {} bytes, need {} ({}x{}, stride={})",
-                src_data.len(), required_src_size, self.width, self.height, linesize
+                src_data.len(), required_src_size, width, height, linesize
             ));
         }
 
-        if linesize < self.width as usize * 4 {
+        if linesize < width as usize * 4 {
             return Err(format!(
                 "Invalid stride: {} < {} (width * 4)",
-                linesize, self.width as usize * 4
+                linesize, width as usize * 4
             ));
         }
 
-        for y in 0..self.height as usize {
+        for y in 0..height as usize {
             let src_offset = y * linesize;
-            let dst_offset = y * (self.width as usize * 4);
-            let row_size = self.width as usize * 4;
+            let dst_offset = y * (width as usize * 4);
+            let row_size = width as usize * 4;
             data[dst_offset..dst_offset + row_size]
                 .copy_from_slice(&src_data[src_offset..src_offset + row_size]);
         }
 
-        Ok(Frame {
-            width: self.width,
-            height: self.height,
-            format: PixelFormat::RGBA,
-            data,
-            timestamp_ms,
-        })
+        Ok(data)
     }
 
     /// YUV420P 프레임 추출 (Export용 — 색공간 변환 없이 직접 전달)
     /// 데이터 레이아웃: [Y plane: w*h][U plane: w/2*h/2][V plane: w/2*h/2]
-    fn extract_yuv_frame(&self, frame: &ffmpeg::frame::Video, timestamp_ms: i64) -> Result<Frame, String> {
-        let w = self.width as usize;
-        let h = self.height as usize;
+    fn extract_yuv_frame(frame: &ffmpeg::frame::Video, width: u32, height: u32) -> Result<Vec<u8>, String> {
+        let w = width as usize;
+        let h = height as usize;
         let y_size = w * h;
         let half_w = w / 2;
         let half_h = h / 2;
@@ -529,13 +664,7 @@ impl Decoder {
             }
         }
 
-        Ok(Frame {
-            width: self.width,
-            height: self.height,
-            format: PixelFormat::YUV420P,
-            data,
-            timestamp_ms,
-        })
+        Ok(data)
     }
 
     /// 다음 프레임 디코딩
@@ -0,0 +1,364 @@
+// 종횡비 맞춤 (Fit Mode) — 스케일러 출력 크기 계산 + 캔버스 합성
+// 디코더가 원본 종횡비를 유지한 채 출력 해상도 캔버스에 배치하도록 지원
+// RGBA(1 plane)와 YUV420P(3 plane) 모두 plane 단위로 동일하게 처리
+
+use super::decoder::PixelFormat;
+use crate::timeline::FitMode;
+
+/// 스케일러 출력 → 캔버스 배치 정보
+/// 모든 값은 YUV420P chroma 정렬을 위해 짝수
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FitLayout {
+    /// 스케일러 출력 크기
+    pub scaled_width: u32,
+    pub scaled_height: u32,
+    /// 스케일러 출력 내 복사 시작점 (Fill 크롭)
+    pub crop_x: u32,
+    pub crop_y: u32,
+    /// 캔버스 내 붙여넣기 위치 (Fit 레터박스/필러박스)
+    pub dst_x: u32,
+    pub dst_y: u32,
+    /// 복사 영역 크기
+    pub copy_width: u32,
+    pub copy_height: u32,
+}
+
+/// 짝수 내림 (최소 2)
+fn even(v: u32) -> u32 {
+    (v & !1).max(2)
+}
+
+impl FitLayout {
+    /// 캔버스 전체를 그대로 채우는 레이아웃 (Stretch)
+    pub fn full(width: u32, height: u32) -> Self {
+        Self {
+            scaled_width: width,
+            scaled_height: height,
+            crop_x: 0,
+            crop_y: 0,
+            dst_x: 0,
+            dst_y: 0,
+            copy_width: width,
+            copy_height: height,
+        }
+    }
+
+    /// 원본 표시 크기(src, SAR 반영)와 캔버스 크기(dst)로 레이아웃 계산
+    /// BlurFill의 전경은 Fit과 동일 (배경은 별도 생성)
+    pub fn compute(src_width: f64, src_height: f64, dst_width: u32, dst_height: u32, mode: FitMode) -> Self {
+        let full = Self::full(dst_width, dst_height);
+        if src_width <= 0.0 || src_height <= 0.0 {
+            return full;
+        }
+
+        let sx = dst_width as f64 / src_width;
+        let sy = dst_height as f64 / src_height;
+        // 종횡비 차이가 0.5% 미만이면 Stretch와 구분 불가 → 전체 채움
+        if (sx / sy - 1.0).abs() < 0.005 {
+            return full;
+        }
+
+        match mode {
+            FitMode::Stretch => full,
+            FitMode::Fit | FitMode::BlurFill => {
+                let scale = sx.min(sy);
+                let w = even((src_width * scale).round() as u32).min(dst_width);
+                let h = even((src_height * scale).round() as u32).min(dst_height);
+                Self {
+                    scaled_width: w,
+                    scaled_height: h,
+                    crop_x: 0,
+                    crop_y: 0,
+                    dst_x: ((dst_width - w) / 2) & !1,
+                    dst_y: ((dst_height - h) / 2) & !1,
+                    copy_width: w,
+                    copy_height: h,
+                }
+            }
+            FitMode::Fill => {
+                let scale = sx.max(sy);
+                let w = even((src_width * scale).ceil() as u32).max(dst_width);
+                let h = even((src_height * scale).ceil() as u32).max(dst_height);
+                Self {
+                    scaled_width: w,
+                    scaled_height: h,
+                    crop_x: ((w - dst_width) / 2) & !1,
+                    crop_y: ((h - dst_height) / 2) & !1,
+                    dst_x: 0,
+                    dst_y: 0,
+                    copy_width: dst_width,
+                    copy_height: dst_height,
+                }
+            }
+        }
+    }
+
+    /// 스케일러 출력이 곧 캔버스인지 (붙여넣기 불필요)
+    pub fn is_full_frame(&self, dst_width: u32, dst_height: u32) -> bool {
+        *self == Self::full(dst_width, dst_height)
+    }
+}
+
+/// plane 정보 (버퍼 내 오프셋, 너비, 높이, 픽셀당 바이트)
+struct Plane {
+    offset: usize,
+    width: usize,
+    height: usize,
+    bpp: usize,
+}
+
+/// 포맷별 plane 배치 (RGBA: 1 plane, YUV420P: Y/U/V 3 plane)
+fn planes(format: PixelFormat, width: u32, height: u32) -> Vec<Plane> {
+    let w = width as usize;
+    let h = height as usize;
+    match format {
+        PixelFormat::RGBA => vec![Plane { offset: 0, width: w, height: h, bpp: 4 }],
+        PixelFormat::RGB => vec![Plane { offset: 0, width: w, height: h, bpp: 3 }],
+        PixelFormat::YUV420P => {
+            let (cw, ch) = (w / 2, h / 2);
+            vec![
+                Plane { offset: 0, width: w, height: h, bpp: 1 },
+                Plane { offset: w * h, width: cw, height: ch, bpp: 1 },
+                Plane { offset: w * h + cw * ch, width: cw, height: ch, bpp: 1 },
+            ]
+        }
+    }
+}
+
+/// plane별 chroma 축소 배율 (좌표 변환용)
+fn plane_shift(format: PixelFormat, plane_index: usize) -> u32 {
+    match format {
+        PixelFormat::YUV420P if plane_index > 0 => 1,
+        _ => 0,
+    }
+}
+
+/// 검은색 캔버스 (RGBA: 불투명 검정, YUV420P: Y=16 / UV=128)
+pub fn black_canvas(format: PixelFormat, width: u32, height: u32) -> Vec<u8> {
+    match format {
+        PixelFormat::RGBA => {
+            let mut data = vec![0u8; (width * height * 4) as usize];
+            for px in data.chunks_exact_mut(4) {
+                px[3] = 255;
+            }
+            data
+        }
+        PixelFormat::RGB => vec![0u8; (width * height * 3) as usize],
+        PixelFormat::YUV420P => {
+            let y_size = (width * height) as usize;
+            let uv_size = ((width / 2) * (height / 2)) as usize;
+            let mut data = vec![128u8; y_size + uv_size * 2];
+            data[..y_size].fill(16);
+            data
+        }
+    }
+}
+
+/// 스케일러 출력(src, layout.scaled 크기)을 캔버스에 레이아웃대로 복사
+pub fn paste(
+    canvas: &mut [u8],
+    canvas_width: u32,
+    canvas_height: u32,
+    src: &[u8],
+    layout: &FitLayout,
+    format: PixelFormat,
+) {
+    let dst_planes = planes(format, canvas_width, canvas_height);
+    let src_planes = planes(format, layout.scaled_width, layout.scaled_height);
+
+    for (i, (dp, sp)) in dst_planes.iter().zip(src_planes.iter()).enumerate() {
+        let shift = plane_shift(format, i);
+        let crop_x = (layout.crop_x >> shift) as usize;
+        let crop_y = (layout.crop_y >> shift) as usize;
+        let dst_x = (layout.dst_x >> shift) as usize;
+        let dst_y = (layout.dst_y >> shift) as usize;
+        let copy_w = ((layout.copy_width >> shift) as usize)
+            .min(sp.width.saturating_sub(crop_x))
+            .min(dp.width.saturating_sub(dst_x));
+        let copy_h = ((layout.copy_height >> shift) as usize)
+            .min(sp.height.saturating_sub(crop_y))
+            .min(dp.height.saturating_sub(dst_y));
+        let row_bytes = copy_w * sp.bpp;
+
+        for row in 0..copy_h {
+            let s = sp.offset + ((crop_y + row) * sp.width + crop_x) * sp.bpp;
+            let d = dp.offset + ((dst_y + row) * dp.width + dst_x) * dp.bpp;
+            if s + row_bytes <= src.len() && d + row_bytes <= canvas.len() {
+                canvas[d..d + row_bytes].copy_from_slice(&src[s..s + row_bytes]);
+            }
+        }
+    }
+}
+
+/// BlurFill 배경 생성: 작은 Fill 이미지를 블러 후 캔버스 크기로 확대
+/// small: small_width x small_height 크기 (이미 Fill 크롭된 저해상도 프레임)
+pub fn blurred_background(
+    small: &[u8],
+    small_width: u32,
+    small_height: u32,
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+) -> Vec<u8> {
+    let src_planes = planes(format, small_width, small_height);
+    let dst_planes = planes(format, width, height);
+    let mut out = black_canvas(format, width, height);
+
+    for (sp, dp) in src_planes.iter().zip(dst_planes.iter()) {
+        if sp.width == 0 || sp.height == 0 {
+            continue;
+        }
+        let end = sp.offset + sp.width * sp.height * sp.bpp;
+        if end > small.len() {
+            continue;
+        }
+        let mut plane = small[sp.offset..end].to_vec();
+        // 박스 블러 2회 ≈ 삼각 필터 (저해상도라 반경 2로도 충분히 흐림)
+        for _ in 0..2 {
+            box_blur(&mut plane, sp.width, sp.height, sp.bpp, 2);
+        }
+        upscale_bilinear(
+            &plane, sp.width, sp.height,
+            &mut out[dp.offset..dp.offset + dp.width * dp.height * dp.bpp],
+            dp.width, dp.height, sp.bpp,
+        );
+    }
+
+    out
+}
+
+/// 분리형 박스 블러 (가로 → 세로), 가장자리는 clamp
+fn box_blur(data: &mut [u8], width: usize, height: usize, bpp: usize, radius: usize) {
+    let mut tmp = vec![0u8; data.len()];
+    let taps = (radius * 2 + 1) as u32;
+
+    // 가로
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..bpp {
+                let mut sum = 0u32;
+                for k in 0..=radius * 2 {
+                    let sx = (x + k).saturating_sub(radius).min(width - 1);
+                    sum += data[(y * width + sx) * bpp + c] as u32;
+                }
+                tmp[(y * width + x) * bpp + c] = (sum / taps) as u8;
+            }
+        }
+    }
+
+    // 세로
+    for y in 0..height {
+        for x in 0..width {
+            for c in 0..bpp {
+                let mut sum = 0u32;
+                for k in 0..=radius * 2 {
+                    let sy = (y + k).saturating_sub(radius).min(height - 1);
+                    sum += tmp[(sy * width + x) * bpp + c] as u32;
+                }
+                data[(y * width + x) * bpp + c] = (sum / taps) as u8;
+            }
+        }
+    }
+}
+
+/// bilinear 확대 (plane 단위)
+fn upscale_bilinear(
+    src: &[u8], sw: usize, sh: usize,
+    dst: &mut [u8], dw: usize, dh: usize,
+    bpp: usize,
+) {
+    let fx_scale = sw as f32 / dw as f32;
+    let fy_scale = sh as f32 / dh as f32;
+
+    for y in 0..dh {
+        let fy = ((y as f32 + 0.5) * fy_scale - 0.5).clamp(0.0, (sh - 1) as f32);
+        let y0 = fy as usize;
+        let y1 = (y0 + 1).min(sh - 1);
+        let ty = fy - y0 as f32;
+        for x in 0..dw {
+            let fx = ((x as f32 + 0.5) * fx_scale - 0.5).clamp(0.0, (sw - 1) as f32);
+            let x0 = fx as usize;
+            let x1 = (x0 + 1).min(sw - 1);
+            let tx = fx - x0 as f32;
+            for c in 0..bpp {
+                let p00 = src[(y0 * sw + x0) * bpp + c] as f32;
+                let p10 = src[(y0 * sw + x1) * bpp + c] as f32;
+                let p01 = src[(y1 * sw + x0) * bpp + c] as f32;
+                let p11 = src[(y1 * sw + x1) * bpp + c] as f32;
+                let top = p00 + (p10 - p00) * tx;
+                let bottom = p01 + (p11 - p01) * tx;
+                dst[(y * dw + x) * bpp + c] = (top + (bottom - top) * ty + 0.5) as u8;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_aspect_is_full_frame() {
+        for mode in [FitMode::Fit, FitMode::Fill, FitMode::Stretch, FitMode::BlurFill] {
+            let layout = FitLayout::compute(1920.0, 1080.0, 960, 540, mode);
+            assert!(layout.is_full_frame(960, 540));
+        }
+    }
+
+    #[test]
+    fn test_fit_vertical_source_pillarbox() {
+        // 9:16 세로 영상 → 16:9 캔버스: 가운데 세로 띠
+        let layout = FitLayout::compute(1080.0, 1920.0, 960, 540, FitMode::Fit);
+        assert_eq!(layout.scaled_height, 540);
+        assert_eq!(layout.scaled_width, 304);
+        assert_eq!(layout.dst_x, 328);
+        assert_eq!(layout.dst_y, 0);
+        assert_eq!(layout.copy_width, 304);
+    }
+
+    #[test]
+    fn test_fill_vertical_source_crops() {
+        let layout = FitLayout::compute(1080.0, 1920.0, 960, 540, FitMode::Fill);
+        assert!(layout.scaled_width >= 960);
+        assert!(layout.scaled_height > 540);
+        assert_eq!(layout.crop_x, 0);
+        assert!(layout.crop_y > 0);
+        assert_eq!((layout.copy_width, layout.copy_height), (960, 540));
+        assert_eq!((layout.dst_x, layout.dst_y), (0, 0));
+    }
+
+    #[test]
+    fn test_stretch_ignores_aspect() {
+        let layout = FitLayout::compute(1080.0, 1920.0, 960, 540, FitMode::Stretch);
+        assert!(layout.is_full_frame(960, 540));
+    }
+
+    #[test]
+    fn test_paste_yuv_letterbox() {
+        // 4x2 콘텐츠를 4x4 캔버스 가운데에 배치
+        let layout = FitLayout {
+            scaled_width: 4, scaled_height: 2,
+            crop_x: 0, crop_y: 0,
+            dst_x: 0, dst_y: 2,
+            copy_width: 4, copy_height: 2,
+        };
+        let mut canvas = black_canvas(PixelFormat::YUV420P, 4, 4);
+        let src = vec![200u8; 4 * 2 + 2 * 2]; // Y 8 + U 2 + V 2
+        paste(&mut canvas, 4, 4, &src, &layout, PixelFormat::YUV420P);
+
+        // Y: 위 2줄 검정(16), 아래 2줄 콘텐츠
+        assert!(canvas[..8].iter().all(|&v| v == 16));
+        assert!(canvas[8..16].iter().all(|&v| v == 200));
+        // U: 2x2 중 아래 줄만 콘텐츠
+        assert_eq!(&canvas[16..20], &[128, 128, 200, 200]);
+    }
+
+    #[test]
+    fn test_blurred_background_size() {
+        let small = vec![100u8; 8 * 4 * 4];
+        let bg = blurred_background(&small, 8, 4, 32, 16, PixelFormat::RGBA);
+        assert_eq!(bg.len(), 32 * 16 * 4);
+        // 균일 입력 → 균일 출력
+        assert!(bg.iter().all(|&v| v == 100));
+    }
+}
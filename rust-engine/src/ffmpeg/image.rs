@@ -0,0 +1,126 @@
+// 정지 이미지 로더 (PNG/JPEG/WebP 등 FFmpeg image2 계열)
+// 이미지 클립은 프레임이 1장뿐이므로 Decoder 상태 머신 대신 1회 디코딩 후 캐시
+
+use ffmpeg_next as ffmpeg;
+use std::path::Path;
+
+use super::decoder::{Decoder, Frame, PixelFormat};
+use super::fit::{self, FitLayout};
+use crate::timeline::FitMode;
+
+/// 이미지 파일을 RGBA(알파 유지)로 디코딩하여 width x height 캔버스에 배치
+/// - Fit 여백은 투명 (하위 트랙이 보이도록), BlurFill은 흐린 배경으로 채움
+/// - high_quality: LANCZOS(Export) vs BILINEAR(프리뷰)
+pub fn load_image(
+    file_path: &Path,
+    width: u32,
+    height: u32,
+    fit_mode: FitMode,
+    high_quality: bool,
+) -> Result<Frame, String> {
+    ffmpeg::init().map_err(|e| format!("FFmpeg init failed: {}", e))?;
+
+    let mut input_ctx = ffmpeg::format::input(&file_path)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
+
+    let stream = input_ctx
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or("No image stream found")?;
+    let stream_index = stream.index();
+
+    let context = ffmpeg::codec::context::Context::from_parameters(stream.parameters())
+        .map_err(|e| format!("Failed to create context: {}", e))?;
+    let mut decoder = context
+        .decoder()
+        .video()
+        .map_err(|e| format!("Failed to get image decoder: {}", e))?;
+
+    // 첫 프레임 디코딩 (패킷 소진 시 EOF 전송 후 drain)
+    let mut raw = ffmpeg::frame::Video::empty();
+    let mut decoded = false;
+    for (stream, packet) in input_ctx.packets() {
+        if stream.index() != stream_index {
+            continue;
+        }
+        if decoder.send_packet(&packet).is_ok() && decoder.receive_frame(&mut raw).is_ok() {
+            decoded = true;
+            break;
+        }
+    }
+    if !decoded {
+        let _ = decoder.send_eof();
+        decoded = decoder.receive_frame(&mut raw).is_ok();
+    }
+    if !decoded {
+        return Err(format!("No frame decoded from image: {}", file_path.display()));
+    }
+
+    let src_width = raw.width();
+    let src_height = raw.height();
+    let flags = if high_quality {
+        ffmpeg::software::scaling::Flags::LANCZOS
+    } else {
+        ffmpeg::software::scaling::Flags::BILINEAR
+    };
+
+    let layout = FitLayout::compute(src_width as f64, src_height as f64, width, height, fit_mode);
+    let content = scale_to_rgba(&raw, layout.scaled_width, layout.scaled_height, flags)?;
+
+    let data = if layout.is_full_frame(width, height) {
+        content
+    } else {
+        let mut canvas = if fit_mode == FitMode::BlurFill {
+            // 1/8 해상도 Fill 이미지 → 블러 → 확대
+            let bg_width = ((width / 8) & !1).max(2);
+            let bg_height = ((height / 8) & !1).max(2);
+            let bg_layout = FitLayout::compute(
+                src_width as f64, src_height as f64, bg_width, bg_height, FitMode::Fill,
+            );
+            let small = scale_to_rgba(
+                &raw, bg_layout.scaled_width, bg_layout.scaled_height,
+                ffmpeg::software::scaling::Flags::FAST_BILINEAR,
+            )?;
+            let mut small_canvas = fit::black_canvas(PixelFormat::RGBA, bg_width, bg_height);
+            fit::paste(&mut small_canvas, bg_width, bg_height, &small, &bg_layout, PixelFormat::RGBA);
+            fit::blurred_background(&small_canvas, bg_width, bg_height, width, height, PixelFormat::RGBA)
+        } else {
+            vec![0u8; (width * height * 4) as usize]
+        };
+        fit::paste(&mut canvas, width, height, &content, &layout, PixelFormat::RGBA);
+        canvas
+    };
+
+    Ok(Frame {
+        width,
+        height,
+        format: PixelFormat::RGBA,
+        data,
+        timestamp_ms: 0,
+    })
+}
+
+/// 원본 프레임을 RGBA로 스케일링 (stride 제거)
+fn scale_to_rgba(
+    raw: &ffmpeg::frame::Video,
+    width: u32,
+    height: u32,
+    flags: ffmpeg::software::scaling::Flags,
+) -> Result<Vec<u8>, String> {
+    let mut scaler = ffmpeg::software::scaling::Context::get(
+        raw.format(),
+        raw.width(),
+        raw.height(),
+        ffmpeg::format::Pixel::RGBA,
+        width,
+        height,
+        flags,
+    )
+    .map_err(|e| format!("Failed to create image scaler: {}", e))?;
+
+    let mut scaled = ffmpeg::frame::Video::empty();
+    scaler.run(raw, &mut scaled)
+        .map_err(|e| format!("Failed to scale image: {}", e))?;
+
+    Decoder::extract_rgba_frame(&scaled, width, height)
+}
@@ -2,5 +2,8 @@
 // 비디오/오디오 디코딩/인코딩
 
 pub mod decoder;
+pub mod fit;
+pub mod image;
+pub mod sequence;
 
 pub use decoder::{Decoder, Frame, PixelFormat, DecoderState, DecodeResult};
@@ -0,0 +1,139 @@
+// 이미지 시퀀스 소스 (render_%05d.png 등 번호 매긴 이미지 파일들)
+// FFmpeg image2 demuxer로 열어 일반 비디오와 동일하게 Decoder에서 처리
+
+use std::path::Path;
+
+/// 파일명 패턴 (prefix + 번호 + suffix)
+/// 예: "render_%05d.png" → prefix="render_", width=Some(5), suffix=".png"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequencePattern {
+    pub prefix: String,
+    /// 0 패딩 자릿수 (%d면 None)
+    pub width: Option<usize>,
+    pub suffix: String,
+}
+
+impl SequencePattern {
+    /// 파일명에서 printf 스타일 번호 자리(%d / %0Nd)를 찾아 분리
+    /// 번호 자리가 없거나 2개 이상이면 None
+    pub fn parse(file_name: &str) -> Option<Self> {
+        let start = file_name.find('%')?;
+        let rest = &file_name[start + 1..];
+        let d_pos = rest.find('d')?;
+        let spec = &rest[..d_pos];
+
+        let width = if spec.is_empty() {
+            None
+        } else if spec.starts_with('0') && spec.len() > 1 && spec.bytes().all(|b| b.is_ascii_digit()) {
+            Some(spec[1..].parse().ok()?)
+        } else {
+            return None;
+        };
+
+        let suffix = &rest[d_pos + 1..];
+        if suffix.contains('%') {
+            return None;
+        }
+
+        Some(Self {
+            prefix: file_name[..start].to_string(),
+            width,
+            suffix: suffix.to_string(),
+        })
+    }
+
+    /// 실제 파일명이 패턴과 일치하면 프레임 번호 반환
+    pub fn frame_number(&self, file_name: &str) -> Option<u32> {
+        let digits = file_name
+            .strip_prefix(self.prefix.as_str())?
+            .strip_suffix(self.suffix.as_str())?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        // %05d는 5자리 미만 번호를 0으로 채움 → 자릿수가 다르면 다른 시퀀스
+        if let Some(width) = self.width {
+            if digits.len() < width || (digits.len() > width && digits.starts_with('0')) {
+                return None;
+            }
+        } else if digits.len() > 1 && digits.starts_with('0') {
+            return None;
+        }
+        digits.parse().ok()
+    }
+}
+
+/// 경로가 이미지 시퀀스 패턴인지 확인
+pub fn is_sequence_pattern(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(SequencePattern::parse)
+        .is_some()
+}
+
+/// 시퀀스의 첫 프레임 번호 (디렉터리에서 패턴과 일치하는 최소 번호)
+/// image2 demuxer는 기본적으로 0~4번만 탐색하므로 start_number로 전달해야 함
+pub fn find_start_number(path: &Path) -> Option<u32> {
+    let pattern = SequencePattern::parse(path.file_name()?.to_str()?)?;
+    let dir = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| pattern.frame_number(entry.file_name().to_str()?))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pattern() {
+        let p = SequencePattern::parse("render_%05d.png").unwrap();
+        assert_eq!(p.prefix, "render_");
+        assert_eq!(p.width, Some(5));
+        assert_eq!(p.suffix, ".png");
+
+        let p = SequencePattern::parse("%d.exr").unwrap();
+        assert_eq!(p.prefix, "");
+        assert_eq!(p.width, None);
+
+        assert!(SequencePattern::parse("clip.mp4").is_none());
+        assert!(SequencePattern::parse("a_%5d.png").is_none());
+        assert!(SequencePattern::parse("a_%03d_%03d.png").is_none());
+    }
+
+    #[test]
+    fn test_frame_number() {
+        let p = SequencePattern::parse("render_%04d.png").unwrap();
+        assert_eq!(p.frame_number("render_0001.png"), Some(1));
+        assert_eq!(p.frame_number("render_12345.png"), Some(12345));
+        assert_eq!(p.frame_number("render_001.png"), None);
+        assert_eq!(p.frame_number("render_0001.jpg"), None);
+        assert_eq!(p.frame_number("other_0001.png"), None);
+
+        let p = SequencePattern::parse("f%d.png").unwrap();
+        assert_eq!(p.frame_number("f7.png"), Some(7));
+        assert_eq!(p.frame_number("f07.png"), None);
+    }
+
+    #[test]
+    fn test_find_start_number() {
+        let dir = std::env::temp_dir().join(format!("vortexcut_seq_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for n in [1001, 1002, 1003] {
+            std::fs::write(dir.join(format!("shot_{:04}.png", n)), b"").unwrap();
+        }
+        std::fs::write(dir.join("shot_0999.jpg"), b"").unwrap();
+
+        assert_eq!(find_start_number(&dir.join("shot_%04d.png")), Some(1001));
+        assert_eq!(find_start_number(&dir.join("none_%04d.png")), None);
+        assert!(is_sequence_pattern(&dir.join("shot_%04d.png")));
+        assert!(!is_sequence_pattern(&dir.join("shot_1001.png")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+// 컴포지터 — 여러 비디오 트랙 레이어를 RGBA로 합성
+// 하단 트랙(index 0)부터 위로 쌓으며 source-over 알파 블렌딩
+// 블렌드 모드는 W3C Compositing 명세의 separable 블렌드 함수 기준
+
+use crate::timeline::BlendMode;
+
+/// RGBA 버퍼가 완전 불투명한지 확인 (모든 픽셀 alpha=255)
+/// true이면 이 레이어 아래의 트랙은 보이지 않으므로 디코딩을 건너뛸 수 있음
+pub fn is_opaque(rgba: &[u8]) -> bool {
+    rgba.chunks_exact(4).all(|px| px[3] == 255)
+}
+
+/// src 레이어를 dst 위에 source-over 합성 (in-place)
+/// dst/src: 동일 크기 RGBA 버퍼 (width * height * 4)
+/// 결과 alpha = sa + da * (1 - sa) — 투명 배경 위 합성도 정확히 처리
+pub fn composite_over(dst: &mut [u8], src: &[u8], width: u32, height: u32) {
+    let pixel_count = (width * height) as usize;
+    let len = (pixel_count * 4).min(dst.len()).min(src.len());
+
+    for idx in (0..len).step_by(4) {
+        let sa = src[idx + 3] as u32;
+        if sa == 0 {
+            continue; // 완전 투명 — 하위 레이어 유지
+        }
+
+        if sa == 255 {
+            // 완전 불투명 — 직접 복사
+            dst[idx..idx + 4].copy_from_slice(&src[idx..idx + 4]);
+            continue;
+        }
+
+        let da = dst[idx + 3] as u32;
+        let inv_sa = 255 - sa;
+        // 결과 alpha (0~255 스케일)
+        let out_a = sa + (da * inv_sa + 127) / 255;
+        if out_a == 0 {
+            continue;
+        }
+
+        for c in 0..3 {
+            let s = src[idx + c] as u32;
+            let d = dst[idx + c] as u32;
+            // premultiplied 합산 후 out_a로 나눠 straight alpha로 복원
+            let premul = s * sa + (d * da * inv_sa + 127) / 255;
+            dst[idx + c] = ((premul + out_a / 2) / out_a).min(255) as u8;
+        }
+        dst[idx + 3] = out_a.min(255) as u8;
+    }
+}
+
+/// 채널 단위 블렌드 함수 (cb: 하위, cs: 상위, 0.0~1.0)
+fn blend_channel(mode: BlendMode, cb: f32, cs: f32) -> f32 {
+    match mode {
+        BlendMode::Normal => cs,
+        BlendMode::Multiply => cb * cs,
+        BlendMode::Screen => cb + cs - cb * cs,
+        // Overlay = 하위 레이어 기준 HardLight
+        BlendMode::Overlay => {
+            if cb <= 0.5 {
+                2.0 * cb * cs
+            } else {
+                1.0 - 2.0 * (1.0 - cb) * (1.0 - cs)
+            }
+        }
+        BlendMode::Add => (cb + cs).min(1.0),
+        BlendMode::Subtract => (cb - cs).max(0.0),
+        BlendMode::Darken => cb.min(cs),
+        BlendMode::Lighten => cb.max(cs),
+    }
+}
+
+/// src 레이어를 블렌드 모드 + 불투명도로 dst 위에 합성 (in-place)
+/// opacity: 0.0~1.0 (src alpha에 곱해짐)
+/// Normal + opacity 1.0이면 composite_over와 동일 (정수 경로 사용)
+pub fn composite_layer(
+    dst: &mut [u8],
+    src: &[u8],
+    width: u32,
+    height: u32,
+    mode: BlendMode,
+    opacity: f32,
+) {
+    let opacity = opacity.clamp(0.0, 1.0);
+    if opacity <= 0.0 {
+        return;
+    }
+    if mode == BlendMode::Normal && opacity >= 1.0 {
+        composite_over(dst, src, width, height);
+        return;
+    }
+
+    let pixel_count = (width * height) as usize;
+    let len = (pixel_count * 4).min(dst.len()).min(src.len());
+
+    for idx in (0..len).step_by(4) {
+        let sa = src[idx + 3] as f32 / 255.0 * opacity;
+        if sa <= 0.0 {
+            continue;
+        }
+        let da = dst[idx + 3] as f32 / 255.0;
+        let out_a = sa + da * (1.0 - sa);
+
+        for c in 0..3 {
+            let cs = src[idx + c] as f32 / 255.0;
+            let cb = dst[idx + c] as f32 / 255.0;
+            // 하위가 투명한 영역은 블렌드 없이 src 색상 그대로
+            let mixed = (1.0 - da) * cs + da * blend_channel(mode, cb, cs);
+            let out = (sa * mixed + da * (1.0 - sa) * cb) / out_a;
+            dst[idx + c] = (out * 255.0 + 0.5).clamp(0.0, 255.0) as u8;
+        }
+        dst[idx + 3] = (out_a * 255.0 + 0.5).clamp(0.0, 255.0) as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opaque_src_replaces_dst() {
+        let mut dst = vec![10, 20, 30, 255];
+        let src = vec![200, 100, 50, 255];
+        composite_over(&mut dst, &src, 1, 1);
+        assert_eq!(dst, vec![200, 100, 50, 255]);
+    }
+
+    #[test]
+    fn test_transparent_src_keeps_dst() {
+        let mut dst = vec![10, 20, 30, 255];
+        let src = vec![200, 100, 50, 0];
+        composite_over(&mut dst, &src, 1, 1);
+        assert_eq!(dst, vec![10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_half_alpha_over_opaque() {
+        let mut dst = vec![0, 0, 0, 255];
+        let src = vec![255, 255, 255, 128];
+        composite_over(&mut dst, &src, 1, 1);
+        // 검정 위 50% 흰색 → 약 128 회색, 결과는 불투명
+        assert!((dst[0] as i32 - 128).abs() <= 1);
+        assert_eq!(dst[3], 255);
+    }
+
+    #[test]
+    fn test_blend_modes_over_opaque() {
+        // 하위 50% 회색, 상위 불투명 색상
+        let base = [128u8, 128, 128, 255];
+        let src = [255u8, 64, 0, 255];
+
+        let mut dst = base.to_vec();
+        composite_layer(&mut dst, &src, 1, 1, BlendMode::Multiply, 1.0);
+        assert_eq!(dst, vec![128, 32, 0, 255]);
+
+        let mut dst = base.to_vec();
+        composite_layer(&mut dst, &src, 1, 1, BlendMode::Screen, 1.0);
+        assert_eq!(dst, vec![255, 160, 128, 255]);
+
+        let mut dst = base.to_vec();
+        composite_layer(&mut dst, &src, 1, 1, BlendMode::Add, 1.0);
+        assert_eq!(dst, vec![255, 192, 128, 255]);
+
+        let mut dst = base.to_vec();
+        composite_layer(&mut dst, &src, 1, 1, BlendMode::Subtract, 1.0);
+        assert_eq!(dst, vec![0, 64, 128, 255]);
+
+        let mut dst = base.to_vec();
+        composite_layer(&mut dst, &src, 1, 1, BlendMode::Darken, 1.0);
+        assert_eq!(dst, vec![128, 64, 0, 255]);
+
+        let mut dst = base.to_vec();
+        composite_layer(&mut dst, &src, 1, 1, BlendMode::Lighten, 1.0);
+        assert_eq!(dst, vec![255, 128, 128, 255]);
+    }
+
+    #[test]
+    fn test_overlay_keeps_mid_gray_contrast() {
+        // 하위가 어두우면 Multiply, 밝으면 Screen 쪽
+        let mut dark = vec![64u8, 64, 64, 255];
+        composite_layer(&mut dark, &[128, 128, 128, 255], 1, 1, BlendMode::Overlay, 1.0);
+        assert!((dark[0] as i32 - 64).abs() <= 1);
+
+        let mut light = vec![192u8, 192, 192, 255];
+        composite_layer(&mut light, &[128, 128, 128, 255], 1, 1, BlendMode::Overlay, 1.0);
+        assert!((light[0] as i32 - 192).abs() <= 1);
+    }
+
+    #[test]
+    fn test_opacity_scales_normal() {
+        let mut dst = vec![0, 0, 0, 255];
+        composite_layer(&mut dst, &[200, 200, 200, 255], 1, 1, BlendMode::Normal, 0.5);
+        assert!((dst[0] as i32 - 100).abs() <= 1);
+        assert_eq!(dst[3], 255);
+
+        // opacity 0 → 변화 없음
+        let mut dst = vec![10, 20, 30, 255];
+        composite_layer(&mut dst, &[200, 200, 200, 255], 1, 1, BlendMode::Screen, 0.0);
+        assert_eq!(dst, vec![10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_is_opaque() {
+        assert!(is_opaque(&[0, 0, 0, 255, 1, 2, 3, 255]));
+        assert!(!is_opaque(&[0, 0, 0, 255, 1, 2, 3, 254]));
+    }
+}
@@ -0,0 +1,277 @@
+// 제너레이터 — 미디어 파일 없이 RGBA 프레임 생성 (단색/그라데이션/테스트 패턴/카운트다운)
+
+use crate::timeline::{GeneratorKind, GeneratorParams};
+
+/// SMPTE 75% 컬러바 (상단 2/3): 흰/노랑/시안/초록/마젠타/빨강/파랑
+const BARS_TOP: [[u8; 3]; 7] = [
+    [191, 191, 191], [191, 191, 0], [0, 191, 191], [0, 191, 0],
+    [191, 0, 191], [191, 0, 0], [0, 0, 191],
+];
+/// 중간 띠 (역순 컬러 + 검정)
+const BARS_MIDDLE: [[u8; 3]; 7] = [
+    [0, 0, 191], [16, 16, 16], [191, 0, 191], [16, 16, 16],
+    [0, 191, 191], [16, 16, 16], [191, 191, 191],
+];
+/// 하단 1/4: -I, 100% 흰색, +Q, 검정 (PLUGE 생략)
+const BARS_BOTTOM: [[u8; 3]; 4] = [[0, 33, 76], [255, 255, 255], [50, 0, 106], [16, 16, 16]];
+
+/// 7-세그먼트 숫자 마스크 (bit0=a 상단, b 우상, c 우하, d 하단, e 좌하, f 좌상, g 중앙)
+const DIGIT_SEGMENTS: [u8; 10] = [0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F];
+
+/// 제너레이터 프레임 생성 (width x height RGBA)
+/// - scale: 출력 픽셀 / 타임라인 픽셀 (체커보드 칸 크기 변환)
+/// - clip_time_ms / duration_ms: 클립 기준 시간 (카운트다운용)
+pub fn render_generator(
+    params: &GeneratorParams,
+    width: u32,
+    height: u32,
+    scale: f32,
+    clip_time_ms: i64,
+    duration_ms: i64,
+) -> Vec<u8> {
+    let mut data = vec![0u8; (width * height * 4) as usize];
+    match params.kind {
+        GeneratorKind::SolidColor => {
+            for px in data.chunks_exact_mut(4) {
+                px.copy_from_slice(&params.color_a);
+            }
+        }
+        GeneratorKind::LinearGradient => {
+            let (sin, cos) = params.angle_deg.to_radians().sin_cos();
+            // 방향 벡터 기준 프레임 반경 (모서리가 t=0/1이 되도록)
+            let half = (width as f32 * 0.5 * cos.abs() + height as f32 * 0.5 * sin.abs()).max(1.0);
+            fill_by(&mut data, width, height, |x, y| {
+                let dx = x - width as f32 * 0.5;
+                let dy = y - height as f32 * 0.5;
+                let t = ((dx * cos + dy * sin) / half + 1.0) * 0.5;
+                lerp_color(&params.color_a, &params.color_b, t)
+            });
+        }
+        GeneratorKind::RadialGradient => {
+            let radius = ((width * width + height * height) as f32).sqrt() * 0.5;
+            fill_by(&mut data, width, height, |x, y| {
+                let dx = x - width as f32 * 0.5;
+                let dy = y - height as f32 * 0.5;
+                lerp_color(&params.color_a, &params.color_b, (dx * dx + dy * dy).sqrt() / radius)
+            });
+        }
+        GeneratorKind::ColorBars => render_color_bars(&mut data, width, height),
+        GeneratorKind::Checkerboard => {
+            let cell = (params.cell_size * scale).max(1.0);
+            fill_by(&mut data, width, height, |x, y| {
+                let cx = (x / cell) as u32;
+                let cy = (y / cell) as u32;
+                if (cx + cy) & 1 == 0 { params.color_a } else { params.color_b }
+            });
+        }
+        GeneratorKind::Countdown => {
+            render_countdown(&mut data, width, height, params, clip_time_ms, duration_ms);
+        }
+    }
+    data
+}
+
+/// 픽셀 중심 좌표 기준으로 색상 계산
+fn fill_by<F: Fn(f32, f32) -> [u8; 4]>(data: &mut [u8], width: u32, height: u32, color_at: F) {
+    for y in 0..height {
+        for x in 0..width {
+            let i = ((y * width + x) * 4) as usize;
+            data[i..i + 4].copy_from_slice(&color_at(x as f32 + 0.5, y as f32 + 0.5));
+        }
+    }
+}
+
+fn lerp_color(a: &[u8; 4], b: &[u8; 4], t: f32) -> [u8; 4] {
+    let t = t.clamp(0.0, 1.0);
+    std::array::from_fn(|c| (a[c] as f32 + (b[c] as f32 - a[c] as f32) * t + 0.5) as u8)
+}
+
+fn render_color_bars(data: &mut [u8], width: u32, height: u32) {
+    let top_end = height * 2 / 3;
+    let middle_end = height * 3 / 4;
+    let bar_w = width as f32 / 7.0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let bar = ((x as f32 / bar_w) as usize).min(6);
+            let rgb = if y < top_end {
+                BARS_TOP[bar]
+            } else if y < middle_end {
+                BARS_MIDDLE[bar]
+            } else {
+                // 하단 블록 4개는 각각 막대 5/4 폭, 나머지는 검정
+                let block = ((x as f32 / (bar_w * 1.25)) as usize).min(3);
+                BARS_BOTTOM[block]
+            };
+            let i = ((y * width + x) * 4) as usize;
+            data[i..i + 3].copy_from_slice(&rgb);
+            data[i + 3] = 255;
+        }
+    }
+}
+
+/// 카운트다운 리더: 배경 + 현재 초의 시계 방향 스윕 + 원/십자선 + 남은 초 숫자
+fn render_countdown(
+    data: &mut [u8],
+    width: u32,
+    height: u32,
+    params: &GeneratorParams,
+    clip_time_ms: i64,
+    duration_ms: i64,
+) {
+    let remaining_ms = (duration_ms - clip_time_ms).max(0);
+    let number = ((remaining_ms + 999) / 1000).min(99) as u32;
+    // 초 경계에서 0 → 1로 차오르는 스윕
+    let sweep = 1.0 - (remaining_ms % 1000) as f32 / 1000.0;
+    let sweep = if remaining_ms % 1000 == 0 { 0.0 } else { sweep };
+
+    let fg = params.color_a;
+    let bg = params.color_b;
+    let swept = lerp_color(&bg, &fg, 0.25);
+
+    let cx = width as f32 * 0.5;
+    let cy = height as f32 * 0.5;
+    let r_outer = height as f32 * 0.42;
+    let r_inner = height as f32 * 0.36;
+    let line = (height as f32 / 180.0).max(1.0);
+
+    fill_by(data, width, height, |x, y| {
+        let dx = x - cx;
+        let dy = y - cy;
+        let dist = (dx * dx + dy * dy).sqrt();
+
+        // 원 테두리 2개 + 십자선
+        if (dist - r_outer).abs() < line || (dist - r_inner).abs() < line
+            || dx.abs() < line * 0.5 || dy.abs() < line * 0.5
+        {
+            return fg;
+        }
+
+        // 12시 방향부터 시계 방향 각도 (0~1)
+        let angle = (dx.atan2(-dy) / std::f32::consts::TAU).rem_euclid(1.0);
+        if angle < sweep { swept } else { bg }
+    });
+
+    // 남은 초 숫자 (두 자리면 나란히)
+    let digit_h = height as f32 * 0.4;
+    let digit_w = digit_h * 0.5;
+    let gap = digit_w * 0.3;
+    let digits: Vec<u32> = if number >= 10 { vec![number / 10, number % 10] } else { vec![number] };
+    let total_w = digits.len() as f32 * digit_w + (digits.len() - 1) as f32 * gap;
+    let mut left = cx - total_w * 0.5;
+    for d in digits {
+        draw_digit(data, width, height, d, (left, cy - digit_h * 0.5, digit_w, digit_h), fg);
+        left += digit_w + gap;
+    }
+}
+
+/// 7-세그먼트 숫자 그리기 (digit_box: 좌상단 x, y + 너비, 높이)
+fn draw_digit(data: &mut [u8], width: u32, height: u32, digit: u32, digit_box: (f32, f32, f32, f32), color: [u8; 4]) {
+    let (x, y, w, h) = digit_box;
+    let t = h * 0.12;
+    let mid = h * 0.5;
+    // (x0, y0, x1, y1) — 숫자 상자 기준 좌표
+    let segments = [
+        (0.0, 0.0, w, t),                    // a
+        (w - t, 0.0, w, mid),                // b
+        (w - t, mid, w, h),                  // c
+        (0.0, h - t, w, h),                  // d
+        (0.0, mid, t, h),                    // e
+        (0.0, 0.0, t, mid),                  // f
+        (0.0, mid - t * 0.5, w, mid + t * 0.5), // g
+    ];
+    let mask = DIGIT_SEGMENTS[(digit % 10) as usize];
+    for (bit, (x0, y0, x1, y1)) in segments.iter().enumerate() {
+        if mask & (1 << bit) != 0 {
+            fill_rect(data, width, height, (x + x0, y + y0, x + x1, y + y1), color);
+        }
+    }
+}
+
+/// 사각형 채우기 (rect: x0, y0, x1, y1 — 프레임 밖은 잘림)
+fn fill_rect(data: &mut [u8], width: u32, height: u32, rect: (f32, f32, f32, f32), color: [u8; 4]) {
+    let (x0, y0, x1, y1) = rect;
+    let x0 = x0.round().clamp(0.0, width as f32) as u32;
+    let x1 = x1.round().clamp(0.0, width as f32) as u32;
+    let y0 = y0.round().clamp(0.0, height as f32) as u32;
+    let y1 = y1.round().clamp(0.0, height as f32) as u32;
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let i = ((y * width + x) * 4) as usize;
+            data[i..i + 4].copy_from_slice(&color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn px(data: &[u8], w: u32, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * w + x) * 4) as usize;
+        [data[i], data[i + 1], data[i + 2], data[i + 3]]
+    }
+
+    #[test]
+    fn test_solid_color() {
+        let mut p = GeneratorParams::new(GeneratorKind::SolidColor);
+        p.color_a = [10, 20, 30, 255];
+        let out = render_generator(&p, 4, 2, 1.0, 0, 1000);
+        assert!(out.chunks_exact(4).all(|c| c == [10, 20, 30, 255]));
+    }
+
+    #[test]
+    fn test_linear_gradient_direction() {
+        let p = GeneratorParams::new(GeneratorKind::LinearGradient);
+        let out = render_generator(&p, 100, 10, 1.0, 0, 1000);
+        // 좌 → 우: 검정 → 흰색
+        assert!(px(&out, 100, 0, 5)[0] < 10);
+        assert!(px(&out, 100, 99, 5)[0] > 245);
+
+        let mut p = p;
+        p.angle_deg = 90.0;
+        let out = render_generator(&p, 10, 100, 1.0, 0, 1000);
+        assert!(px(&out, 10, 5, 0)[0] < 10);
+        assert!(px(&out, 10, 5, 99)[0] > 245);
+    }
+
+    #[test]
+    fn test_checkerboard_cells_scale() {
+        let p = GeneratorParams { cell_size: 4.0, ..GeneratorParams::new(GeneratorKind::Checkerboard) };
+        // 출력이 타임라인의 절반 → 칸 크기 2px
+        let out = render_generator(&p, 8, 8, 0.5, 0, 1000);
+        assert_eq!(px(&out, 8, 0, 0), [0, 0, 0, 255]);
+        assert_eq!(px(&out, 8, 1, 0), [0, 0, 0, 255]);
+        assert_eq!(px(&out, 8, 2, 0), [255, 255, 255, 255]);
+        assert_eq!(px(&out, 8, 2, 2), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_color_bars() {
+        let out = render_generator(&GeneratorParams::new(GeneratorKind::ColorBars), 70, 40, 1.0, 0, 1000);
+        assert_eq!(px(&out, 70, 5, 5), [191, 191, 191, 255]);
+        assert_eq!(px(&out, 70, 15, 5), [191, 191, 0, 255]);
+        assert_eq!(px(&out, 70, 65, 5), [0, 0, 191, 255]);
+        assert_eq!(px(&out, 70, 5, 28), [0, 0, 191, 255]);
+        assert_eq!(px(&out, 70, 69, 39), [16, 16, 16, 255]);
+    }
+
+    #[test]
+    fn test_countdown_sweep_and_digit() {
+        let p = GeneratorParams::new(GeneratorKind::Countdown);
+        let (w, h) = (320, 180);
+
+        // 남은 3초 정각: 스윕 없음 → 좌상단 배경색
+        let out = render_generator(&p, w, h, 1.0, 2000, 5000);
+        assert_eq!(px(&out, w, 100, 5), p.color_b);
+
+        // 남은 2.5초: 스윕 절반 → 12시~6시 (오른쪽) 영역만 어두움
+        let out = render_generator(&p, w, h, 1.0, 2500, 5000);
+        assert_ne!(px(&out, w, 280, 20), p.color_b);
+        assert_eq!(px(&out, w, 40, 20), p.color_b);
+
+        // 숫자 "3": 상단 세그먼트 (a) 위치는 전경색
+        let top_y = (h as f32 * 0.5 - h as f32 * 0.2 + 2.0) as u32;
+        assert_eq!(px(&out, w, w / 2 + 5, top_y), p.color_a);
+    }
+}
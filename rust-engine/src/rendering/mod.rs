@@ -5,6 +5,9 @@ pub mod renderer;
 pub mod playback_engine;
 pub mod effects;
 pub mod transitions;
+pub mod compositor;
+pub mod transform;
+pub mod generator;
 
 pub use frame_queue::FrameQueue;
 pub use renderer::{Renderer, RenderedFrame};
@@ -1,12 +1,17 @@
 // 렌더링 엔진 - Timeline을 실제 프레임으로 렌더링
 // 아키텍처: FrameCache + DecodeResult 기반 안전 렌더링
 
-use crate::timeline::{Timeline, VideoClip};
+use crate::timeline::{Timeline, VideoClip, ClipType, BlendMode, KeyframeProperty};
 use crate::timeline::track::TransitionInfo;
 use crate::ffmpeg::{Decoder, DecodeResult};
+use crate::ffmpeg::image::load_image;
 use crate::rendering::effects::{EffectParams, apply_effects};
 use crate::rendering::transitions::apply_transition;
-use crate::subtitle::overlay::{yuv420p_to_rgba, rgba_to_yuv420p};
+use crate::rendering::compositor::{composite_layer, is_opaque};
+use crate::rendering::transform::apply_transform;
+use crate::rendering::generator::render_generator;
+use crate::subtitle::overlay::{SubtitleOverlay, SubtitleOverlayList, blend_overlay_rgba, yuv420p_to_rgba, rgba_to_yuv420p};
+use crate::subtitle::text::{TextRenderer, overlay_to_canvas};
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 
@@ -108,6 +113,9 @@ impl FrameCache {
     }
 }
 
+/// 이미지/제너레이터 캐시 최대 개수 (초과 시 전체 비움)
+const MAX_IMAGE_CACHE: usize = 32;
+
 // ============================================================
 // 렌더링된 프레임
 // ============================================================
@@ -128,11 +136,25 @@ pub struct RenderedFrame {
 // 렌더러
 // ============================================================
 
+/// 리샘플링한 자막 오버레이 캐시 (목록, 출력 해상도, 인덱스별 오버레이)
+type ScaledSubtitles = (Arc<SubtitleOverlayList>, (u32, u32), HashMap<usize, SubtitleOverlay>);
+
 /// 비디오 렌더러 (캐시 + DecodeResult 기반)
 pub struct Renderer {
     timeline: Arc<Mutex<Timeline>>,
     decoder_cache: HashMap<String, Decoder>,
     frame_cache: FrameCache,
+    /// 이미지/정적 제너레이터 클립 캐시 (1회 생성 후 재사용, 이펙트/트랜스폼 적용 전 RGBA)
+    image_cache: HashMap<String, RenderedFrame>,
+    /// 텍스트 클립 래스터라이저 (폰트 + 렌더링 결과 캐시)
+    text_renderer: TextRenderer,
+    /// 번인 자막 (매 프레임 Timeline에서 갱신, subtitle_override가 있으면 그쪽 우선)
+    subtitles: Option<Arc<SubtitleOverlayList>>,
+    /// Export 전용 자막 목록 (exporter_start_v2/v3로 전달된 목록)
+    subtitle_override: Option<Arc<SubtitleOverlayList>>,
+    /// 출력 해상도로 리샘플링한 오버레이 캐시 (key: 목록 + 출력 해상도, 값: 인덱스별 오버레이)
+    /// 목록 Arc를 보관 → 교체된 목록의 메모리가 새 목록에 재사용되어 키가 겹치는 일 없음
+    scaled_subtitles: Option<ScaledSubtitles>,
     /// 마지막 성공 렌더링 프레임 (fallback용)
     last_rendered_frame: Option<RenderedFrame>,
     /// 재생 모드: true일 때 forward_threshold를 5초로 올려 seek 대신 forward decode
@@ -140,8 +162,14 @@ pub struct Renderer {
     playback_mode: bool,
     /// Export용 출력 해상도 (None이면 프리뷰 960x540)
     export_resolution: Option<(u32, u32)>,
+    /// Export 합성 결과를 YUV420P로 줄이지 않고 RGBA로 전달 (4:2:2/4:4:4 출력 시 크로마 보존)
+    export_rgba: bool,
+    /// 알파 채널 Export: 빈 영역을 검정 대신 투명(alpha=0)으로 유지 (export_rgba 포함)
+    export_alpha: bool,
     /// 클립별 이펙트 파라미터
     clip_effects: HashMap<u64, EffectParams>,
+    /// 출력 해상도 / 타임라인 해상도 (트랜스폼 position 단위 변환, 매 프레임 갱신)
+    canvas_scale: (f32, f32),
     /// 직전 프레임 렌더 소요 시간 (ms) — 적응형 프레임 스킵 판정용
     last_render_elapsed_ms: u64,
     /// 진단 카운터 (매 30프레임마다 출력)
@@ -154,6 +182,31 @@ pub struct Renderer {
     diag_error: u64,
     diag_transition: u64,
     diag_transition_skip: u64,
+    diag_composite: u64,
+}
+
+/// 트랙 1개가 기여하는 렌더 레이어 (timeline lock 안에서 clone)
+enum RenderLayer {
+    /// 단일 클립 + 원본 소스 시간
+    Clip(Box<VideoClip>, i64),
+    /// 같은 트랙 내 두 클립이 겹치는 트랜지션 구간
+    Transition(Box<TransitionInfo>),
+}
+
+impl RenderLayer {
+    /// 레이어 합성 방식 (블렌드 모드, 불투명도) — Opacity 키프레임 반영
+    /// 트랜지션은 incoming 클립의 블렌드 모드 + progress 기준 불투명도 보간
+    fn blend(&self, timestamp_ms: i64) -> (BlendMode, f32) {
+        match self {
+            RenderLayer::Clip(clip, _) => (clip.blend_mode, clip.opacity_at(timestamp_ms)),
+            RenderLayer::Transition(info) => {
+                let p = info.progress as f32;
+                let opacity = info.outgoing.opacity_at(timestamp_ms) * (1.0 - p)
+                    + info.incoming.opacity_at(timestamp_ms) * p;
+                (info.incoming.blend_mode, opacity)
+            }
+        }
+    }
 }
 
 /// 검은색 프레임 생성 (기본 960x540, Export 시 지정 해상도)
@@ -190,6 +243,21 @@ fn black_frame_yuv(width: u32, height: u32, timestamp_ms: i64) -> RenderedFrame
     }
 }
 
+/// 합성 베이스용 불투명 검은색 RGBA 프레임 (alpha=255)
+fn opaque_black_frame(width: u32, height: u32, timestamp_ms: i64) -> RenderedFrame {
+    let mut data = vec![0u8; (width * height * 4) as usize];
+    for px in data.chunks_exact_mut(4) {
+        px[3] = 255;
+    }
+    RenderedFrame {
+        width,
+        height,
+        data,
+        timestamp_ms,
+        is_yuv: false,
+    }
+}
+
 impl Renderer {
     /// 새 렌더러 생성 (프리뷰용)
     pub fn new(timeline: Arc<Mutex<Timeline>>) -> Self {
@@ -198,10 +266,18 @@ impl Renderer {
             decoder_cache: HashMap::new(),
             // 60프레임 캐시 (~120MB at 960x540 RGBA)
             frame_cache: FrameCache::new(60, 200 * 1024 * 1024),
+            image_cache: HashMap::new(),
+            text_renderer: TextRenderer::new(),
+            subtitles: None,
+            subtitle_override: None,
+            scaled_subtitles: None,
             last_rendered_frame: None,
             playback_mode: false,
             export_resolution: None,
+            export_rgba: false,
+            export_alpha: false,
             clip_effects: HashMap::new(),
+            canvas_scale: (1.0, 1.0),
             last_render_elapsed_ms: 0,
             diag_total: 0,
             diag_cache_hit: 0,
@@ -212,6 +288,7 @@ impl Renderer {
             diag_error: 0,
             diag_transition: 0,
             diag_transition_skip: 0,
+            diag_composite: 0,
         }
     }
 
@@ -225,10 +302,18 @@ impl Renderer {
             decoder_cache: HashMap::new(),
             // Export: 캐시 최소 (순차 인코딩이라 재사용 거의 없음)
             frame_cache: FrameCache::new(5, 50 * 1024 * 1024),
+            image_cache: HashMap::new(),
+            text_renderer: TextRenderer::new(),
+            subtitles: None,
+            subtitle_override: None,
+            scaled_subtitles: None,
             last_rendered_frame: None,
             playback_mode: true, // forward decode 모드 (순차 접근)
             export_resolution: Some((width, height)),
+            export_rgba: false,
+            export_alpha: false,
             clip_effects: HashMap::new(),
+            canvas_scale: (1.0, 1.0),
             last_render_elapsed_ms: 0,
             diag_total: 0,
             diag_cache_hit: 0,
@@ -239,6 +324,7 @@ impl Renderer {
             diag_error: 0,
             diag_transition: 0,
             diag_transition_skip: 0,
+            diag_composite: 0,
         }
     }
 
@@ -263,6 +349,87 @@ impl Renderer {
         }
     }
 
+    /// Export 합성 프레임을 RGBA로 유지 (4:2:0이 아닌 출력 포맷용, 기본 false = YUV420P)
+    pub fn set_export_rgba(&mut self, enabled: bool) {
+        self.export_rgba = enabled;
+    }
+
+    /// 알파 채널 Export (투명 배경 유지, 합성 결과는 항상 RGBA)
+    pub fn set_export_alpha(&mut self, enabled: bool) {
+        self.export_alpha = enabled;
+        if enabled {
+            self.export_rgba = true;
+        }
+    }
+
+    /// Export 빈 프레임 (일반: 검은 YUV420P, RGBA Export: 불투명 검정, 알파 Export: 투명 RGBA)
+    fn export_blank_frame(&self, width: u32, height: u32, timestamp_ms: i64) -> RenderedFrame {
+        if self.export_alpha {
+            black_frame_with_size(width, height, timestamp_ms)
+        } else if self.export_rgba {
+            opaque_black_frame(width, height, timestamp_ms)
+        } else {
+            black_frame_yuv(width, height, timestamp_ms)
+        }
+    }
+
+    /// Timeline 자막 대신 사용할 자막 목록 지정 (Export 전용, None이면 Timeline 자막 사용)
+    pub fn set_subtitle_override(&mut self, subtitles: Option<Arc<SubtitleOverlayList>>) {
+        self.subtitle_override = subtitles;
+    }
+
+    /// 현재 적용할 자막 목록
+    fn active_subtitles(&self) -> Option<Arc<SubtitleOverlayList>> {
+        self.subtitle_override.clone().or_else(|| self.subtitles.clone())
+    }
+
+    /// 렌더링 결과 위에 활성 자막 전체를 번인 (프리뷰/Export 공통 후처리)
+    /// last_rendered_frame은 자막 합성 전 프레임이므로 fallback 재사용 시 이중 합성되지 않음
+    fn burn_subtitles(&mut self, mut frame: RenderedFrame) -> RenderedFrame {
+        let list = match self.active_subtitles() {
+            Some(list) if list.has_active(frame.timestamp_ms) => list,
+            _ => return frame,
+        };
+
+        let (w, h) = (frame.width, frame.height);
+        let mut rgba = if frame.is_yuv {
+            yuv420p_to_rgba(&frame.data, w, h)
+        } else {
+            std::mem::take(&mut frame.data)
+        };
+
+        // 비트맵 기준 해상도가 출력과 다르면 리샘플링 (목록/해상도가 바뀌면 캐시 무효화)
+        let scale = list.frame_size
+            .filter(|&(rw, rh)| rw > 0 && rh > 0 && (rw, rh) != (w, h))
+            .map(|(rw, rh)| (w as f32 / rw as f32, h as f32 / rh as f32));
+        let scaled_cache = match &mut self.scaled_subtitles {
+            Some((cached, size, overlays)) if Arc::ptr_eq(cached, &list) && *size == (w, h) => overlays,
+            slot => &mut slot.insert((list.clone(), (w, h), HashMap::new())).2,
+        };
+
+        for (index, overlay) in list.overlays.iter().enumerate() {
+            if !overlay.is_active(frame.timestamp_ms) {
+                continue;
+            }
+            match scale {
+                Some((sx, sy)) => {
+                    let scaled = scaled_cache
+                        .entry(index)
+                        .or_insert_with(|| overlay.scaled(sx, sy));
+                    blend_overlay_rgba(&mut rgba, w, h, scaled);
+                }
+                None => blend_overlay_rgba(&mut rgba, w, h, overlay),
+            }
+        }
+
+        frame.data = if frame.is_yuv {
+            rgba_to_yuv420p(&rgba, w, h)
+        } else {
+            rgba
+        };
+        frame
+    }
+
     /// 프리뷰 시 proxy, Export 시 원본 경로 반환
     fn video_path_for_decode(&self, clip: &VideoClip) -> std::path::PathBuf {
         // Export 모드: 항상 원본 (최종 품질 보장)
@@ -278,73 +445,253 @@ impl Renderer {
         clip.file_path.clone()
     }
 
+    /// 디코더/프레임 캐시 키 — 같은 파일도 fit_mode가 다르면 출력이 다르므로 분리
+    /// 이미지 시퀀스는 fps에 따라 프레임 타이밍이 달라지므로 fps도 포함
+    fn cache_key(decode_path: &std::path::Path, clip: &VideoClip) -> String {
+        match clip.sequence_fps {
+            Some(fps) => format!("{}#fit{}#fps{}", decode_path.to_string_lossy(), clip.fit_mode as u32, fps),
+            None => format!("{}#fit{}", decode_path.to_string_lossy(), clip.fit_mode as u32),
+        }
+    }
+
     /// 단일 클립 디코딩 + 이펙트 적용 (RGBA 반환, 트랜지션 블렌딩 전처리용)
     fn decode_and_render_clip(&mut self, clip: &VideoClip, source_time_ms: i64, timestamp_ms: i64) -> Option<RenderedFrame> {
+        match clip.clip_type {
+            ClipType::Image => return self.render_image_clip(clip, timestamp_ms),
+            ClipType::Generator => return self.render_generator_clip(clip, timestamp_ms),
+            ClipType::Text => return self.render_text_clip(clip, timestamp_ms),
+            _ => {}
+        }
+
         let decode_path = self.video_path_for_decode(clip);
-        let file_path = decode_path.to_string_lossy().to_string();
+        let file_path = Self::cache_key(&decode_path, clip);
+
+        // 캐시 조회 → 없으면 디코딩 (캐시에는 디코딩 원본만 저장 — 키가 소스 기준이라
+        // 같은 소스를 쓰는 다른 클립/다른 키프레임 값과 공유됨, 이펙트/트랜스폼은 조회 후 적용)
+        let mut rendered = match self.frame_cache.get(&file_path, source_time_ms).cloned() {
+            // 단일 클립 직접 경로가 저장한 Export YUV 프레임 → 블렌딩용 RGBA로 변환
+            Some(frame) if frame.is_yuv => RenderedFrame {
+                data: yuv420p_to_rgba(&frame.data, frame.width, frame.height),
+                is_yuv: false,
+                ..frame
+            },
+            Some(frame) => frame,
+            None => {
+                let result = self.decode_clip_frame(clip, source_time_ms);
+                match result {
+                    Ok(DecodeResult::Frame(frame)) | Ok(DecodeResult::EndOfStream(frame)) => {
+                        let is_yuv = frame.format == crate::ffmpeg::PixelFormat::YUV420P;
+                        let rendered = RenderedFrame {
+                            width: frame.width,
+                            height: frame.height,
+                            data: if is_yuv {
+                                yuv420p_to_rgba(&frame.data, frame.width, frame.height)
+                            } else {
+                                frame.data
+                            },
+                            timestamp_ms,
+                            is_yuv: false, // 항상 RGBA (블렌딩용)
+                        };
+                        if !self.playback_mode {
+                            self.frame_cache.put(file_path, source_time_ms, rendered.clone());
+                        }
+                        rendered
+                    }
+                    _ => return None,
+                }
+            }
+        };
+        rendered.timestamp_ms = timestamp_ms;
 
-        // 캐시 조회
-        if let Some(frame) = self.frame_cache.get(&file_path, source_time_ms).cloned() {
-            return Some(frame);
+        // 이펙트 (정적 값 + 키프레임) — 클립/시간마다 다르므로 캐시 이후 적용
+        if let Some(params) = self.effect_params_at(clip, timestamp_ms) {
+            apply_effects(&mut rendered.data, rendered.width, rendered.height, &params);
         }
 
-        // 디코딩
-        let result = self.decode_clip_frame(clip, source_time_ms);
-        match result {
-            Ok(DecodeResult::Frame(frame)) | Ok(DecodeResult::EndOfStream(frame)) => {
-                let is_yuv = frame.format == crate::ffmpeg::PixelFormat::YUV420P;
-                let mut rendered = RenderedFrame {
+        // 트랜스폼 (위치/크기/회전/크롭) — 키프레임으로 매 프레임 달라질 수 있으므로 캐시 이후 적용
+        let transform = clip.transform_at(timestamp_ms);
+        if !transform.is_identity() {
+            rendered.data = apply_transform(
+                &rendered.data,
+                rendered.width,
+                rendered.height,
+                &transform,
+                self.canvas_scale,
+            );
+        }
+
+        Some(rendered)
+    }
+
+    /// 이미지 클립 렌더링 (RGBA, 알파 유지)
+    /// 디코딩은 파일당 1회 → image_cache 재사용, 이펙트/트랜스폼은 키프레임 때문에 매 프레임 적용
+    fn render_image_clip(&mut self, clip: &VideoClip, timestamp_ms: i64) -> Option<RenderedFrame> {
+        let key = Self::cache_key(&clip.file_path, clip);
+
+        let mut rendered = match self.image_cache.get(&key) {
+            Some(frame) => {
+                self.diag_cache_hit += 1;
+                frame.clone()
+            }
+            None => {
+                let (width, height) = self.export_resolution.unwrap_or((960, 540));
+                let high_quality = self.export_resolution.is_some();
+                let frame = match load_image(&clip.file_path, width, height, clip.fit_mode, high_quality) {
+                    Ok(frame) => frame,
+                    Err(e) => {
+                        eprintln!("Image load error {:?}: {}", clip.file_path, e);
+                        self.diag_error += 1;
+                        return None;
+                    }
+                };
+                self.diag_decoded += 1;
+
+                let rendered = RenderedFrame {
                     width: frame.width,
                     height: frame.height,
-                    data: if is_yuv {
-                        yuv420p_to_rgba(&frame.data, frame.width, frame.height)
-                    } else {
-                        frame.data
-                    },
+                    data: frame.data,
                     timestamp_ms,
-                    is_yuv: false, // 항상 RGBA (블렌딩용)
+                    is_yuv: false,
                 };
+                // 무제한 증가 방지 (이미지 1장 = 960x540 기준 ~2MB)
+                if self.image_cache.len() >= MAX_IMAGE_CACHE {
+                    self.image_cache.clear();
+                }
+                self.image_cache.insert(key, rendered.clone());
+                rendered
+            }
+        };
+        rendered.timestamp_ms = timestamp_ms;
 
-                // 이펙트 적용
-                if let Some(params) = self.clip_effects.get(&clip.id) {
-                    if !params.is_default() {
-                        apply_effects(&mut rendered.data, rendered.width, rendered.height, params);
+        self.apply_still_stages(clip, &mut rendered, timestamp_ms);
+        Some(rendered)
+    }
+
+    /// 제너레이터 클립 렌더링 (RGBA)
+    /// 정적 패턴은 image_cache에 1회 생성 후 재사용, 카운트다운 등 애니메이션은 매 프레임 생성
+    fn render_generator_clip(&mut self, clip: &VideoClip, timestamp_ms: i64) -> Option<RenderedFrame> {
+        let params = clip.generator?;
+        let (width, height) = self.export_resolution.unwrap_or((960, 540));
+        let clip_time_ms = timestamp_ms - clip.start_time_ms;
+
+        let mut rendered = if params.is_animated() {
+            RenderedFrame {
+                width,
+                height,
+                data: render_generator(&params, width, height, self.canvas_scale.1, clip_time_ms, clip.duration_ms),
+                timestamp_ms,
+                is_yuv: false,
+            }
+        } else {
+            // 파라미터 + 출력 배율이 같으면 동일 프레임
+            let key = format!("generator#{:?}#{}", params, self.canvas_scale.1);
+            match self.image_cache.get(&key) {
+                Some(frame) => frame.clone(),
+                None => {
+                    let frame = RenderedFrame {
+                        width,
+                        height,
+                        data: render_generator(&params, width, height, self.canvas_scale.1, 0, clip.duration_ms),
+                        timestamp_ms,
+                        is_yuv: false,
+                    };
+                    if self.image_cache.len() >= MAX_IMAGE_CACHE {
+                        self.image_cache.clear();
                     }
+                    self.image_cache.insert(key, frame.clone());
+                    frame
                 }
+            }
+        };
+        rendered.timestamp_ms = timestamp_ms;
 
-                if !self.playback_mode {
-                    self.frame_cache.put(file_path, source_time_ms, rendered.clone());
-                }
-                Some(rendered)
+        self.apply_still_stages(clip, &mut rendered, timestamp_ms);
+        Some(rendered)
+    }
+
+    /// 텍스트 클립 렌더링 (투명 캔버스 위 텍스트, RGBA)
+    /// 래스터라이즈 결과는 TextRenderer가 내용/스타일/해상도 기준으로 캐시
+    fn render_text_clip(&mut self, clip: &VideoClip, timestamp_ms: i64) -> Option<RenderedFrame> {
+        let layer = clip.text.as_ref()?;
+        let (width, height) = self.export_resolution.unwrap_or((960, 540));
+
+        let overlay = match self.text_renderer.render(&layer.text, &layer.style, width, height, self.canvas_scale.1) {
+            Ok(overlay) => overlay,
+            Err(e) => {
+                eprintln!("Text render error (clip {}): {}", clip.id, e);
+                self.diag_error += 1;
+                return None;
             }
-            _ => None,
+        };
+
+        let mut rendered = RenderedFrame {
+            width,
+            height,
+            data: overlay_to_canvas(&overlay, width, height),
+            timestamp_ms,
+            is_yuv: false,
+        };
+        self.apply_still_stages(clip, &mut rendered, timestamp_ms);
+        Some(rendered)
+    }
+
+    /// 이미지/제너레이터/텍스트 공통 후처리: 이펙트 + 트랜스폼 (키프레임 반영, 캐시 이후 매 프레임)
+    fn apply_still_stages(&self, clip: &VideoClip, rendered: &mut RenderedFrame, timestamp_ms: i64) {
+        if let Some(params) = self.effect_params_at(clip, timestamp_ms) {
+            apply_effects(&mut rendered.data, rendered.width, rendered.height, &params);
+        }
+
+        let transform = clip.transform_at(timestamp_ms);
+        if !transform.is_identity() {
+            rendered.data = apply_transform(
+                &rendered.data,
+                rendered.width,
+                rendered.height,
+                &transform,
+                self.canvas_scale,
+            );
         }
     }
 
     /// 특정 시간의 프레임 렌더링 (캐시 + DecodeResult 안전 처리)
+    /// 활성 비디오 트랙마다 레이어 1개 → 트랙 index 순(하단→상단)으로 합성 → 자막 번인
     pub fn render_frame(&mut self, timestamp_ms: i64) -> Result<RenderedFrame, String> {
+        let frame = self.render_layers(timestamp_ms)?;
+        Ok(self.burn_subtitles(frame))
+    }
+
+    /// 자막 합성 전 프레임 렌더링
+    fn render_layers(&mut self, timestamp_ms: i64) -> Result<RenderedFrame, String> {
         self.diag_total += 1;
         let render_start = std::time::Instant::now();
 
-        // Timeline 데이터 복사 (non-blocking lock → 오디오 fill thread와 경합 시 프레임 스킵)
-        // 최적화: 최상위 트랙 클립 1개만 clone (멀티트랙에서 불필요한 clone 제거)
-        let (clips_to_render, transitions) = {
-            let timeline = match self.timeline.try_lock() {
-                Ok(tl) => tl,
-                Err(_) => {
-                    // Timeline busy (오디오 fill thread가 lock 보유 중) → 프레임 스킵
-                    self.diag_skipped += 1;
-                    return Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
-                        match self.export_resolution {
-                            Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
-                            None => black_frame(timestamp_ms),
-                        }
-                    }));
+        // Timeline 데이터 복사 (프리뷰: non-blocking lock → 오디오 fill thread와 경합 시 프레임 스킵)
+        // Export: 프레임 누락 금지 → 병렬 워커 Renderer끼리 경합해도 blocking lock
+        // 트랙별 활성 클립/트랜지션만 clone (전체 Timeline clone 방지)
+        let layers = {
+            let timeline = if self.export_resolution.is_some() {
+                self.timeline.lock().map_err(|e| format!("Timeline lock failed: {}", e))?
+            } else {
+                match self.timeline.try_lock() {
+                    Ok(tl) => tl,
+                    Err(_) => {
+                        // Timeline busy (오디오 fill thread가 lock 보유 중) → 프레임 스킵
+                        self.diag_skipped += 1;
+                        return Ok(self.last_rendered_frame.clone()
+                            .unwrap_or_else(|| black_frame(timestamp_ms)));
+                    }
                 }
             };
 
-            let mut clips: Vec<(crate::timeline::VideoClip, i64)> = Vec::with_capacity(1);
-            let mut transitions: Vec<TransitionInfo> = Vec::new();
+            // 트랜스폼 position(타임라인 픽셀) → 출력 픽셀 배율
+            let (out_w, out_h) = self.export_resolution.unwrap_or((960, 540));
+            self.canvas_scale = (
+                out_w as f32 / timeline.width.max(1) as f32,
+                out_h as f32 / timeline.height.max(1) as f32,
+            );
+            self.subtitles = timeline.subtitles.clone();
+
+            let mut layers: Vec<(usize, RenderLayer)> = Vec::with_capacity(timeline.video_tracks.len());
 
             for track in &timeline.video_tracks {
                 if !track.enabled {
@@ -353,105 +700,205 @@ impl Renderer {
 
                 // 트랜지션 먼저 확인 (겹치는 2클립)
                 if let Some(info) = track.get_transition_at_time(timestamp_ms) {
-                    transitions.push(info);
-                } else if clips.is_empty() {
-                    // 최상위 트랙 클립만 수집 (이미 찾았으면 건너뜀)
-                    if let Some(clip) = track.get_clip_at_time(timestamp_ms) {
-                        if let Some(source_time_ms) = clip.timeline_to_source_time(timestamp_ms) {
-                            clips.push((clip.clone(), source_time_ms));
-                        }
+                    layers.push((track.index, RenderLayer::Transition(Box::new(info))));
+                } else if let Some(clip) = track.get_clip_at_time(timestamp_ms) {
+                    if let Some(source_time_ms) = clip.timeline_to_source_time(timestamp_ms) {
+                        layers.push((track.index, RenderLayer::Clip(Box::new(clip.clone()), source_time_ms)));
                     }
                 }
             }
 
-            (clips, transitions)
+            // 트랙 인덱스 순으로 정렬 (하단부터)
+            layers.sort_by_key(|(index, _)| *index);
+            layers.into_iter().map(|(_, layer)| layer).collect::<Vec<_>>()
         }; // timeline lock 해제
 
         // 클립도 트랜지션도 없으면 검은색 프레임
-        if clips_to_render.is_empty() && transitions.is_empty() {
+        if layers.is_empty() {
             self.diag_no_clip += 1;
             self.print_diag_if_needed(timestamp_ms);
+            // 자막이 있는 빈 구간은 불투명 검정 (alpha=0 프레임은 PlaybackEngine이 버림)
+            let has_subtitle = self.active_subtitles().is_some_and(|s| s.has_active(timestamp_ms));
             return Ok(match self.export_resolution {
-                Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
+                Some((w, h)) => self.export_blank_frame(w, h, timestamp_ms),
+                None if has_subtitle => opaque_black_frame(960, 540, timestamp_ms),
                 None => black_frame(timestamp_ms),
             });
         }
 
-        // 트랜지션 처리 (있으면 우선)
-        if !transitions.is_empty() {
-            let info = &transitions[0];
-
-            // 적응형 프레임 스킵: 직전 프레임이 28ms 이상 걸렸으면
-            // 트랜지션(2x 디코딩) 대신 이전 프레임 재사용 → 프레임 지연 누적 방지
-            // Export는 스킵하지 않음 (품질 우선)
-            if self.playback_mode && self.export_resolution.is_none()
-                && self.last_render_elapsed_ms > 28
-            {
-                if let Some(ref frame) = self.last_rendered_frame {
-                    let mut skipped = frame.clone();
-                    skipped.timestamp_ms = timestamp_ms;
-                    self.diag_transition_skip += 1;
-                    self.last_render_elapsed_ms = 0; // 다음 프레임은 렌더
-                    self.print_diag_if_needed(timestamp_ms);
-                    return Ok(skipped);
+        // 단일 클립 레이어 + 일반 합성: 기존 직접 경로 (캐시 활용, Export 시 YUV 직행)
+        // Export에서 이펙트가 있는 클립 / RGBA·알파 Export는 합성 경로로 보내 RGBA 프레임을 받음
+        if layers.len() == 1 {
+            if let RenderLayer::Clip(clip, source_time_ms) = &layers[0] {
+                let needs_rgba = self.export_resolution.is_some()
+                    && (self.export_rgba || self.effect_params_at(clip, timestamp_ms).is_some());
+                // 이미지/제너레이터/텍스트는 디코더가 없고 투명 영역이 있을 수 있어 항상 합성 경로
+                if clip.clip_type == ClipType::Video
+                    && clip.is_normal_composite(timestamp_ms)
+                    && clip.transform_at(timestamp_ms).is_identity()
+                    && !needs_rgba
+                {
+                    return self.render_single_clip(clip, *source_time_ms, timestamp_ms, render_start);
                 }
             }
+        }
 
-            let out_src = info.outgoing.timeline_to_source_time(timestamp_ms);
-            let in_src = info.incoming.timeline_to_source_time(timestamp_ms);
-
-            if let (Some(out_src), Some(in_src)) = (out_src, in_src) {
-                // .clone() 제거: info.outgoing/incoming은 TransitionInfo 내 소유 데이터
-                // decode_and_render_clip은 &VideoClip만 필요 → 불필요한 PathBuf clone 제거
-                let out_frame = self.decode_and_render_clip(&info.outgoing, out_src, timestamp_ms);
-                let in_frame = self.decode_and_render_clip(&info.incoming, in_src, timestamp_ms);
-
-                if let (Some(mut out_f), Some(in_f)) = (out_frame, in_frame) {
-                    // RGBA 블렌딩
-                    apply_transition(
-                        &mut out_f.data,
-                        &in_f.data,
-                        out_f.width,
-                        out_f.height,
-                        info.progress,
-                        info.transition_type,
-                    );
-
-                    // Export 시 YUV 변환
-                    if self.export_resolution.is_some() {
-                        let yuv = rgba_to_yuv420p(&out_f.data, out_f.width, out_f.height);
-                        out_f.data = yuv;
-                        out_f.is_yuv = true;
-                    }
+        // 적응형 프레임 스킵: 직전 프레임이 28ms 이상 걸렸으면
+        // 트랜지션/멀티트랙(다중 디코딩) 대신 이전 프레임 재사용 → 프레임 지연 누적 방지
+        // Export는 스킵하지 않음 (품질 우선)
+        if self.playback_mode && self.export_resolution.is_none()
+            && self.last_render_elapsed_ms > 28
+        {
+            if let Some(ref frame) = self.last_rendered_frame {
+                let mut skipped = frame.clone();
+                skipped.timestamp_ms = timestamp_ms;
+                self.diag_transition_skip += 1;
+                self.last_render_elapsed_ms = 0; // 다음 프레임은 렌더
+                self.print_diag_if_needed(timestamp_ms);
+                return Ok(skipped);
+            }
+        }
+
+        self.render_composite(&layers, timestamp_ms, render_start)
+    }
+
+    /// 멀티 레이어 합성 렌더링 (RGBA 블렌딩 → Export 시 YUV 변환)
+    /// 상단 레이어부터 디코딩하고, 불투명 레이어를 만나면 그 아래 트랙은 디코딩 생략
+    fn render_composite(
+        &mut self,
+        layers: &[RenderLayer],
+        timestamp_ms: i64,
+        render_start: std::time::Instant,
+    ) -> Result<RenderedFrame, String> {
+        let (out_w, out_h) = self.export_resolution.unwrap_or((960, 540));
+
+        // 상단 → 하단 순서로 수집 (프레임, 블렌드 모드, 불투명도)
+        let mut rendered_layers: Vec<(RenderedFrame, BlendMode, f32)> = Vec::with_capacity(layers.len());
+        for layer in layers.iter().rev() {
+            let (mode, opacity) = layer.blend(timestamp_ms);
+            if opacity <= 0.0 {
+                continue; // 완전 투명 레이어는 디코딩 생략
+            }
 
-                    // clone+move 패턴: return용 clone 1회, last_rendered에 move
-                    let return_frame = out_f.clone();
-                    self.last_rendered_frame = Some(out_f);
+            let frame = match layer {
+                RenderLayer::Clip(clip, source_time_ms) => {
+                    self.decode_and_render_clip(clip, *source_time_ms, timestamp_ms)
+                }
+                RenderLayer::Transition(info) => {
                     self.diag_transition += 1;
-                    self.last_render_elapsed_ms = render_start.elapsed().as_millis() as u64;
-                    self.print_diag_if_needed(timestamp_ms);
-                    return Ok(return_frame);
+                    self.render_transition_layer(info, timestamp_ms)
                 }
+            };
+
+            let frame = match frame {
+                Some(f) => f,
+                None => continue, // 디코딩 실패 레이어는 건너뜀 (하위 레이어 노출)
+            };
+
+            // 디코더는 항상 출력 해상도로 스케일링하지만, 방어적으로 크기 확인
+            if frame.width != out_w || frame.height != out_h {
+                debug_log!(
+                    "[RENDER] layer size mismatch: {}x{} (expected {}x{})",
+                    frame.width, frame.height, out_w, out_h
+                );
+                continue;
+            }
+
+            // Normal + 100% 불투명 레이어는 하위 트랙을 완전히 가림
+            let covers_below = mode == BlendMode::Normal && opacity >= 1.0 && is_opaque(&frame.data);
+            rendered_layers.push((frame, mode, opacity));
+            if covers_below {
+                break;
             }
         }
 
-        // 단일 클립 렌더링 (기존 경로)
-        if clips_to_render.is_empty() {
-            self.diag_no_clip += 1;
+        if rendered_layers.is_empty() {
+            self.diag_error += 1;
             self.print_diag_if_needed(timestamp_ms);
-            return Ok(match self.export_resolution {
-                Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
-                None => black_frame(timestamp_ms),
-            });
+            return Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
+                match self.export_resolution {
+                    Some((w, h)) => self.export_blank_frame(w, h, timestamp_ms),
+                    None => black_frame(timestamp_ms),
+                }
+            }));
         }
 
-        let (clip, source_time_ms) = &clips_to_render[0];
+        // 하단 레이어부터 합성: 최하단이 하위를 가리면 그대로 베이스, 아니면 검은 배경 위에 합성
+        // 알파 Export는 투명 배경 위에 합성 (타이틀/로어서드의 투명 영역 유지)
+        let mut bottom_up = rendered_layers.into_iter().rev();
+        let (first, first_mode, first_opacity) = bottom_up.next().expect("rendered_layers is not empty");
+        let mut out = if first_mode == BlendMode::Normal && first_opacity >= 1.0 && is_opaque(&first.data) {
+            first
+        } else {
+            let mut base = if self.export_alpha {
+                black_frame_with_size(out_w, out_h, timestamp_ms)
+            } else {
+                opaque_black_frame(out_w, out_h, timestamp_ms)
+            };
+            composite_layer(&mut base.data, &first.data, out_w, out_h, first_mode, first_opacity);
+            base
+        };
+        for (layer, mode, opacity) in bottom_up {
+            composite_layer(&mut out.data, &layer.data, out_w, out_h, mode, opacity);
+        }
+        out.timestamp_ms = timestamp_ms;
+
+        // Export 시 YUV 변환 (RGBA 출력 모드면 인코더가 출력 포맷으로 직접 변환)
+        if self.export_resolution.is_some() && !self.export_rgba {
+            out.data = rgba_to_yuv420p(&out.data, out_w, out_h);
+            out.is_yuv = true;
+        }
+
+        // clone+move 패턴: return용 clone 1회, last_rendered에 move
+        let return_frame = out.clone();
+        self.last_rendered_frame = Some(out);
+        self.diag_composite += 1;
+        self.last_render_elapsed_ms = render_start.elapsed().as_millis() as u64;
+        self.print_diag_if_needed(timestamp_ms);
+        Ok(return_frame)
+    }
+
+    /// 트랜지션 레이어 렌더링 (outgoing/incoming 디코딩 후 RGBA 블렌딩)
+    fn render_transition_layer(&mut self, info: &TransitionInfo, timestamp_ms: i64) -> Option<RenderedFrame> {
+        let out_src = info.outgoing.timeline_to_source_time(timestamp_ms)?;
+        let in_src = info.incoming.timeline_to_source_time(timestamp_ms)?;
+
+        // decode_and_render_clip은 &VideoClip만 필요 → 불필요한 PathBuf clone 제거
+        let mut out_f = self.decode_and_render_clip(&info.outgoing, out_src, timestamp_ms)?;
+        let in_f = self.decode_and_render_clip(&info.incoming, in_src, timestamp_ms)?;
+
+        apply_transition(
+            &mut out_f.data,
+            &in_f.data,
+            out_f.width,
+            out_f.height,
+            info.progress,
+            info.transition_type,
+        );
+
+        Some(out_f)
+    }
+
+    /// 단일 클립 렌더링 (레이어가 1개일 때의 직접 경로)
+    fn render_single_clip(
+        &mut self,
+        clip: &VideoClip,
+        source_time_ms: i64,
+        timestamp_ms: i64,
+        render_start: std::time::Instant,
+    ) -> Result<RenderedFrame, String> {
         let decode_path = self.video_path_for_decode(clip);
-        let file_path = decode_path.to_string_lossy().to_string();
+        let file_path = Self::cache_key(&decode_path, clip);
 
         // 1단계: 캐시 조회 (.cloned()로 즉시 소유권 획득 → 가변 참조 해제)
-        if let Some(mut frame) = self.frame_cache.get(&file_path, *source_time_ms).cloned() {
+        // 캐시는 디코딩 원본 → 이 클립의 이펙트는 조회 후 적용
+        if let Some(mut frame) = self.frame_cache.get(&file_path, source_time_ms).cloned() {
             frame.timestamp_ms = timestamp_ms;
+            if !frame.is_yuv {
+                if let Some(params) = self.effect_params_at(clip, timestamp_ms) {
+                    apply_effects(&mut frame.data, frame.width, frame.height, &params);
+                }
+            }
             self.diag_cache_hit += 1;
             self.print_diag_if_needed(timestamp_ms);
             return Ok(frame);
@@ -459,7 +906,7 @@ impl Renderer {
 
         // 2단계: 디코딩
         let decode_start = std::time::Instant::now();
-        let result = self.decode_clip_frame(clip, *source_time_ms);
+        let result = self.decode_clip_frame(clip, source_time_ms);
         let decode_elapsed = decode_start.elapsed().as_millis();
 
         // 처음 10프레임 또는 50ms 이상 걸린 경우 로그
@@ -483,18 +930,16 @@ impl Renderer {
                             timestamp_ms,
                             is_yuv,
                         };
+                        // 캐시 저장 (이펙트 전 원본): 재생 모드에서는 건너뜀 (순차 프레임 = 캐시 히트 없음, clone 2MB 낭비 방지)
+                        if !self.playback_mode {
+                            self.frame_cache.put(file_path, source_time_ms, rendered.clone());
+                        }
                         // 이펙트 적용 (RGBA 프리뷰만, YUV Export는 건너뜀)
                         if !rendered.is_yuv {
-                            if let Some(params) = self.clip_effects.get(&clip.id) {
-                                if !params.is_default() {
-                                    apply_effects(&mut rendered.data, rendered.width, rendered.height, params);
-                                }
+                            if let Some(params) = self.effect_params_at(clip, timestamp_ms) {
+                                apply_effects(&mut rendered.data, rendered.width, rendered.height, &params);
                             }
                         }
-                        // 캐시 저장: 재생 모드에서는 건너뜀 (순차 프레임 = 캐시 히트 없음, clone 2MB 낭비 방지)
-                        if !self.playback_mode {
-                            self.frame_cache.put(file_path, *source_time_ms, rendered.clone());
-                        }
                         // last_rendered: clone 대신 반환 프레임을 clone하고 원본은 move
                         let return_frame = rendered.clone();
                         self.last_rendered_frame = Some(rendered);
@@ -508,7 +953,7 @@ impl Renderer {
                         // 프레임 스킵 → 마지막 렌더링 프레임 반환 (재생 중단 방지)
                         Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
                             match self.export_resolution {
-                                Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
+                                Some((w, h)) => self.export_blank_frame(w, h, timestamp_ms),
                                 None => black_frame(timestamp_ms),
                             }
                         }))
@@ -533,7 +978,7 @@ impl Renderer {
                         self.print_diag_if_needed(timestamp_ms);
                         Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
                             match self.export_resolution {
-                                Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
+                                Some((w, h)) => self.export_blank_frame(w, h, timestamp_ms),
                                 None => black_frame(timestamp_ms),
                             }
                         }))
@@ -547,7 +992,7 @@ impl Renderer {
                 // 에러 시에도 마지막 프레임 반환 (재생 중단 방지)
                 Ok(self.last_rendered_frame.clone().unwrap_or_else(|| {
                     match self.export_resolution {
-                        Some((w, h)) => black_frame_yuv(w, h, timestamp_ms),
+                        Some((w, h)) => self.export_blank_frame(w, h, timestamp_ms),
                         None => black_frame(timestamp_ms),
                     }
                 }))
@@ -559,11 +1004,12 @@ impl Renderer {
     fn print_diag_if_needed(&self, last_ts: i64) {
         if self.diag_total % 30 == 0 {
             debug_log!(
-                "[RENDER DIAG] t={}ms | total={} cache={} decode={} trans={} trans_skip={} eof={} skip={} noclip={} err={} last={}ms",
+                "[RENDER DIAG] t={}ms | total={} cache={} decode={} comp={} trans={} trans_skip={} eof={} skip={} noclip={} err={} last={}ms",
                 last_ts,
                 self.diag_total,
                 self.diag_cache_hit,
                 self.diag_decoded,
+                self.diag_composite,
                 self.diag_transition,
                 self.diag_transition_skip,
                 self.diag_eof,
@@ -579,7 +1025,7 @@ impl Renderer {
     /// 에러 시 디코더 재생성 1회 재시도 (corrupted state 복구)
     fn decode_clip_frame(&mut self, clip: &VideoClip, source_time_ms: i64) -> Result<DecodeResult, String> {
         let decode_path = self.video_path_for_decode(clip);
-        let file_path = decode_path.to_string_lossy().to_string();
+        let file_path = Self::cache_key(&decode_path, clip);
 
         // Error 상태 디코더는 제거 후 재생성 (복구 불가능 상태 탈출)
         if let Some(decoder) = self.decoder_cache.get(&file_path) {
@@ -593,10 +1039,7 @@ impl Renderer {
         let threshold = if self.playback_mode { 5000 } else { 100 };
         if !self.decoder_cache.contains_key(&file_path) {
             // Export: LANCZOS 고품질 (원본), 프리뷰: FAST_BILINEAR (proxy 또는 원본)
-            let mut decoder = match self.export_resolution {
-                Some((w, h)) => Decoder::open_for_export(&decode_path, w, h)?,
-                None => Decoder::open(&decode_path)?,
-            };
+            let mut decoder = self.open_clip_decoder(clip, &decode_path)?;
             decoder.set_forward_threshold(threshold);
             self.decoder_cache.insert(file_path.clone(), decoder);
         }
@@ -610,12 +1053,8 @@ impl Renderer {
                 debug_log!("[DECODER] Decode error at {}ms: {}, recreating decoder", source_time_ms, e);
                 self.decoder_cache.remove(&file_path);
 
-                let mut new_decoder = match self.export_resolution {
-                    Some((w, h)) => Decoder::open_for_export(&decode_path, w, h)
-                        .map_err(|e2| format!("Decoder recreate failed: {}", e2))?,
-                    None => Decoder::open(&decode_path)
-                        .map_err(|e2| format!("Decoder recreate failed: {}", e2))?,
-                };
+                let mut new_decoder = self.open_clip_decoder(clip, &decode_path)
+                    .map_err(|e2| format!("Decoder recreate failed: {}", e2))?;
                 new_decoder.set_forward_threshold(threshold);
                 self.decoder_cache.insert(file_path.clone(), new_decoder);
 
@@ -627,6 +1066,16 @@ impl Renderer {
         }
     }
 
+    /// 클립 소스에 맞는 디코더 생성 (비디오 파일 / 이미지 시퀀스, Export / 프리뷰)
+    fn open_clip_decoder(&self, clip: &VideoClip, decode_path: &std::path::Path) -> Result<Decoder, String> {
+        match (clip.sequence_fps, self.export_resolution) {
+            (Some(fps), Some((w, h))) => Decoder::open_sequence_for_export(decode_path, fps, w, h, clip.fit_mode),
+            (Some(fps), None) => Decoder::open_sequence_with_fit(decode_path, fps, 960, 540, clip.fit_mode),
+            (None, Some((w, h))) => Decoder::open_for_export(decode_path, w, h, clip.fit_mode),
+            (None, None) => Decoder::open_with_fit(decode_path, 960, 540, clip.fit_mode),
+        }
+    }
+
     /// 클립 이펙트 설정 (C# Slider 변경 시 호출)
     pub fn set_clip_effects(&mut self, clip_id: u64, params: EffectParams) {
         if params.is_default() {
@@ -634,19 +1083,61 @@ impl Renderer {
         } else {
             self.clip_effects.insert(clip_id, params);
         }
-        // 캐시 클리어 — 이펙트가 변경되면 캐시된 프레임도 무효화
+        // 프레임 캐시는 이펙트 전 원본이라 무효화 불필요 (다음 렌더링부터 새 값 적용)
+    }
+
+    /// 타임라인 시간의 클립 이펙트 파라미터 (정적 값 위에 키프레임 값 덮어씀)
+    /// 모든 값이 기본값이면 None → 이펙트 연산 건너뜀
+    fn effect_params_at(&self, clip: &VideoClip, timestamp_ms: i64) -> Option<EffectParams> {
+        let mut params = self.clip_effects.get(&clip.id).cloned().unwrap_or_default();
+
+        if !clip.keyframes.is_empty() {
+            let clip_time = (timestamp_ms - clip.start_time_ms) as f64;
+            let animated = [
+                (KeyframeProperty::Brightness, &mut params.brightness),
+                (KeyframeProperty::Contrast, &mut params.contrast),
+                (KeyframeProperty::Saturation, &mut params.saturation),
+                (KeyframeProperty::Temperature, &mut params.temperature),
+            ];
+            for (property, field) in animated {
+                if let Some(v) = clip.keyframes.evaluate(property, clip_time) {
+                    *field = (v as f32).clamp(-1.0, 1.0);
+                }
+            }
+        }
+
+        if params.is_default() { None } else { Some(params) }
+    }
+
+    /// 클립 블렌드 모드 + 불투명도 설정 (Timeline의 VideoClip에 저장 → Export에도 반영)
+    /// 클립을 찾지 못하면 false
+    pub fn set_clip_blend_mode(&mut self, clip_id: u64, blend_mode: BlendMode, opacity: f32) -> bool {
+        let found = match self.timeline.lock() {
+            Ok(mut timeline) => timeline.video_tracks.iter_mut()
+                .find_map(|track| track.get_clip_by_id_mut(clip_id))
+                .map(|clip| {
+                    clip.blend_mode = blend_mode;
+                    clip.opacity = opacity.clamp(0.0, 1.0);
+                })
+                .is_some(),
+            Err(_) => false,
+        };
+        // 캐시 클리어 — 합성 결과가 바뀌므로 이전 프레임 재사용 방지
         self.frame_cache.clear();
+        found
     }
 
     /// 클립 이펙트 제거
     pub fn clear_clip_effects(&mut self, clip_id: u64) {
         self.clip_effects.remove(&clip_id);
-        self.frame_cache.clear();
     }
 
     /// 캐시 클리어 (클립 편집 시 호출)
     pub fn clear_cache(&mut self) {
         self.frame_cache.clear();
+        self.image_cache.clear();
+        self.text_renderer.clear_cache();
+        self.scaled_subtitles = None;
     }
 
     /// 캐시 통계 조회
@@ -719,6 +1210,175 @@ mod tests {
         assert!(frame.data.iter().all(|&b| b == 0));
     }
 
+    #[test]
+    fn test_preview_burns_all_active_subtitles() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let mut list = SubtitleOverlayList::new();
+        list.frame_size = Some((1920, 1080));
+        for (x, color) in [(0, [255u8, 0, 0, 255]), (200, [0, 255, 0, 255])] {
+            list.overlays.push(SubtitleOverlay {
+                start_ms: 0,
+                end_ms: 1000,
+                x,
+                y: 100,
+                width: 20,
+                height: 20,
+                rgba_data: color.repeat(400),
+            });
+        }
+        timeline.lock().unwrap().set_subtitles(Some(list));
+
+        let mut renderer = Renderer::new(timeline);
+        let frame = renderer.render_frame(500).unwrap();
+        let px = |x: usize, y: usize| &frame.data[(y * 960 + x) * 4..(y * 960 + x) * 4 + 4];
+        // 1920x1080 기준 좌표 → 960x540 프리뷰에서 절반 위치
+        assert_eq!(px(5, 55), &[255, 0, 0, 255]);
+        assert_eq!(px(105, 55), &[0, 255, 0, 255]);
+        // 빈 구간이어도 불투명 검정 (PlaybackEngine BLACK 판정 회피)
+        assert_eq!(px(0, 0), &[0, 0, 0, 255]);
+
+        let frame = renderer.render_frame(1500).unwrap();
+        assert!(frame.data.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_replaced_subtitle_list_is_rescaled() {
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let subtitle_list = |color: [u8; 4]| {
+            let mut list = SubtitleOverlayList::new();
+            list.frame_size = Some((1920, 1080));
+            list.overlays.push(SubtitleOverlay {
+                start_ms: 0,
+                end_ms: 1000,
+                x: 0,
+                y: 100,
+                width: 20,
+                height: 20,
+                rgba_data: color.repeat(400),
+            });
+            list
+        };
+
+        let mut renderer = Renderer::new(timeline.clone());
+        timeline.lock().unwrap().set_subtitles(Some(subtitle_list([255, 0, 0, 255])));
+        assert_eq!(&renderer.render_frame(500).unwrap().data[(55 * 960 + 5) * 4..][..4], &[255, 0, 0, 255]);
+
+        // 같은 크기 목록으로 교체 → 이전 목록의 리샘플링 비트맵을 재사용하지 않음
+        timeline.lock().unwrap().set_subtitles(Some(subtitle_list([0, 255, 0, 255])));
+        assert_eq!(&renderer.render_frame(500).unwrap().data[(55 * 960 + 5) * 4..][..4], &[0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn test_export_alpha_keeps_transparency() {
+        use crate::timeline::{GeneratorKind, GeneratorParams};
+
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        {
+            let mut tl = timeline.lock().unwrap();
+            let track_id = tl.add_video_track();
+            let mut params = GeneratorParams::new(GeneratorKind::SolidColor);
+            params.color_a = [255, 0, 0, 128];
+            tl.add_generator_clip(track_id, params, 0, 1000).unwrap();
+        }
+
+        let mut renderer = Renderer::new_for_export(timeline.clone(), 64, 36);
+        renderer.set_export_alpha(true);
+        let frame = renderer.render_frame(500).unwrap();
+        assert!(!frame.is_yuv);
+        assert_eq!(&frame.data[0..4], &[255, 0, 0, 128]);
+
+        // 클립 없는 구간: 투명 RGBA
+        let frame = renderer.render_frame(1500).unwrap();
+        assert!(!frame.is_yuv);
+        assert!(frame.data.iter().all(|&b| b == 0));
+
+        // 일반 Export: 검은 배경에 합성 후 YUV420P
+        let mut renderer = Renderer::new_for_export(timeline, 64, 36);
+        assert!(renderer.render_frame(500).unwrap().is_yuv);
+    }
+
+    #[test]
+    fn test_export_waits_for_timeline_lock() {
+        use crate::timeline::{GeneratorKind, GeneratorParams};
+
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        {
+            let mut tl = timeline.lock().unwrap();
+            let track_id = tl.add_video_track();
+            let mut params = GeneratorParams::new(GeneratorKind::SolidColor);
+            params.color_a = [255, 0, 0, 128];
+            tl.add_generator_clip(track_id, params, 0, 1000).unwrap();
+        }
+
+        // 다른 워커가 Timeline lock을 잡고 있어도 Export는 프레임을 건너뛰지 않음
+        let mut renderer = Renderer::new_for_export(timeline.clone(), 64, 36);
+        renderer.set_export_alpha(true);
+        let guard = timeline.lock().unwrap();
+        let worker = std::thread::spawn(move || renderer.render_frame(500));
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        drop(guard);
+        let frame = worker.join().unwrap().unwrap();
+        assert_eq!(&frame.data[0..4], &[255, 0, 0, 128]);
+    }
+
+    #[test]
+    fn test_frame_cache_keeps_per_clip_effects() {
+        let path = std::env::temp_dir().join(format!("vortex_renderer_cache_{}.mkv", std::process::id()));
+        crate::encoding::encoder::tests::write_test_video(&path, (64, 36), 25.0, 50, [128, 128, 128, 255]).unwrap();
+
+        // 같은 소스를 0ms부터 쓰는 클립 2개 → 소스 시간이 같아 캐시 키도 같음
+        let timeline = Arc::new(Mutex::new(Timeline::new(1920, 1080, 30.0)));
+        let bright_clip = {
+            let mut tl = timeline.lock().unwrap();
+            let track_id = tl.add_video_track();
+            tl.add_video_clip(track_id, path.clone(), 0, 1000, None).unwrap();
+            tl.add_video_clip(track_id, path.clone(), 1000, 1000, None).unwrap()
+        };
+        let mut renderer = Renderer::new(timeline);
+        renderer.set_clip_effects(bright_clip, EffectParams { brightness: 0.5, ..EffectParams::default() });
+
+        let plain = renderer.render_frame(0).unwrap();
+        let bright = renderer.render_frame(1000).unwrap();
+        let plain_again = renderer.render_frame(0).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(renderer.frame_cache.hit_count >= 2);
+        assert!(bright.data[0] > plain.data[0].saturating_add(64));
+        assert_eq!(plain_again.data[0], plain.data[0]);
+    }
+
+    #[test]
+    fn test_export_rgba_single_video_clip() {
+        let path = std::env::temp_dir().join(format!("vortex_renderer_rgba_{}.mkv", std::process::id()));
+        crate::encoding::encoder::tests::write_test_video(&path, (64, 36), 25.0, 25, [128, 128, 128, 255]).unwrap();
+
+        let timeline = Arc::new(Mutex::new(Timeline::new(64, 36, 25.0)));
+        {
+            let mut tl = timeline.lock().unwrap();
+            let track_id = tl.add_video_track();
+            tl.add_video_clip(track_id, path.clone(), 0, 1000, None).unwrap();
+        }
+
+        // 일반 Export: 단일 클립 직접 경로 → YUV420P
+        let mut renderer = Renderer::new_for_export(timeline.clone(), 64, 36);
+        assert!(renderer.render_frame(500).unwrap().is_yuv);
+
+        // RGBA Export (4:4:4/RGB 출력): 단일 클립도 RGBA
+        let mut renderer = Renderer::new_for_export(timeline, 64, 36);
+        renderer.set_export_rgba(true);
+        let frame = renderer.render_frame(500).unwrap();
+        let blank = renderer.render_frame(1500).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert!(!frame.is_yuv);
+        assert_eq!(frame.data.len(), 64 * 36 * 4);
+        assert!(frame.data[0].abs_diff(128) <= 3);
+        assert_eq!(frame.data[3], 255);
+        // 빈 구간: 불투명 검정 RGBA
+        assert!(!blank.is_yuv);
+        assert_eq!(&blank.data[0..4], &[0, 0, 0, 255]);
+    }
+
     #[test]
     fn test_renderer_with_real_video() {
         let video_path = PathBuf::from(r"C:\Users\USER\Videos\드론 대응 2.75인치 로켓 '비궁'으로 유도키트 개발, 사우디 기술협력 추진.mp4");
@@ -739,7 +1399,7 @@ mod tests {
 
         let _clip_id = {
             let mut tl = timeline.lock().unwrap();
-            tl.add_video_clip(track_id, video_path.clone(), 0, 5000)
+            tl.add_video_clip(track_id, video_path.clone(), 0, 5000, None)
                 .expect("Failed to add video clip")
         };
 
@@ -0,0 +1,218 @@
+// 트랜스폼 스테이지 — 클립 위치/크기/회전/크롭을 캔버스에 배치
+// 역매핑(출력 픽셀 → 원본 좌표) + bilinear 샘플링, 캔버스 밖/크롭 영역은 투명
+
+use crate::timeline::ClipTransform;
+
+/// RGBA 프레임에 트랜스폼 적용 → 같은 크기의 새 RGBA 버퍼 (바깥 영역 alpha=0)
+/// canvas_scale: (출력 해상도 / 타임라인 해상도) — position은 타임라인 픽셀 단위이므로 변환 필요
+pub fn apply_transform(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    transform: &ClipTransform,
+    canvas_scale: (f32, f32),
+) -> Vec<u8> {
+    let w = width as f32;
+    let h = height as f32;
+    let mut dst = vec![0u8; (width * height * 4) as usize];
+
+    // 크롭 영역 (원본 픽셀 좌표)
+    let crop_x0 = transform.crop_left.clamp(0.0, 1.0) * w;
+    let crop_y0 = transform.crop_top.clamp(0.0, 1.0) * h;
+    let crop_x1 = (1.0 - transform.crop_right.clamp(0.0, 1.0)) * w;
+    let crop_y1 = (1.0 - transform.crop_bottom.clamp(0.0, 1.0)) * h;
+    if crop_x1 <= crop_x0 || crop_y1 <= crop_y0 {
+        return dst;
+    }
+
+    let sx = transform.scale_x;
+    let sy = transform.scale_y;
+    if sx.abs() < 1e-6 || sy.abs() < 1e-6 {
+        return dst;
+    }
+
+    // 앵커(회전/스케일 기준점)와 앵커가 놓일 캔버스 위치
+    let ax = transform.anchor_x * w;
+    let ay = transform.anchor_y * h;
+    let px = ax + transform.position_x * canvas_scale.0;
+    let py = ay + transform.position_y * canvas_scale.1;
+
+    let (sin, cos) = transform.rotation_deg.to_radians().sin_cos();
+
+    // 정방향: dst = P + R * S * (src - A)
+    let forward = |x: f32, y: f32| -> (f32, f32) {
+        let lx = (x - ax) * sx;
+        let ly = (y - ay) * sy;
+        (px + lx * cos - ly * sin, py + lx * sin + ly * cos)
+    };
+
+    // 크롭 사각형의 출력 bounding box만 순회 (나머지는 투명)
+    let corners = [
+        forward(crop_x0, crop_y0),
+        forward(crop_x1, crop_y0),
+        forward(crop_x0, crop_y1),
+        forward(crop_x1, crop_y1),
+    ];
+    let min_x = corners.iter().map(|c| c.0).fold(f32::MAX, f32::min).floor().max(0.0) as u32;
+    let max_x = corners.iter().map(|c| c.0).fold(f32::MIN, f32::max).ceil().min(w) as u32;
+    let min_y = corners.iter().map(|c| c.1).fold(f32::MAX, f32::min).floor().max(0.0) as u32;
+    let max_y = corners.iter().map(|c| c.1).fold(f32::MIN, f32::max).ceil().min(h) as u32;
+
+    // 역방향: src = A + S^-1 * R^-1 * (dst - P)
+    let inv_sx = 1.0 / sx;
+    let inv_sy = 1.0 / sy;
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            // 픽셀 중심 기준
+            let dx = x as f32 + 0.5 - px;
+            let dy = y as f32 + 0.5 - py;
+            let src_x = ax + (dx * cos + dy * sin) * inv_sx;
+            let src_y = ay + (-dx * sin + dy * cos) * inv_sy;
+
+            if src_x < crop_x0 - 1.0 || src_x > crop_x1 + 1.0
+                || src_y < crop_y0 - 1.0 || src_y > crop_y1 + 1.0
+            {
+                continue;
+            }
+
+            let idx = ((y * width + x) * 4) as usize;
+            sample_bilinear(
+                src, width, height,
+                src_x - 0.5, src_y - 0.5,
+                (crop_x0, crop_y0, crop_x1, crop_y1),
+                &mut dst[idx..idx + 4],
+            );
+        }
+    }
+
+    dst
+}
+
+/// bilinear 샘플링 — 크롭 영역 밖 탭은 투명으로 취급 (가장자리 안티에일리어싱)
+/// 알파 가중 평균으로 투명 탭의 색이 섞여 들어오지 않게 함
+fn sample_bilinear(
+    src: &[u8],
+    width: u32,
+    height: u32,
+    fx: f32,
+    fy: f32,
+    crop: (f32, f32, f32, f32),
+    out: &mut [u8],
+) {
+    let x0 = fx.floor();
+    let y0 = fy.floor();
+    let tx = fx - x0;
+    let ty = fy - y0;
+
+    let mut color = [0.0f32; 3];
+    let mut alpha = 0.0f32;
+
+    for (oy, wy) in [(0.0, 1.0 - ty), (1.0, ty)] {
+        for (ox, wx) in [(0.0, 1.0 - tx), (1.0, tx)] {
+            let weight = wx * wy;
+            if weight <= 0.0 {
+                continue;
+            }
+            let sx = x0 + ox;
+            let sy = y0 + oy;
+            // 탭 픽셀 중심이 크롭 영역 안에 있어야 유효
+            if sx + 0.5 < crop.0 || sx + 0.5 > crop.2 || sy + 0.5 < crop.1 || sy + 0.5 > crop.3 {
+                continue;
+            }
+            if sx < 0.0 || sy < 0.0 || sx >= width as f32 || sy >= height as f32 {
+                continue;
+            }
+            let i = ((sy as u32 * width + sx as u32) * 4) as usize;
+            let a = src[i + 3] as f32 * weight;
+            color[0] += src[i] as f32 * a;
+            color[1] += src[i + 1] as f32 * a;
+            color[2] += src[i + 2] as f32 * a;
+            alpha += a;
+        }
+    }
+
+    if alpha <= 0.0 {
+        return;
+    }
+    out[0] = (color[0] / alpha + 0.5).min(255.0) as u8;
+    out[1] = (color[1] / alpha + 0.5).min(255.0) as u8;
+    out[2] = (color[2] / alpha + 0.5).min(255.0) as u8;
+    out[3] = (alpha + 0.5).min(255.0) as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 좌우 2색 (왼쪽 빨강, 오른쪽 파랑) 테스트 프레임
+    fn split_frame(w: u32, h: u32) -> Vec<u8> {
+        let mut data = vec![0u8; (w * h * 4) as usize];
+        for y in 0..h {
+            for x in 0..w {
+                let i = ((y * w + x) * 4) as usize;
+                if x < w / 2 {
+                    data[i] = 255;
+                } else {
+                    data[i + 2] = 255;
+                }
+                data[i + 3] = 255;
+            }
+        }
+        data
+    }
+
+    fn px(data: &[u8], w: u32, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * w + x) * 4) as usize;
+        [data[i], data[i + 1], data[i + 2], data[i + 3]]
+    }
+
+    #[test]
+    fn test_identity_keeps_frame() {
+        let src = split_frame(8, 4);
+        let out = apply_transform(&src, 8, 4, &ClipTransform::default(), (1.0, 1.0));
+        assert_eq!(out, src);
+    }
+
+    #[test]
+    fn test_half_scale_leaves_transparent_border() {
+        let src = split_frame(16, 16);
+        let t = ClipTransform { scale_x: 0.5, scale_y: 0.5, ..Default::default() };
+        let out = apply_transform(&src, 16, 16, &t, (1.0, 1.0));
+        // 모서리는 투명, 중앙은 불투명
+        assert_eq!(px(&out, 16, 0, 0)[3], 0);
+        assert_eq!(px(&out, 16, 6, 8), [255, 0, 0, 255]);
+        assert_eq!(px(&out, 16, 9, 8), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_position_scaled_to_output() {
+        let src = split_frame(8, 4);
+        // 타임라인 4px 이동, 출력이 타임라인의 절반 → 2px 이동
+        let t = ClipTransform { position_x: 4.0, ..Default::default() };
+        let out = apply_transform(&src, 8, 4, &t, (0.5, 0.5));
+        assert_eq!(px(&out, 8, 0, 0)[3], 0);
+        assert_eq!(px(&out, 8, 1, 0)[3], 0);
+        assert_eq!(px(&out, 8, 2, 0), [255, 0, 0, 255]);
+        assert_eq!(px(&out, 8, 6, 0), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_rotation_180_swaps_sides() {
+        let src = split_frame(8, 4);
+        let t = ClipTransform { rotation_deg: 180.0, ..Default::default() };
+        let out = apply_transform(&src, 8, 4, &t, (1.0, 1.0));
+        assert_eq!(px(&out, 8, 1, 1), [0, 0, 255, 255]);
+        assert_eq!(px(&out, 8, 6, 1), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_crop_makes_area_transparent() {
+        let src = split_frame(8, 4);
+        let t = ClipTransform { crop_left: 0.5, ..Default::default() };
+        let out = apply_transform(&src, 8, 4, &t, (1.0, 1.0));
+        // 왼쪽 절반 잘림 (크롭은 위치 유지)
+        assert_eq!(px(&out, 8, 1, 1)[3], 0);
+        assert_eq!(px(&out, 8, 6, 1), [0, 0, 255, 255]);
+    }
+}
@@ -0,0 +1,398 @@
+// 자막 파일(사이드카) 출력 — SRT / WebVTT / ASS
+// TranscriptSegment·cue 목록 → 줄 길이 맞춰 줄바꿈 + 긴 cue 분할 → 파일 문자열 직렬화
+
+use super::cue::{SubtitleCue, ass_escape, ass_header};
+use std::path::Path;
+
+/// 자막 파일 형식 (FFI u32 매핑)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptionFormat {
+    Srt = 0,
+    WebVtt = 1,
+    Ass = 2,
+}
+
+impl CaptionFormat {
+    pub fn from_u32(v: u32) -> Option<Self> {
+        match v {
+            0 => Some(CaptionFormat::Srt),
+            1 => Some(CaptionFormat::WebVtt),
+            2 => Some(CaptionFormat::Ass),
+            _ => None,
+        }
+    }
+
+    /// 확장자로 형식 추정 (.srt / .vtt / .ass, .ssa)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "srt" => Some(CaptionFormat::Srt),
+            "vtt" => Some(CaptionFormat::WebVtt),
+            "ass" | "ssa" => Some(CaptionFormat::Ass),
+            _ => None,
+        }
+    }
+}
+
+/// 줄바꿈/분할 옵션
+#[derive(Debug, Clone, Copy)]
+pub struct CaptionOptions {
+    /// 한 줄 최대 글자 수 (0이면 줄바꿈 안 함)
+    pub max_line_chars: usize,
+    /// cue 1개 최대 줄 수 (초과 분량은 다음 cue로 분할)
+    pub max_lines: usize,
+    /// cue 1개 최대 표시 시간 (ms, 0이면 분할 안 함)
+    pub max_duration_ms: i64,
+    /// ASS PlayResX/PlayResY
+    pub play_res: (u32, u32),
+}
+
+impl Default for CaptionOptions {
+    fn default() -> Self {
+        Self {
+            max_line_chars: 42,
+            max_lines: 2,
+            max_duration_ms: 7000,
+            play_res: (1920, 1080),
+        }
+    }
+}
+
+/// cue 목록 정리: 시간 순 정렬 → 최대 시간/줄 수 기준 분할 → 줄바꿈
+/// 분할된 cue의 시간은 최대 표시 시간이 있으면 균등, 없으면 글자 수 비례로 나눔
+pub fn prepare_cues(cues: &[SubtitleCue], opts: &CaptionOptions) -> Vec<SubtitleCue> {
+    let mut sorted: Vec<&SubtitleCue> = cues.iter()
+        .filter(|c| c.end_ms > c.start_ms && !c.text.trim().is_empty())
+        .collect();
+    sorted.sort_by_key(|c| (c.start_ms, c.end_ms));
+
+    let mut out = Vec::with_capacity(sorted.len());
+    for cue in sorted {
+        let words = split_words(&cue.text, opts.max_line_chars);
+
+        // 최대 표시 시간 기준 조각 수 → 조각마다 줄바꿈 → 줄 수 초과 시 추가 분할
+        let duration = cue.end_ms - cue.start_ms;
+        let pieces = if opts.max_duration_ms > 0 {
+            ((duration + opts.max_duration_ms - 1) / opts.max_duration_ms).max(1) as usize
+        } else {
+            1
+        };
+
+        let mut chunks: Vec<Vec<String>> = Vec::new();
+        for piece in split_balanced(&words, pieces) {
+            let lines = wrap_words(piece, opts.max_line_chars);
+            for group in lines.chunks(opts.max_lines.max(1)) {
+                chunks.push(group.to_vec());
+            }
+        }
+
+        // 시간 배분 (마지막 조각은 원래 끝 시간에 맞춤)
+        // 최대 표시 시간: 조각마다 균등 (조각 수 ≥ pieces → 조각 길이 ≤ 최대 시간)
+        let weights: Vec<usize> = if opts.max_duration_ms > 0 {
+            vec![1; chunks.len()]
+        } else {
+            chunks.iter()
+                .map(|lines| lines.iter().map(|l| l.chars().count()).sum::<usize>().max(1))
+                .collect()
+        };
+        let total: usize = weights.iter().sum();
+        let mut acc = 0usize;
+        let mut start = cue.start_ms;
+        for (lines, weight) in chunks.iter().zip(&weights) {
+            acc += weight;
+            let end = cue.start_ms + (duration as i128 * acc as i128 / total as i128) as i64;
+            // 단어가 모자라 조각 수가 pieces보다 적으면 최대 시간까지만 표시
+            let shown_end = if opts.max_duration_ms > 0 {
+                end.min(start + opts.max_duration_ms)
+            } else {
+                end
+            };
+            if shown_end > start {
+                out.push(SubtitleCue::new(start, shown_end, lines.join("\n")));
+            }
+            start = end;
+        }
+    }
+    out
+}
+
+/// cue 원래 줄바꿈 토큰 (wrap_words에서 강제 줄 끝)
+const LINE_BREAK: &str = "\n";
+
+/// 공백 기준 단어 분리 (max_chars보다 긴 단어는 글자 단위로 자름)
+/// cue의 줄바꿈은 줄 사이에 LINE_BREAK 토큰으로 유지
+fn split_words(text: &str, max_chars: usize) -> Vec<String> {
+    let mut words = Vec::new();
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        if !words.is_empty() {
+            words.push(LINE_BREAK.to_string());
+        }
+        for word in line.split_whitespace() {
+            let chars: Vec<char> = word.chars().collect();
+            if max_chars == 0 || chars.len() <= max_chars {
+                words.push(word.to_string());
+            } else {
+                words.extend(chars.chunks(max_chars).map(|c| c.iter().collect::<String>()));
+            }
+        }
+    }
+    words
+}
+
+/// 단어 목록을 글자 수가 비슷한 n개의 연속 구간으로 나눔 (단어 뒤에서만 자름)
+fn split_balanced(words: &[String], n: usize) -> Vec<&[String]> {
+    let word_count = words.iter().filter(|w| *w != LINE_BREAK).count();
+    let n = n.clamp(1, word_count.max(1));
+    if n == 1 {
+        return vec![words];
+    }
+
+    let total: usize = words.iter().map(|w| w.chars().count() + 1).sum();
+    let mut parts = Vec::with_capacity(n);
+    let mut begin = 0;
+    let mut acc = 0;
+    let mut words_left = word_count;
+    for (i, word) in words.iter().enumerate() {
+        acc += word.chars().count() + 1;
+        if word == LINE_BREAK {
+            continue;
+        }
+        words_left -= 1;
+        // 남은 조각 수만큼 단어를 남겨 두고, 목표 누적 글자 수에 도달하면 자름
+        // 남은 단어 수 = 남은 조각 수면 목표와 무관하게 자름 (조각 수 보장)
+        let target = total * (parts.len() + 1) / n;
+        let remaining_parts = n - parts.len() - 1;
+        if remaining_parts > 0
+            && words_left >= remaining_parts
+            && (acc >= target || words_left == remaining_parts)
+        {
+            parts.push(&words[begin..=i]);
+            begin = i + 1;
+        }
+    }
+    parts.push(&words[begin..]);
+    parts
+}
+
+/// 단어를 max_chars 이하 줄로 채움 (0이면 원래 줄바꿈에서만 줄을 나눔)
+fn wrap_words(words: &[String], max_chars: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in words {
+        if word == LINE_BREAK {
+            // cue 원래 줄바꿈 → 강제 줄 끝 (조각 맨 앞이면 무시)
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+        } else if current.is_empty() {
+            current.push_str(word);
+        } else if max_chars == 0 || current.chars().count() + 1 + word.chars().count() <= max_chars {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            lines.push(std::mem::take(&mut current));
+            current.push_str(word);
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// ms → (시, 분, 초, 밀리초)
+fn split_time(ms: i64) -> (i64, i64, i64, i64) {
+    let ms = ms.max(0);
+    (ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000)
+}
+
+/// SRT 타임스탬프 (00:01:02,345)
+pub fn format_srt_time(ms: i64) -> String {
+    let (h, m, s, ms) = split_time(ms);
+    format!("{:02}:{:02}:{:02},{:03}", h, m, s, ms)
+}
+
+/// WebVTT 타임스탬프 (00:01:02.345)
+pub fn format_vtt_time(ms: i64) -> String {
+    let (h, m, s, ms) = split_time(ms);
+    format!("{:02}:{:02}:{:02}.{:03}", h, m, s, ms)
+}
+
+/// ASS 타임스탬프 (0:01:02.34, 1/100초 단위)
+pub fn format_ass_time(ms: i64) -> String {
+    let (h, m, s, ms) = split_time(ms);
+    format!("{}:{:02}:{:02}.{:02}", h, m, s, ms / 10)
+}
+
+/// SRT 직렬화 (번호는 1부터)
+pub fn to_srt(cues: &[SubtitleCue]) -> String {
+    let mut out = String::new();
+    for (i, cue) in cues.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_srt_time(cue.start_ms),
+            format_srt_time(cue.end_ms),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// WebVTT 직렬화 (&, <, >는 엔티티로 이스케이프)
+pub fn to_vtt(cues: &[SubtitleCue]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues {
+        let text = cue.text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_vtt_time(cue.start_ms),
+            format_vtt_time(cue.end_ms),
+            text
+        ));
+    }
+    out
+}
+
+/// ASS 직렬화 (Default 스타일 1개)
+pub fn to_ass(cues: &[SubtitleCue], play_res: (u32, u32)) -> String {
+    let mut out = ass_header(play_res.0, play_res.1);
+    for cue in cues {
+        out.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+            format_ass_time(cue.start_ms),
+            format_ass_time(cue.end_ms),
+            ass_escape(&cue.text)
+        ));
+    }
+    out
+}
+
+/// cue 목록을 지정 형식 문자열로 변환 (prepare_cues 적용)
+pub fn format_captions(cues: &[SubtitleCue], format: CaptionFormat, opts: &CaptionOptions) -> String {
+    let cues = prepare_cues(cues, opts);
+    match format {
+        CaptionFormat::Srt => to_srt(&cues),
+        CaptionFormat::WebVtt => to_vtt(&cues),
+        CaptionFormat::Ass => to_ass(&cues, opts.play_res),
+    }
+}
+
+/// 자막 파일 저장 (UTF-8, SRT는 일부 플레이어 호환을 위해 BOM 포함)
+pub fn write_caption_file(
+    path: &Path,
+    cues: &[SubtitleCue],
+    format: CaptionFormat,
+    opts: &CaptionOptions,
+) -> Result<(), String> {
+    let mut content = format_captions(cues, format, opts);
+    if format == CaptionFormat::Srt {
+        content.insert(0, '\u{FEFF}');
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("자막 디렉토리 생성 실패: {}", e))?;
+    }
+    std::fs::write(path, content)
+        .map_err(|e| format!("자막 파일 저장 실패 ({}): {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_formats() {
+        assert_eq!(format_srt_time(3_723_045), "01:02:03,045");
+        assert_eq!(format_vtt_time(3_723_045), "01:02:03.045");
+        assert_eq!(format_ass_time(3_723_045), "1:02:03.04");
+        assert_eq!(format_srt_time(-5), "00:00:00,000");
+    }
+
+    #[test]
+    fn test_wrap_and_split_by_lines() {
+        let opts = CaptionOptions { max_line_chars: 10, max_lines: 2, max_duration_ms: 0, ..Default::default() };
+        let cues = [SubtitleCue::new(0, 3000, "one two three four five six seven")];
+        let out = prepare_cues(&cues, &opts);
+
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].text, "one two\nthree four");
+        assert_eq!(out[1].text, "five six\nseven");
+        assert_eq!(out[0].start_ms, 0);
+        assert_eq!(out[0].end_ms, out[1].start_ms);
+        assert_eq!(out[1].end_ms, 3000);
+    }
+
+    #[test]
+    fn test_split_by_max_duration() {
+        let opts = CaptionOptions { max_line_chars: 0, max_lines: 2, max_duration_ms: 4000, ..Default::default() };
+        let cues = [SubtitleCue::new(1000, 11000, "가 나 다 라 마 바")];
+        let out = prepare_cues(&cues, &opts);
+
+        assert_eq!(out.len(), 3);
+        assert!(out.iter().all(|c| c.end_ms - c.start_ms <= 4000));
+        assert_eq!(out.last().unwrap().end_ms, 11000);
+        let joined: Vec<&str> = out.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(joined.join(" "), "가 나 다 라 마 바");
+    }
+
+    #[test]
+    fn test_max_duration_with_few_words() {
+        // 20초 cue, 단어 3개 → 조각 수가 모자라도 조각마다 최대 시간 이하
+        let opts = CaptionOptions { max_line_chars: 0, max_lines: 2, max_duration_ms: 4000, ..Default::default() };
+        let out = prepare_cues(&[SubtitleCue::new(0, 20000, "가 나다라마바사아자차카 타")], &opts);
+
+        assert_eq!(out.len(), 3);
+        assert!(out.iter().all(|c| c.end_ms - c.start_ms <= 4000));
+        assert_eq!(out[1].text, "나다라마바사아자차카");
+    }
+
+    #[test]
+    fn test_keeps_cue_line_breaks() {
+        let cues = [SubtitleCue::new(0, 3000, "첫째 줄\r\n둘째 줄")];
+        let opts = CaptionOptions { max_line_chars: 0, ..Default::default() };
+        assert_eq!(prepare_cues(&cues, &opts)[0].text, "첫째 줄\n둘째 줄");
+
+        // 원래 줄바꿈 + 줄 길이 줄바꿈
+        let opts = CaptionOptions { max_line_chars: 6, max_lines: 3, ..Default::default() };
+        assert_eq!(prepare_cues(&cues, &opts)[0].text, "첫째 줄\n둘째 줄");
+        let cues = [SubtitleCue::new(0, 3000, "a b c d e\nf")];
+        assert_eq!(prepare_cues(&cues, &opts)[0].text, "a b c\nd e\nf");
+    }
+
+    #[test]
+    fn test_long_word_is_broken() {
+        let opts = CaptionOptions { max_line_chars: 4, max_lines: 3, max_duration_ms: 0, ..Default::default() };
+        let out = prepare_cues(&[SubtitleCue::new(0, 1000, "abcdefghij")], &opts);
+        assert_eq!(out[0].text, "abcd\nefgh\nij");
+    }
+
+    #[test]
+    fn test_serializers() {
+        let cues = [
+            SubtitleCue::new(0, 1500, "안녕하세요"),
+            SubtitleCue::new(2000, 3000, "a < b & c\n둘째 줄"),
+        ];
+        let srt = to_srt(&cues);
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:01,500\n안녕하세요\n\n2\n"));
+
+        let vtt = to_vtt(&cues);
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:01.500\n"));
+        assert!(vtt.contains("a &lt; b &amp; c\n둘째 줄"));
+
+        let ass = to_ass(&cues, (1920, 1080));
+        assert!(ass.contains("PlayResY: 1080"));
+        assert!(ass.contains("Dialogue: 0,0:00:02.00,0:00:03.00,Default,,0,0,0,,a < b & c\\N둘째 줄\n"));
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(CaptionFormat::from_path(Path::new("a/b.SRT")), Some(CaptionFormat::Srt));
+        assert_eq!(CaptionFormat::from_path(Path::new("b.ssa")), Some(CaptionFormat::Ass));
+        assert_eq!(CaptionFormat::from_path(Path::new("b.txt")), None);
+    }
+}
@@ -0,0 +1,187 @@
+// 시간 지정 텍스트 자막 (cue) — 소프트 자막 먹싱 / 자막 파일 입출력 공통 단위
+// 비트맵 오버레이(overlay.rs)와 달리 텍스트 그대로 컨테이너에 저장 → 플레이어에서 켜고 끌 수 있음
+
+/// 텍스트 자막 1개 (표시 구간 + 본문, 여러 줄은 '\n')
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+    /// 스타일/배치 (ASS 스타일·태그, VTT cue 설정에서 추출, 없으면 하단 중앙 기본)
+    pub style: Option<Box<CueStyle>>,
+}
+
+impl SubtitleCue {
+    pub fn new(start_ms: i64, end_ms: i64, text: impl Into<String>) -> Self {
+        Self { start_ms, end_ms, text: text.into(), style: None }
+    }
+}
+
+/// cue 스타일/배치 (좌표·크기는 스크립트 해상도(PlayRes) 기준)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueStyle {
+    /// ASS 스타일 이름 / 폰트 이름 (렌더링 시 폰트 파일은 호출 측 지정)
+    pub name: String,
+    pub font_name: String,
+    pub font_size: f32,
+    pub color: [u8; 4],             // RGBA
+    pub outline_color: [u8; 4],
+    pub shadow_color: [u8; 4],
+    pub outline_width: f32,
+    pub shadow_depth: f32,
+    pub bold: bool,
+    pub italic: bool,
+    /// 키패드 배치 1~9 (1~3 하단, 4~6 중앙, 7~9 상단 / 왼쪽·가운데·오른쪽)
+    pub alignment: u8,
+    pub margin_l: i32,
+    pub margin_r: i32,
+    pub margin_v: i32,
+    /// \pos(x, y) 절대 위치 (alignment 기준점)
+    pub position: Option<(f32, f32)>,
+}
+
+impl Default for CueStyle {
+    /// ass_header의 Default 스타일과 동일 (1080p 기준)
+    fn default() -> Self {
+        Self {
+            name: "Default".to_string(),
+            font_name: "Arial".to_string(),
+            font_size: 59.0,
+            color: [255, 255, 255, 255],
+            outline_color: [0, 0, 0, 255],
+            shadow_color: [0, 0, 0, 127],
+            outline_width: 2.0,
+            shadow_depth: 0.0,
+            bold: false,
+            italic: false,
+            alignment: 2,
+            margin_l: 10,
+            margin_r: 10,
+            margin_v: 54,
+            position: None,
+        }
+    }
+}
+
+/// 소프트 자막 트랙 (언어별 1개, Export 시 자막 스트림 1개로 먹싱)
+#[derive(Debug, Clone, Default)]
+pub struct SubtitleTrack {
+    /// 언어 코드 ("ko", "kor", "en" 등, 먹싱 시 ISO 639-2로 변환)
+    pub language: String,
+    /// 트랙 이름 (플레이어 자막 메뉴에 표시, 없으면 None)
+    pub title: Option<String>,
+    pub cues: Vec<SubtitleCue>,
+}
+
+impl SubtitleTrack {
+    pub fn new(language: &str, title: Option<&str>) -> Self {
+        Self {
+            language: language.to_string(),
+            title: title.map(|t| t.to_string()),
+            cues: Vec::new(),
+        }
+    }
+
+    /// 시작 시간 순 정렬 + 빈 구간/빈 텍스트 제거 (먹서는 pts 순서를 요구)
+    pub fn normalize(&mut self) {
+        self.cues.retain(|c| c.end_ms > c.start_ms && !c.text.trim().is_empty());
+        self.cues.sort_by_key(|c| (c.start_ms, c.end_ms));
+    }
+}
+
+/// 언어 코드 → ISO 639-2 (MP4/MKV/WebM 메타데이터 형식)
+/// 2글자 코드는 주요 언어만 변환, 3글자는 그대로, 알 수 없으면 "und"
+pub fn iso639_2(language: &str) -> String {
+    let lang = language.trim().to_ascii_lowercase();
+    // "en-US" 같은 지역 접미사 제거
+    let lang = lang.split(['-', '_']).next().unwrap_or("");
+
+    let mapped = match lang {
+        "ko" => "kor",
+        "en" => "eng",
+        "ja" => "jpn",
+        "zh" => "zho",
+        "fr" => "fra",
+        "de" => "deu",
+        "es" => "spa",
+        "it" => "ita",
+        "pt" => "por",
+        "ru" => "rus",
+        "vi" => "vie",
+        "th" => "tha",
+        "id" => "ind",
+        "ar" => "ara",
+        "hi" => "hin",
+        l if l.len() == 3 && l.bytes().all(|b| b.is_ascii_lowercase()) => l,
+        _ => "und",
+    };
+    mapped.to_string()
+}
+
+/// ASS 스크립트 헤더 (Script Info + Default 스타일 + Events 포맷)
+/// FFmpeg 텍스트 자막 인코더(mov_text/subrip/webvtt/ass)는 이 헤더로 초기화됨
+pub fn ass_header(play_res_x: u32, play_res_y: u32) -> String {
+    let font_size = (play_res_y as f32 * 0.055).round().max(8.0) as u32;
+    let margin_v = (play_res_y as f32 * 0.05).round() as u32;
+    format!(
+        "[Script Info]\n\
+         ; Script generated by VortexCut\n\
+         ScriptType: v4.00+\n\
+         PlayResX: {}\n\
+         PlayResY: {}\n\
+         ScaledBorderAndShadow: yes\n\
+         \n\
+         [V4+ Styles]\n\
+         Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, \
+         Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, \
+         Alignment, MarginL, MarginR, MarginV, Encoding\n\
+         Style: Default,Arial,{},&H00FFFFFF,&H00FFFFFF,&H00000000,&H80000000,0,0,0,0,100,100,0,0,1,2,0,2,10,10,{},1\n\
+         \n\
+         [Events]\n\
+         Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+        play_res_x, play_res_y, font_size, margin_v
+    )
+}
+
+/// 일반 텍스트 → ASS 대사 텍스트 (줄바꿈 \N, 중괄호는 오버라이드 태그로 해석되지 않게 치환)
+/// NUL 문자는 제거 (FFmpeg 자막 rect는 C 문자열 → NUL이 있으면 기록 불가)
+pub fn ass_escape(text: &str) -> String {
+    text.replace('\0', "")
+        .replace("\r\n", "\n")
+        .replace('{', "(")
+        .replace('}', ")")
+        .replace('\n', "\\N")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iso639_2() {
+        assert_eq!(iso639_2("ko"), "kor");
+        assert_eq!(iso639_2("en-US"), "eng");
+        assert_eq!(iso639_2("JPN"), "jpn");
+        assert_eq!(iso639_2(""), "und");
+        assert_eq!(iso639_2("x1"), "und");
+    }
+
+    #[test]
+    fn test_normalize_sorts_and_drops_empty() {
+        let mut track = SubtitleTrack::new("ko", None);
+        track.cues.push(SubtitleCue::new(2000, 3000, "둘"));
+        track.cues.push(SubtitleCue::new(500, 500, "길이 0"));
+        track.cues.push(SubtitleCue::new(0, 1000, "하나"));
+        track.cues.push(SubtitleCue::new(1000, 2000, "  "));
+        track.normalize();
+
+        let texts: Vec<&str> = track.cues.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, ["하나", "둘"]);
+    }
+
+    #[test]
+    fn test_ass_escape() {
+        assert_eq!(ass_escape("첫 줄\r\n둘째 {줄}"), "첫 줄\\N둘째 (줄)");
+        assert_eq!(ass_escape("널\0문자\0"), "널문자");
+    }
+}
@@ -1,3 +1,7 @@
-// 자막 처리 모듈 — RGBA 오버레이 알파 블렌딩
+// 자막 처리 모듈 — RGBA 오버레이 알파 블렌딩 + 텍스트 래스터라이즈 + 텍스트 자막(cue) + 자막 파일 입출력
 
+pub mod caption;
+pub mod cue;
 pub mod overlay;
+pub mod parser;
+pub mod text;
@@ -1,7 +1,8 @@
 // 자막 오버레이 — RGBA 비트맵 알파 블렌딩
-// C#에서 텍스트를 RGBA 비트맵으로 렌더링 → FFI로 전달 → Export 시 프레임 위에 합성
+// C#에서 텍스트를 RGBA 비트맵으로 렌더링 → FFI로 전달 → 프리뷰/Export 프레임 위에 합성
 
 /// 단일 자막 오버레이 (시간 범위 + RGBA 비트맵)
+#[derive(Debug, Clone)]
 pub struct SubtitleOverlay {
     /// 표시 시작 시간 (ms)
     pub start_ms: i64,
@@ -19,19 +20,93 @@ pub struct SubtitleOverlay {
     pub rgba_data: Vec<u8>,
 }
 
+impl SubtitleOverlay {
+    /// 특정 시간에 표시 중인지
+    pub fn is_active(&self, timestamp_ms: i64) -> bool {
+        timestamp_ms >= self.start_ms && timestamp_ms < self.end_ms
+    }
+
+    /// 위치/비트맵을 배율만큼 리샘플링 (박스 필터, premultiplied 평균)
+    /// 프리뷰(960x540)처럼 비트맵 기준 해상도와 출력 해상도가 다를 때 사용
+    pub fn scaled(&self, sx: f32, sy: f32) -> SubtitleOverlay {
+        let dst_w = ((self.width as f32 * sx).round() as u32).max(1);
+        let dst_h = ((self.height as f32 * sy).round() as u32).max(1);
+        let src_w = self.width as usize;
+        let src_h = self.height as usize;
+        let mut rgba_data = vec![0u8; (dst_w * dst_h * 4) as usize];
+
+        if src_w > 0 && src_h > 0 && self.rgba_data.len() >= src_w * src_h * 4 {
+            let fx = src_w as f32 / dst_w as f32;
+            let fy = src_h as f32 / dst_h as f32;
+            for dy in 0..dst_h as usize {
+                let y0 = (dy as f32 * fy) as usize;
+                let y1 = (((dy + 1) as f32 * fy).ceil() as usize).clamp(y0 + 1, src_h);
+                for dx in 0..dst_w as usize {
+                    let x0 = (dx as f32 * fx) as usize;
+                    let x1 = (((dx + 1) as f32 * fx).ceil() as usize).clamp(x0 + 1, src_w);
+
+                    let mut sum = [0u32; 4];
+                    for y in y0..y1 {
+                        for x in x0..x1 {
+                            let i = (y * src_w + x) * 4;
+                            let a = self.rgba_data[i + 3] as u32;
+                            sum[0] += self.rgba_data[i] as u32 * a;
+                            sum[1] += self.rgba_data[i + 1] as u32 * a;
+                            sum[2] += self.rgba_data[i + 2] as u32 * a;
+                            sum[3] += a;
+                        }
+                    }
+
+                    if sum[3] == 0 {
+                        continue; // 완전 투명
+                    }
+                    let o = (dy * dst_w as usize + dx) * 4;
+                    rgba_data[o] = (sum[0] / sum[3]) as u8;
+                    rgba_data[o + 1] = (sum[1] / sum[3]) as u8;
+                    rgba_data[o + 2] = (sum[2] / sum[3]) as u8;
+                    rgba_data[o + 3] = (sum[3] / ((y1 - y0) * (x1 - x0)) as u32) as u8;
+                }
+            }
+        }
+
+        SubtitleOverlay {
+            start_ms: self.start_ms,
+            end_ms: self.end_ms,
+            x: (self.x as f32 * sx).round() as i32,
+            y: (self.y as f32 * sy).round() as i32,
+            width: dst_w,
+            height: dst_h,
+            rgba_data,
+        }
+    }
+}
+
 /// 자막 오버레이 목록 (FFI에서 생성/해제)
+#[derive(Debug, Default)]
 pub struct SubtitleOverlayList {
     pub overlays: Vec<SubtitleOverlay>,
+    /// 비트맵 좌표 기준 해상도 (None이면 출력 프레임과 동일하다고 보고 그대로 합성)
+    pub frame_size: Option<(u32, u32)>,
 }
 
 impl SubtitleOverlayList {
     pub fn new() -> Self {
-        Self { overlays: Vec::new() }
+        Self { overlays: Vec::new(), frame_size: None }
     }
 
-    /// 특정 시간에 활성인 오버레이 찾기
+    /// 특정 시간에 활성인 오버레이 찾기 (첫 번째만)
     pub fn get_active(&self, timestamp_ms: i64) -> Option<&SubtitleOverlay> {
-        self.overlays.iter().find(|o| timestamp_ms >= o.start_ms && timestamp_ms < o.end_ms)
+        self.overlays.iter().find(|o| o.is_active(timestamp_ms))
+    }
+
+    /// 특정 시간에 활성인 오버레이 전체 (추가 순서 = 합성 순서, 뒤쪽이 위)
+    pub fn active_overlays(&self, timestamp_ms: i64) -> impl Iterator<Item = &SubtitleOverlay> {
+        self.overlays.iter().filter(move |o| o.is_active(timestamp_ms))
+    }
+
+    /// 활성 오버레이가 하나라도 있는지
+    pub fn has_active(&self, timestamp_ms: i64) -> bool {
+        self.overlays.iter().any(|o| o.is_active(timestamp_ms))
     }
 }
 
@@ -187,3 +262,63 @@ pub fn rgba_to_yuv420p(rgba: &[u8], width: u32, height: u32) -> Vec<u8> {
 
     yuv
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(start_ms: i64, end_ms: i64, x: i32, y: i32, w: u32, h: u32, rgba: [u8; 4]) -> SubtitleOverlay {
+        SubtitleOverlay {
+            start_ms,
+            end_ms,
+            x,
+            y,
+            width: w,
+            height: h,
+            rgba_data: rgba.repeat((w * h) as usize),
+        }
+    }
+
+    #[test]
+    fn test_active_overlays_returns_all_overlapping() {
+        let mut list = SubtitleOverlayList::new();
+        list.overlays.push(solid(0, 1000, 0, 0, 1, 1, [255, 0, 0, 255]));
+        list.overlays.push(solid(500, 1500, 1, 0, 1, 1, [0, 255, 0, 255]));
+        list.overlays.push(solid(2000, 3000, 0, 0, 1, 1, [0, 0, 255, 255]));
+
+        assert_eq!(list.active_overlays(700).count(), 2);
+        assert_eq!(list.active_overlays(1200).count(), 1);
+        assert!(!list.has_active(1800));
+        assert_eq!(list.get_active(700).unwrap().rgba_data[0], 255);
+    }
+
+    #[test]
+    fn test_later_overlay_blends_on_top() {
+        let mut list = SubtitleOverlayList::new();
+        list.overlays.push(solid(0, 1000, 0, 0, 2, 1, [255, 0, 0, 255]));
+        list.overlays.push(solid(0, 1000, 1, 0, 1, 1, [0, 0, 255, 255]));
+
+        let mut frame = vec![0u8; 2 * 4];
+        for overlay in list.active_overlays(10) {
+            blend_overlay_rgba(&mut frame, 2, 1, overlay);
+        }
+        assert_eq!(&frame[0..4], &[255, 0, 0, 255]);
+        assert_eq!(&frame[4..8], &[0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_scaled_halves_position_and_size() {
+        let mut overlay = solid(0, 1000, 100, 40, 4, 2, [255, 255, 255, 255]);
+        // 왼쪽 절반만 불투명
+        for y in 0..2 {
+            for x in 2..4 {
+                overlay.rgba_data[(y * 4 + x) * 4 + 3] = 0;
+            }
+        }
+
+        let half = overlay.scaled(0.5, 0.5);
+        assert_eq!((half.x, half.y, half.width, half.height), (50, 20, 2, 1));
+        assert_eq!(&half.rgba_data[0..4], &[255, 255, 255, 255]);
+        assert_eq!(half.rgba_data[7], 0);
+    }
+}
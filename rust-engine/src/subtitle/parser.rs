@@ -0,0 +1,645 @@
+// 자막 파일 파싱 — SRT / WebVTT / ASS·SSA → SubtitleCue 목록
+// ASS 스타일(폰트 크기·색·외곽선·배치·여백)과 \pos / \an 태그는 CueStyle로 보존
+// → 소프트 자막 먹싱(SubtitleTrack) 또는 TextRenderer로 번인 오버레이 생성
+
+use super::caption::CaptionFormat;
+use super::cue::{CueStyle, SubtitleCue};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 파싱 결과
+#[derive(Debug, Clone, Default)]
+pub struct ParsedCaptions {
+    pub cues: Vec<SubtitleCue>,
+    /// ASS PlayResX/PlayResY (CueStyle 좌표 기준, SRT/VTT는 None)
+    pub play_res: Option<(u32, u32)>,
+}
+
+/// 내용으로 형식 추정 (확장자가 없거나 틀린 파일용)
+pub fn detect_format(content: &str) -> CaptionFormat {
+    let head = content.trim_start_matches('\u{FEFF}').trim_start();
+    if head.starts_with("WEBVTT") {
+        CaptionFormat::WebVtt
+    } else if head.starts_with("[Script Info]") || content.contains("\nDialogue:") {
+        CaptionFormat::Ass
+    } else {
+        CaptionFormat::Srt
+    }
+}
+
+/// 자막 파일 읽기 + 파싱 (UTF-8, BOM 허용 / 형식은 확장자 → 내용 순으로 결정)
+pub fn parse_caption_file(path: &Path) -> Result<ParsedCaptions, String> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("자막 파일 읽기 실패 ({}): {}", path.display(), e))?;
+    let content = String::from_utf8(bytes)
+        .map_err(|_| format!("UTF-8 자막 파일이 아닙니다: {}", path.display()))?;
+    let format = CaptionFormat::from_path(path).unwrap_or_else(|| detect_format(&content));
+    parse_captions(&content, format)
+}
+
+/// 형식별 파싱
+pub fn parse_captions(content: &str, format: CaptionFormat) -> Result<ParsedCaptions, String> {
+    let content = content.trim_start_matches('\u{FEFF}').replace("\r\n", "\n").replace('\r', "\n");
+    let parsed = match format {
+        CaptionFormat::Srt => ParsedCaptions { cues: parse_srt(&content), play_res: None },
+        CaptionFormat::WebVtt => ParsedCaptions { cues: parse_vtt(&content)?, play_res: None },
+        CaptionFormat::Ass => parse_ass(&content)?,
+    };
+    if parsed.cues.is_empty() && !content.trim().is_empty() {
+        return Err(format!("{:?} 자막에서 cue를 찾지 못했습니다", format));
+    }
+    Ok(parsed)
+}
+
+/// 타임스탬프 파싱: "HH:MM:SS,mmm" / "HH:MM:SS.mmm" / "MM:SS.mmm" / ASS "H:MM:SS.cc"
+pub fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (hms, frac) = match s.find(['.', ',']) {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => (s, ""),
+    };
+
+    let parts: Vec<i64> = hms.split(':')
+        .map(|p| p.trim().parse::<i64>().ok().filter(|v| *v >= 0))
+        .collect::<Option<_>>()?;
+    let (h, m, sec) = match parts.as_slice() {
+        [h, m, s] => (*h, *m, *s),
+        [m, s] => (0, *m, *s),
+        _ => return None,
+    };
+
+    // 소수부 자릿수에 맞춰 ms 환산 (1자리=1/10초, 2자리=1/100초, 3자리 이상=ms)
+    let ms = if frac.is_empty() {
+        0
+    } else {
+        if !frac.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let digits = &frac[..frac.len().min(3)];
+        digits.parse::<i64>().ok()? * 10i64.pow(3 - digits.len() as u32)
+    };
+
+    // 파일에서 읽은 큰 값이 overflow하면 잘못된 타임스탬프
+    h.checked_mul(60)?
+        .checked_add(m)?
+        .checked_mul(60)?
+        .checked_add(sec)?
+        .checked_mul(1000)?
+        .checked_add(ms)
+}
+
+/// "start --> end [설정]" 줄 파싱
+fn parse_time_line(line: &str) -> Option<(i64, i64, &str)> {
+    let (start, rest) = line.split_once("-->")?;
+    let rest = rest.trim_start();
+    let (end, settings) = match rest.find(char::is_whitespace) {
+        Some(i) => (&rest[..i], rest[i..].trim()),
+        None => (rest, ""),
+    };
+    Some((parse_timestamp(start)?, parse_timestamp(end)?, settings))
+}
+
+// ============================================================
+// SRT
+// ============================================================
+
+/// SRT 파싱 (번호 줄 생략/깨진 블록은 건너뜀, HTML 태그 제거, {\anN} 배치 태그 지원)
+pub fn parse_srt(content: &str) -> Vec<SubtitleCue> {
+    let mut cues = Vec::new();
+    let mut lines = content.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let (start, end) = match parse_time_line(line) {
+            Some((s, e, _)) => (s, e),
+            None => continue, // 번호 줄 / 빈 줄 / 깨진 줄
+        };
+
+        let mut text_lines = Vec::new();
+        while let Some(next) = lines.peek() {
+            if next.trim().is_empty() {
+                break;
+            }
+            text_lines.push(*next);
+            lines.next();
+        }
+
+        let raw = text_lines.join("\n");
+        let (text, alignment) = strip_srt_tags(&raw);
+        if end <= start || text.trim().is_empty() {
+            continue;
+        }
+
+        let mut cue = SubtitleCue::new(start, end, text);
+        if let Some(alignment) = alignment {
+            cue.style = Some(Box::new(CueStyle { alignment, ..Default::default() }));
+        }
+        cues.push(cue);
+    }
+    cues
+}
+
+/// SRT 본문에서 <i>/<b>/<font> 등 태그와 {...} 블록 제거, {\anN}은 배치로 반환
+fn strip_srt_tags(text: &str) -> (String, Option<u8>) {
+    let mut alignment = None;
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '<' => {
+                for c in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+            }
+            '{' => {
+                let block: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if let Some(tags) = parse_override_block(&block) {
+                    alignment = tags.alignment.or(alignment);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    (out, alignment)
+}
+
+// ============================================================
+// WebVTT
+// ============================================================
+
+/// WebVTT 파싱 (NOTE/STYLE/REGION 블록 무시, cue 설정 align/line/position 지원)
+pub fn parse_vtt(content: &str) -> Result<Vec<SubtitleCue>, String> {
+    let mut blocks = content.split("\n\n").map(|b| b.trim_matches('\n'));
+    match blocks.next() {
+        Some(header) if header.starts_with("WEBVTT") => {}
+        _ => return Err("WEBVTT 헤더가 없습니다".to_string()),
+    }
+
+    let mut cues = Vec::new();
+    for block in blocks {
+        let mut lines = block.lines();
+        let mut first = match lines.next() {
+            Some(l) => l,
+            None => continue,
+        };
+        if first.starts_with("NOTE") || first.starts_with("STYLE") || first.starts_with("REGION") {
+            continue;
+        }
+        // cue 식별자 줄 (선택)
+        if !first.contains("-->") {
+            first = match lines.next() {
+                Some(l) => l,
+                None => continue,
+            };
+        }
+
+        let (start, end, settings) = match parse_time_line(first) {
+            Some(t) => t,
+            None => continue,
+        };
+
+        let raw: Vec<&str> = lines.collect();
+        let text = strip_vtt_tags(&raw.join("\n"));
+        if end <= start || text.trim().is_empty() {
+            continue;
+        }
+
+        let mut cue = SubtitleCue::new(start, end, text);
+        cue.style = vtt_settings_style(settings);
+        cues.push(cue);
+    }
+    Ok(cues)
+}
+
+/// VTT 태그(<c.x>, <v 이름>, <00:00:01.000> 등) 제거 + HTML 엔티티 복원
+fn strip_vtt_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// VTT cue 설정 → CueStyle (기준 해상도 1920x1080, 설정이 없으면 None)
+/// align: 가로 정렬 / line: 세로 위치(% 또는 줄 번호) / position: 가로 위치(%)
+fn vtt_settings_style(settings: &str) -> Option<Box<CueStyle>> {
+    if settings.is_empty() {
+        return None;
+    }
+
+    let mut col = 1u8; // 0=왼쪽, 1=가운데, 2=오른쪽
+    let mut line_pct: Option<f32> = None;
+    let mut position_pct: Option<f32> = None;
+    for setting in settings.split_whitespace() {
+        let (key, value) = match setting.split_once(':') {
+            Some(kv) => kv,
+            None => continue,
+        };
+        match key {
+            "align" => {
+                col = match value {
+                    "start" | "left" => 0,
+                    "end" | "right" => 2,
+                    _ => 1,
+                }
+            }
+            "line" => {
+                let value = value.split(',').next().unwrap_or("");
+                line_pct = match value.strip_suffix('%') {
+                    Some(p) => p.parse().ok(),
+                    // 줄 번호: 양수는 위에서, 음수는 아래에서 (한 줄 ≈ 5.33%)
+                    None => value.parse::<f32>().ok().map(|n| {
+                        if n >= 0.0 { n * 5.33 } else { 100.0 + (n + 1.0) * 5.33 }
+                    }),
+                };
+            }
+            "position" => {
+                position_pct = value.split(',').next().and_then(|p| p.strip_suffix('%')).and_then(|p| p.parse().ok());
+            }
+            _ => {}
+        }
+    }
+
+    let row = match line_pct {
+        Some(p) if p < 33.0 => 2, // 상단
+        Some(p) if p < 66.0 => 1, // 중앙
+        _ => 0,                   // 하단
+    };
+    let mut style = CueStyle { alignment: row * 3 + col + 1, ..Default::default() };
+    if line_pct.is_some() || position_pct.is_some() {
+        let x = position_pct.unwrap_or(50.0).clamp(0.0, 100.0) / 100.0 * 1920.0;
+        let y = line_pct.unwrap_or(100.0).clamp(0.0, 100.0) / 100.0 * 1080.0;
+        style.position = Some((x, y));
+    }
+    Some(Box::new(style))
+}
+
+// ============================================================
+// ASS / SSA
+// ============================================================
+
+/// ASS 기본 이벤트 필드 순서 (Format 줄이 없을 때)
+const DEFAULT_EVENT_FORMAT: &str = "Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text";
+
+/// ASS/SSA 파싱 ([Script Info] PlayRes, [V4+ Styles]/[V4 Styles], [Events] Dialogue)
+pub fn parse_ass(content: &str) -> Result<ParsedCaptions, String> {
+    let mut section = String::new();
+    let mut play_res_x: Option<u32> = None;
+    let mut play_res_y: Option<u32> = None;
+    let mut style_format: Vec<String> = Vec::new();
+    let mut event_format: Vec<String> = split_format(DEFAULT_EVENT_FORMAT);
+    let mut styles: HashMap<String, CueStyle> = HashMap::new();
+    let mut cues = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line[1..line.len() - 1].to_ascii_lowercase();
+            continue;
+        }
+        let (key, value) = match line.split_once(':') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => continue,
+        };
+
+        match (section.as_str(), key) {
+            ("script info", "PlayResX") => play_res_x = value.parse().ok(),
+            ("script info", "PlayResY") => play_res_y = value.parse().ok(),
+            ("v4+ styles" | "v4 styles", "Format") => style_format = split_format(value),
+            ("v4+ styles" | "v4 styles", "Style") => {
+                let legacy = section == "v4 styles";
+                if let Some(style) = parse_ass_style(&style_format, value, legacy) {
+                    styles.insert(style.name.clone(), style);
+                }
+            }
+            ("events", "Format") => event_format = split_format(value),
+            ("events", "Dialogue") => {
+                if let Some(cue) = parse_ass_dialogue(&event_format, value, &styles) {
+                    cues.push(cue);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if section.is_empty() {
+        return Err("ASS 섹션을 찾지 못했습니다".to_string());
+    }
+
+    // PlayRes 한쪽만 있으면 4:3 기준으로 보완 (libass 동작과 동일), 둘 다 없으면 384x288
+    let play_res = match (play_res_x, play_res_y) {
+        (Some(x), Some(y)) => (x, y),
+        (Some(x), None) => (x, if x == 1280 { 1024 } else { x * 3 / 4 }),
+        (None, Some(y)) => (if y == 1024 { 1280 } else { y * 4 / 3 }, y),
+        (None, None) => (384, 288),
+    };
+
+    cues.sort_by_key(|c: &SubtitleCue| (c.start_ms, c.end_ms));
+    Ok(ParsedCaptions { cues, play_res: Some(play_res) })
+}
+
+/// "Name, Fontname, ..." → 소문자 필드 이름 목록
+fn split_format(value: &str) -> Vec<String> {
+    value.split(',').map(|f| f.trim().to_ascii_lowercase()).collect()
+}
+
+/// Format 순서대로 값 매핑 (마지막 필드는 쉼표 포함 나머지 전체)
+fn map_fields<'a>(format: &'a [String], value: &'a str) -> HashMap<&'a str, &'a str> {
+    format.iter()
+        .map(String::as_str)
+        .zip(value.splitn(format.len().max(1), ','))
+        .map(|(k, v)| (k, v.trim()))
+        .collect()
+}
+
+/// Style 줄 → CueStyle
+fn parse_ass_style(format: &[String], value: &str, legacy: bool) -> Option<CueStyle> {
+    let fields = map_fields(format, value);
+    let mut style = CueStyle { name: fields.get("name")?.to_string(), ..Default::default() };
+
+    let num = |key: &str| fields.get(key).and_then(|v| v.parse::<f32>().ok());
+    if let Some(font) = fields.get("fontname") {
+        style.font_name = font.to_string();
+    }
+    if let Some(size) = num("fontsize") {
+        style.font_size = size;
+    }
+    if let Some(c) = fields.get("primarycolour").and_then(|v| parse_ass_color(v)) {
+        style.color = c;
+    }
+    // SSA v4는 외곽선 색이 TertiaryColour
+    if let Some(c) = fields.get("outlinecolour").or(fields.get("tertiarycolour")).and_then(|v| parse_ass_color(v)) {
+        style.outline_color = c;
+    }
+    if let Some(c) = fields.get("backcolour").and_then(|v| parse_ass_color(v)) {
+        style.shadow_color = c;
+    }
+    // Bold/Italic: -1(true) / 0(false), 굵기 값(700 등)도 허용
+    style.bold = num("bold").is_some_and(|v| v != 0.0);
+    style.italic = num("italic").is_some_and(|v| v != 0.0);
+    if let Some(v) = num("outline") {
+        style.outline_width = v;
+    }
+    if let Some(v) = num("shadow") {
+        style.shadow_depth = v;
+    }
+    if let Some(a) = num("alignment") {
+        style.alignment = if legacy { legacy_alignment(a as u8) } else { (a as u8).clamp(1, 9) };
+    }
+    if let Some(v) = num("marginl") {
+        style.margin_l = v as i32;
+    }
+    if let Some(v) = num("marginr") {
+        style.margin_r = v as i32;
+    }
+    if let Some(v) = num("marginv") {
+        style.margin_v = v as i32;
+    }
+    Some(style)
+}
+
+/// Dialogue 줄 → SubtitleCue (스타일 복사 + 이벤트 여백/오버라이드 태그 반영)
+fn parse_ass_dialogue(
+    format: &[String],
+    value: &str,
+    styles: &HashMap<String, CueStyle>,
+) -> Option<SubtitleCue> {
+    let fields = map_fields(format, value);
+    let start = parse_timestamp(fields.get("start")?)?;
+    let end = parse_timestamp(fields.get("end")?)?;
+    let raw = fields.get("text")?;
+
+    let style_name = fields.get("style").map(|s| s.trim_start_matches('*')).unwrap_or("Default");
+    let mut style = styles.get(style_name)
+        .or_else(|| styles.get("Default"))
+        .cloned()
+        .unwrap_or_default();
+
+    // 이벤트 여백이 0이 아니면 스타일 여백 대체
+    for (key, target) in [
+        ("marginl", &mut style.margin_l),
+        ("marginr", &mut style.margin_r),
+        ("marginv", &mut style.margin_v),
+    ] {
+        if let Some(v) = fields.get(key).and_then(|v| v.parse::<i32>().ok()).filter(|v| *v != 0) {
+            *target = v;
+        }
+    }
+
+    let (text, tags) = strip_ass_tags(raw);
+    if end <= start || text.trim().is_empty() {
+        return None;
+    }
+    if let Some(alignment) = tags.alignment {
+        style.alignment = alignment;
+    }
+    if tags.position.is_some() {
+        style.position = tags.position;
+    }
+
+    let mut cue = SubtitleCue::new(start, end, text);
+    cue.style = Some(Box::new(style));
+    Some(cue)
+}
+
+/// ASS 색상 "&HAABBGGRR" / "&HBBGGRR&" / 10진수 → RGBA (ASS 알파는 0=불투명)
+fn parse_ass_color(value: &str) -> Option<[u8; 4]> {
+    let v = value.trim().trim_end_matches('&');
+    let n = match v.strip_prefix("&H").or_else(|| v.strip_prefix("&h")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => v.parse::<i64>().ok()? as u32,
+    };
+    let [a, b, g, r] = n.to_be_bytes();
+    Some([r, g, b, 255 - a])
+}
+
+/// SSA 배치(1~3 하단, 5~7 상단, 9~11 중앙) → 키패드 배치(1~9)
+fn legacy_alignment(a: u8) -> u8 {
+    match a {
+        9..=11 => a - 5,
+        5..=7 => a + 2,
+        1..=3 => a,
+        _ => 2,
+    }
+}
+
+/// 오버라이드 태그에서 추출한 배치 정보
+#[derive(Debug, Default)]
+struct OverrideTags {
+    alignment: Option<u8>,
+    position: Option<(f32, f32)>,
+}
+
+/// {...} 블록 1개 해석 (\anN, \aN, \pos(x,y) 외 태그는 무시)
+fn parse_override_block(block: &str) -> Option<OverrideTags> {
+    if !block.contains('\\') {
+        return None; // 태그가 아닌 주석 블록
+    }
+    let mut tags = OverrideTags::default();
+    for tag in block.split('\\').skip(1) {
+        let tag = tag.trim();
+        if let Some(v) = tag.strip_prefix("an") {
+            tags.alignment = v.parse::<u8>().ok().filter(|a| (1..=9).contains(a));
+        } else if let Some(args) = tag.strip_prefix("pos(").and_then(|r| r.strip_suffix(')')) {
+            let mut it = args.split(',').map(|v| v.trim().parse::<f32>());
+            if let (Some(Ok(x)), Some(Ok(y))) = (it.next(), it.next()) {
+                tags.position = Some((x, y));
+            }
+        } else if let Some(v) = tag.strip_prefix('a').filter(|v| !v.is_empty() && v.bytes().all(|b| b.is_ascii_digit())) {
+            tags.alignment = v.parse::<u8>().ok().map(legacy_alignment);
+        }
+    }
+    Some(tags)
+}
+
+/// ASS 대사 텍스트: 태그 블록 제거 + \N·\n 줄바꿈, \h 공백 (첫 배치/위치 태그 우선)
+fn strip_ass_tags(text: &str) -> (String, OverrideTags) {
+    let mut tags = OverrideTags::default();
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let block: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                if let Some(t) = parse_override_block(&block) {
+                    tags.alignment = tags.alignment.or(t.alignment);
+                    tags.position = tags.position.or(t.position);
+                }
+            }
+            '\\' => match chars.peek() {
+                Some('N') | Some('n') => {
+                    chars.next();
+                    out.push('\n');
+                }
+                Some('h') => {
+                    chars.next();
+                    out.push(' ');
+                }
+                _ => out.push(c),
+            },
+            _ => out.push(c),
+        }
+    }
+    (out, tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("01:02:03,045"), Some(3_723_045));
+        assert_eq!(parse_timestamp("01:02:03.045"), Some(3_723_045));
+        assert_eq!(parse_timestamp("02:03.5"), Some(123_500));
+        assert_eq!(parse_timestamp("1:02:03.04"), Some(3_723_040));
+        assert_eq!(parse_timestamp("abc"), None);
+        assert_eq!(parse_timestamp("1:2:3:4"), None);
+        // overflow → None (패닉 없음)
+        assert_eq!(parse_timestamp("9223372036854775807:00:00,000"), None);
+        assert_eq!(parse_timestamp("2562047788015:12:55,808"), None);
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "\u{FEFF}1\r\n00:00:01,000 --> 00:00:02,500\r\n<i>안녕</i>하세요\r\n둘째 줄\r\n\r\n\
+                   2\r\n00:00:03,000 --> 00:00:04,000\r\n{\\an8}위쪽 자막\r\n\r\n\
+                   깨진 블록\r\n\r\n";
+        let parsed = parse_captions(srt, CaptionFormat::Srt).unwrap();
+        assert_eq!(parsed.cues.len(), 2);
+        assert_eq!(parsed.cues[0].text, "안녕하세요\n둘째 줄");
+        assert_eq!((parsed.cues[0].start_ms, parsed.cues[0].end_ms), (1000, 2500));
+        assert!(parsed.cues[0].style.is_none());
+        assert_eq!(parsed.cues[1].text, "위쪽 자막");
+        assert_eq!(parsed.cues[1].style.as_ref().unwrap().alignment, 8);
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let vtt = "WEBVTT - 제목\n\nNOTE 주석\n\nintro\n00:01.000 --> 00:02.000 align:start line:10%\n<v 화자>a &amp; b</v>\n\n\
+                   00:00:03.000 --> 00:00:04.000\n<c.yellow>노랑</c>\n";
+        let parsed = parse_captions(vtt, CaptionFormat::WebVtt).unwrap();
+        assert_eq!(parsed.cues.len(), 2);
+        assert_eq!(parsed.cues[0].text, "a & b");
+        assert_eq!(parsed.cues[0].start_ms, 1000);
+        let style = parsed.cues[0].style.as_ref().unwrap();
+        assert_eq!(style.alignment, 7); // 상단 왼쪽
+        assert_eq!(parsed.cues[1].text, "노랑");
+        assert!(parsed.cues[1].style.is_none());
+
+        assert!(parse_vtt("00:01.000 --> 00:02.000\n텍스트").is_err());
+    }
+
+    #[test]
+    fn test_parse_ass_styles_and_tags() {
+        let ass = "[Script Info]\nPlayResX: 1280\nPlayResY: 720\n\n\
+                   [V4+ Styles]\n\
+                   Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+                   Style: Sign,Malgun Gothic,40,&H0000FFFF,&H000000FF,&H00000000,&H80000000,-1,0,0,0,100,100,0,0,1,3,1,8,20,20,30,1\n\n\
+                   [Events]\n\
+                   Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+                   Dialogue: 0,0:00:05.00,0:00:06.50,Sign,,0,0,0,,{\\pos(640,100)\\an5}표지판, 쉼표 포함\\N둘째\n\
+                   Dialogue: 0,0:00:01.00,0:00:02.00,Unknown,,0,0,45,,기본\\h스타일\n";
+        let parsed = parse_captions(ass, CaptionFormat::Ass).unwrap();
+        assert_eq!(parsed.play_res, Some((1280, 720)));
+        assert_eq!(parsed.cues.len(), 2);
+
+        // 시간 순 정렬 + 스타일 없으면 기본값, 이벤트 MarginV 우선
+        let first = &parsed.cues[0];
+        assert_eq!(first.text, "기본 스타일");
+        assert_eq!(first.style.as_ref().unwrap().margin_v, 45);
+
+        let sign = &parsed.cues[1];
+        assert_eq!(sign.text, "표지판, 쉼표 포함\n둘째");
+        assert_eq!((sign.start_ms, sign.end_ms), (5000, 6500));
+        let style = sign.style.as_ref().unwrap();
+        assert_eq!(style.font_name, "Malgun Gothic");
+        assert_eq!(style.font_size, 40.0);
+        assert_eq!(style.color, [255, 255, 0, 255]); // &H0000FFFF = 노랑
+        assert_eq!(style.shadow_color, [0, 0, 0, 127]);
+        assert!(style.bold);
+        assert_eq!(style.outline_width, 3.0);
+        assert_eq!(style.alignment, 5);
+        assert_eq!(style.position, Some((640.0, 100.0)));
+    }
+
+    #[test]
+    fn test_ssa_legacy_alignment() {
+        assert_eq!(legacy_alignment(2), 2);
+        assert_eq!(legacy_alignment(6), 8);
+        assert_eq!(legacy_alignment(10), 5);
+
+        let ssa = "[Script Info]\nScriptType: v4.00\n\n[V4 Styles]\n\
+                   Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, TertiaryColour, BackColour, Bold, Italic, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, AlphaLevel, Encoding\n\
+                   Style: Default,Arial,20,16777215,65535,0,0,0,0,1,2,0,6,10,10,10,0,1\n\n\
+                   [Events]\nFormat: Marked, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+                   Dialogue: Marked=0,0:00:00.00,0:00:01.00,Default,,0000,0000,0000,,{\\a1}왼쪽 아래\n";
+        let parsed = parse_captions(ssa, CaptionFormat::Ass).unwrap();
+        assert_eq!(parsed.play_res, Some((384, 288)));
+        let style = parsed.cues[0].style.as_ref().unwrap();
+        assert_eq!(style.color, [255, 255, 255, 255]);
+        assert_eq!(style.alignment, 1); // 스타일은 상단(6→8)이지만 \a1 태그 우선
+    }
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format("\u{FEFF}WEBVTT\n\n"), CaptionFormat::WebVtt);
+        assert_eq!(detect_format("[Script Info]\n"), CaptionFormat::Ass);
+        assert_eq!(detect_format("1\n00:00:01,000 --> 00:00:02,000\nx\n"), CaptionFormat::Srt);
+    }
+}
@@ -0,0 +1,612 @@
+// 텍스트 레이어 — TTF/OTF 폰트로 타이틀/자막을 RGBA 비트맵으로 래스터라이즈
+// C#에서 비트맵을 굽지 않고 엔진이 직접 렌더링 → 프리뷰/재생/Export 결과 동일
+
+use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::cue::{CueStyle, SubtitleCue};
+use super::overlay::{SubtitleOverlay, SubtitleOverlayList};
+
+/// 렌더링 결과 캐시 최대 개수 (초과 시 전체 비움)
+const MAX_TEXT_CACHE: usize = 64;
+
+/// 가로 정렬 (여러 줄일 때 줄 단위 정렬 + 앵커 기준)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum TextAlign {
+    Left = 0,
+    Center = 1,
+    Right = 2,
+}
+
+impl TextAlign {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Left,
+            2 => Self::Right,
+            _ => Self::Center,
+        }
+    }
+}
+
+/// 세로 정렬 (텍스트 블록의 어느 쪽이 position_y에 놓일지)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum VerticalAlign {
+    Top = 0,
+    Middle = 1,
+    Bottom = 2,
+}
+
+impl VerticalAlign {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Top,
+            1 => Self::Middle,
+            _ => Self::Bottom,
+        }
+    }
+}
+
+/// 텍스트 스타일 (크기/두께/오프셋은 타임라인 픽셀 단위)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextStyle {
+    pub font_path: PathBuf,
+    pub size: f32,                  // 폰트 크기 (px)
+    pub color: [u8; 4],             // RGBA
+    pub outline_color: [u8; 4],
+    pub outline_width: f32,         // 0 = 외곽선 없음
+    pub shadow_color: [u8; 4],      // alpha 0 = 그림자 없음
+    pub shadow_offset_x: f32,
+    pub shadow_offset_y: f32,
+    pub align: TextAlign,
+    pub vertical_align: VerticalAlign,
+    pub position_x: f32,            // 0.0~1.0 (캔버스 대비 앵커 위치)
+    pub position_y: f32,
+    pub max_width: f32,             // 0.0~1.0 (캔버스 너비 대비 줄바꿈 폭, 0 = 줄바꿈 안 함)
+    pub line_spacing: f32,          // 줄 간격 배율 (1.0 = 폰트 기본)
+}
+
+impl TextStyle {
+    /// 하단 중앙 자막 기본 스타일 (흰 글자 + 검은 외곽선)
+    pub fn new(font_path: PathBuf, size: f32) -> Self {
+        Self {
+            font_path,
+            size,
+            color: [255, 255, 255, 255],
+            outline_color: [0, 0, 0, 255],
+            outline_width: 2.0,
+            shadow_color: [0, 0, 0, 0],
+            shadow_offset_x: 2.0,
+            shadow_offset_y: 2.0,
+            align: TextAlign::Center,
+            vertical_align: VerticalAlign::Bottom,
+            position_x: 0.5,
+            position_y: 0.9,
+            max_width: 0.9,
+            line_spacing: 1.0,
+        }
+    }
+}
+
+/// 텍스트 레이어 (텍스트 클립 내용)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayer {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+/// 폰트 + 렌더링 결과 캐시를 보유하는 텍스트 렌더러
+/// Renderer마다 1개 (Export 렌더러와 프리뷰 렌더러는 각자 보유)
+pub struct TextRenderer {
+    fonts: HashMap<PathBuf, Arc<FontVec>>,
+    cache: HashMap<String, SubtitleOverlay>,
+}
+
+impl Default for TextRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextRenderer {
+    pub fn new() -> Self {
+        Self {
+            fonts: HashMap::new(),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// 폰트 로드 (경로당 1회)
+    fn font(&mut self, path: &Path) -> Result<Arc<FontVec>, String> {
+        if let Some(font) = self.fonts.get(path) {
+            return Ok(font.clone());
+        }
+        let data = std::fs::read(path)
+            .map_err(|e| format!("Failed to read font {}: {}", path.display(), e))?;
+        let font = FontVec::try_from_vec(data)
+            .map_err(|e| format!("Invalid font {}: {}", path.display(), e))?;
+        let font = Arc::new(font);
+        self.fonts.insert(path.to_path_buf(), font.clone());
+        Ok(font)
+    }
+
+    /// 텍스트를 캔버스 좌표의 RGBA 비트맵으로 렌더링 (start_ms/end_ms는 0, 호출자가 설정)
+    /// - scale: 출력 픽셀 / 타임라인 픽셀
+    pub fn render(
+        &mut self,
+        text: &str,
+        style: &TextStyle,
+        canvas_width: u32,
+        canvas_height: u32,
+        scale: f32,
+    ) -> Result<SubtitleOverlay, String> {
+        let key = format!("{:?}#{:?}#{}x{}#{}", text, style, canvas_width, canvas_height, scale);
+        if let Some(overlay) = self.cache.get(&key) {
+            return Ok(overlay.clone());
+        }
+
+        let font = self.font(&style.font_path)?;
+        let overlay = rasterize(font.as_ref(), text, style, canvas_width, canvas_height, scale);
+
+        if self.cache.len() >= MAX_TEXT_CACHE {
+            self.cache.clear();
+        }
+        self.cache.insert(key, overlay.clone());
+        Ok(overlay)
+    }
+
+    /// 자막 cue 목록 → 번인 오버레이 목록 (자막 파일을 UI 없이 Export에 태울 때)
+    /// 좌표/크기는 play_res 기준 → width x height 캔버스로 변환, 폰트는 font_path 하나로 통일
+    /// 결과는 크기가 크므로 캐시하지 않음
+    pub fn render_cues(
+        &mut self,
+        cues: &[SubtitleCue],
+        play_res: (u32, u32),
+        font_path: &Path,
+        width: u32,
+        height: u32,
+    ) -> Result<SubtitleOverlayList, String> {
+        let font = self.font(font_path)?;
+        let scale = height as f32 / play_res.1.max(1) as f32;
+        let default_style = CueStyle::default();
+
+        let mut list = SubtitleOverlayList::new();
+        list.frame_size = Some((width, height));
+        for cue in cues {
+            let style = cue_text_style(cue.style.as_deref().unwrap_or(&default_style), play_res, font_path);
+            let mut overlay = rasterize(font.as_ref(), &cue.text, &style, width, height, scale);
+            if overlay.width == 0 || overlay.height == 0 {
+                continue;
+            }
+            overlay.start_ms = cue.start_ms;
+            overlay.end_ms = cue.end_ms;
+            list.overlays.push(overlay);
+        }
+        Ok(list)
+    }
+
+    /// 렌더링 결과 캐시 비우기 (폰트는 유지)
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// 폰트로 텍스트 래스터라이즈 → 그림자/외곽선/본문 합성
+fn rasterize(
+    font: &FontVec,
+    text: &str,
+    style: &TextStyle,
+    canvas_width: u32,
+    canvas_height: u32,
+    scale: f32,
+) -> SubtitleOverlay {
+    let px_size = (style.size * scale).max(1.0);
+    let scaled = font.as_scaled(PxScale::from(px_size));
+
+    let measure = |s: &str| -> f32 {
+        let mut width = 0.0;
+        let mut prev = None;
+        for c in s.chars() {
+            let id = scaled.glyph_id(c);
+            if let Some(p) = prev {
+                width += scaled.kern(p, id);
+            }
+            width += scaled.h_advance(id);
+            prev = Some(id);
+        }
+        width
+    };
+
+    let max_width = if style.max_width > 0.0 {
+        style.max_width * canvas_width as f32
+    } else {
+        f32::INFINITY
+    };
+    let lines = wrap_lines(text, max_width, &measure);
+    let widths: Vec<f32> = lines.iter().map(|l| measure(l)).collect();
+
+    let ascent = scaled.ascent();
+    let glyph_height = ascent - scaled.descent();
+    let line_height = (glyph_height + scaled.line_gap()) * style.line_spacing.max(0.1);
+    let block_width = widths.iter().cloned().fold(0.0f32, f32::max);
+    let block_height = glyph_height + line_height * (lines.len().max(1) - 1) as f32;
+
+    // 외곽선/그림자가 잘리지 않도록 여백
+    let outline = (style.outline_width * scale).max(0.0);
+    let shadow = (style.shadow_offset_x * scale, style.shadow_offset_y * scale);
+    let pad = (outline + shadow.0.abs().max(shadow.1.abs()) + 2.0).ceil();
+    let width = (block_width.ceil() + pad * 2.0) as u32;
+    let height = (block_height.ceil() + pad * 2.0) as u32;
+
+    // 본문 커버리지 마스크 (0.0~1.0)
+    let mut fill = vec![0.0f32; (width * height) as usize];
+    for (i, line) in lines.iter().enumerate() {
+        let mut caret = pad + line_offset(style.align, block_width, widths[i]);
+        let baseline = pad + ascent + line_height * i as f32;
+        let mut prev = None;
+        for c in line.chars() {
+            let id = scaled.glyph_id(c);
+            if let Some(p) = prev {
+                caret += scaled.kern(p, id);
+            }
+            let glyph = id.with_scale_and_position(px_size, point(caret, baseline));
+            if let Some(outlined) = font.outline_glyph(glyph) {
+                let bounds = outlined.px_bounds();
+                outlined.draw(|gx, gy, coverage| {
+                    let x = bounds.min.x as i32 + gx as i32;
+                    let y = bounds.min.y as i32 + gy as i32;
+                    if x >= 0 && y >= 0 && (x as u32) < width && (y as u32) < height {
+                        let idx = (y as u32 * width + x as u32) as usize;
+                        fill[idx] = fill[idx].max(coverage.min(1.0));
+                    }
+                });
+            }
+            caret += scaled.h_advance(id);
+            prev = Some(id);
+        }
+    }
+
+    let outline_mask = if outline > 0.0 && style.outline_color[3] > 0 {
+        Some(dilate(&fill, width, height, outline))
+    } else {
+        None
+    };
+    let shadow_mask = if style.shadow_color[3] > 0 {
+        let base = outline_mask.as_deref().unwrap_or(&fill);
+        Some(shift(base, width, height, shadow.0.round() as i32, shadow.1.round() as i32))
+    } else {
+        None
+    };
+
+    let rgba_data = compose_layers(
+        &[
+            (shadow_mask.as_deref(), style.shadow_color),
+            (outline_mask.as_deref(), style.outline_color),
+            (Some(&fill), style.color),
+        ],
+        (width * height) as usize,
+    );
+
+    // 앵커 기준 블록 배치 (캔버스 좌표)
+    let anchor_x = style.position_x * canvas_width as f32;
+    let anchor_y = style.position_y * canvas_height as f32;
+    let block_x = anchor_x
+        - match style.align {
+            TextAlign::Left => 0.0,
+            TextAlign::Center => block_width * 0.5,
+            TextAlign::Right => block_width,
+        };
+    let block_y = anchor_y
+        - match style.vertical_align {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Middle => block_height * 0.5,
+            VerticalAlign::Bottom => block_height,
+        };
+
+    SubtitleOverlay {
+        start_ms: 0,
+        end_ms: 0,
+        x: (block_x - pad).round() as i32,
+        y: (block_y - pad).round() as i32,
+        width,
+        height,
+        rgba_data,
+    }
+}
+
+/// 블록 안에서 줄의 가로 시작 위치
+fn line_offset(align: TextAlign, block_width: f32, line_width: f32) -> f32 {
+    match align {
+        TextAlign::Left => 0.0,
+        TextAlign::Center => (block_width - line_width) * 0.5,
+        TextAlign::Right => block_width - line_width,
+    }
+}
+
+/// 줄바꿈: '\n'은 강제 줄바꿈, 그 외는 단어 단위로 max_width 안에 채움
+/// 한 단어가 max_width보다 길면 글자 단위로 자름 (CJK 공백 없는 문장 대응)
+pub fn wrap_lines<F: Fn(&str) -> f32>(text: &str, max_width: f32, measure: &F) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let paragraph = paragraph.trim_end_matches('\r');
+        if measure(paragraph) <= max_width {
+            lines.push(paragraph.to_string());
+            continue;
+        }
+
+        let mut current = String::new();
+        for word in paragraph.split(' ').filter(|w| !w.is_empty()) {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current, word)
+            };
+            if measure(&candidate) <= max_width {
+                current = candidate;
+                continue;
+            }
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            // 단어 자체가 너무 길면 글자 단위 분할
+            for c in word.chars() {
+                let mut next = current.clone();
+                next.push(c);
+                if !current.is_empty() && measure(&next) > max_width {
+                    lines.push(std::mem::take(&mut current));
+                    current.push(c);
+                } else {
+                    current = next;
+                }
+            }
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+/// 마스크 팽창 (반경 radius 원형 최대값 필터) — 외곽선 생성용
+/// 반경 경계는 거리 기반으로 부드럽게 감쇠
+fn dilate(mask: &[f32], width: u32, height: u32, radius: f32) -> Vec<f32> {
+    let r = radius.ceil() as i32;
+    let mut offsets = Vec::new();
+    for dy in -r..=r {
+        for dx in -r..=r {
+            let dist = ((dx * dx + dy * dy) as f32).sqrt();
+            let weight = (radius + 1.0 - dist).clamp(0.0, 1.0);
+            if weight > 0.0 {
+                offsets.push((dx, dy, weight));
+            }
+        }
+    }
+
+    let (w, h) = (width as i32, height as i32);
+    let mut out = vec![0.0f32; mask.len()];
+    for y in 0..h {
+        for x in 0..w {
+            let mut value = 0.0f32;
+            for &(dx, dy, weight) in &offsets {
+                let sx = x + dx;
+                let sy = y + dy;
+                if sx < 0 || sy < 0 || sx >= w || sy >= h {
+                    continue;
+                }
+                value = value.max(mask[(sy * w + sx) as usize] * weight);
+                if value >= 1.0 {
+                    break;
+                }
+            }
+            out[(y * w + x) as usize] = value;
+        }
+    }
+    out
+}
+
+/// 마스크 평행 이동 (그림자용, 밖으로 나간 부분은 버림)
+fn shift(mask: &[f32], width: u32, height: u32, dx: i32, dy: i32) -> Vec<f32> {
+    let (w, h) = (width as i32, height as i32);
+    let mut out = vec![0.0f32; mask.len()];
+    for y in 0..h {
+        for x in 0..w {
+            let sx = x - dx;
+            let sy = y - dy;
+            if sx >= 0 && sy >= 0 && sx < w && sy < h {
+                out[(y * w + x) as usize] = mask[(sy * w + sx) as usize];
+            }
+        }
+    }
+    out
+}
+
+/// 마스크 + 색상 레이어를 하단부터 source-over 합성 → straight alpha RGBA
+fn compose_layers(layers: &[(Option<&[f32]>, [u8; 4])], pixel_count: usize) -> Vec<u8> {
+    let mut out = vec![0u8; pixel_count * 4];
+    for (i, px) in out.chunks_exact_mut(4).enumerate() {
+        let mut color = [0.0f32; 3];
+        let mut alpha = 0.0f32;
+        for (mask, rgba) in layers {
+            let Some(mask) = mask else { continue };
+            let sa = mask[i] * rgba[3] as f32 / 255.0;
+            if sa <= 0.0 {
+                continue;
+            }
+            let out_a = sa + alpha * (1.0 - sa);
+            for (c, value) in color.iter_mut().enumerate() {
+                let s = rgba[c] as f32 / 255.0;
+                *value = (s * sa + *value * alpha * (1.0 - sa)) / out_a;
+            }
+            alpha = out_a;
+        }
+        if alpha > 0.0 {
+            px[0] = (color[0] * 255.0 + 0.5) as u8;
+            px[1] = (color[1] * 255.0 + 0.5) as u8;
+            px[2] = (color[2] * 255.0 + 0.5) as u8;
+            px[3] = (alpha * 255.0 + 0.5) as u8;
+        }
+    }
+    out
+}
+
+/// 오버레이를 투명 캔버스(width x height RGBA)에 배치 — 텍스트 클립 레이어용
+pub fn overlay_to_canvas(overlay: &SubtitleOverlay, width: u32, height: u32) -> Vec<u8> {
+    let mut canvas = vec![0u8; (width * height * 4) as usize];
+    let row_bytes = (overlay.width * 4) as usize;
+    for oy in 0..overlay.height as i32 {
+        let y = overlay.y + oy;
+        if y < 0 || y >= height as i32 {
+            continue;
+        }
+        // 가로 클리핑 범위
+        let x0 = overlay.x.max(0);
+        let x1 = (overlay.x + overlay.width as i32).min(width as i32);
+        if x1 <= x0 {
+            continue;
+        }
+        let src = oy as usize * row_bytes + ((x0 - overlay.x) * 4) as usize;
+        let dst = ((y as u32 * width + x0 as u32) * 4) as usize;
+        let len = ((x1 - x0) * 4) as usize;
+        canvas[dst..dst + len].copy_from_slice(&overlay.rgba_data[src..src + len]);
+    }
+    canvas
+}
+
+/// cue 스타일 → TextStyle (키패드 배치 + 여백 / \pos → 정규화 앵커)
+/// 크기·외곽선·그림자는 play_res 단위 그대로 (렌더링 시 캔버스 높이/PlayResY 배율 적용)
+pub fn cue_text_style(cue: &CueStyle, play_res: (u32, u32), font_path: &Path) -> TextStyle {
+    let (pw, ph) = (play_res.0.max(1) as f32, play_res.1.max(1) as f32);
+    let alignment = cue.alignment.clamp(1, 9) - 1;
+    let (col, row) = (alignment % 3, alignment / 3);
+
+    let align = match col {
+        0 => TextAlign::Left,
+        2 => TextAlign::Right,
+        _ => TextAlign::Center,
+    };
+    let vertical_align = match row {
+        0 => VerticalAlign::Bottom,
+        1 => VerticalAlign::Middle,
+        _ => VerticalAlign::Top,
+    };
+
+    let (x, y) = cue.position.unwrap_or_else(|| {
+        let x = match col {
+            0 => cue.margin_l as f32,
+            2 => pw - cue.margin_r as f32,
+            _ => (pw + (cue.margin_l - cue.margin_r) as f32) / 2.0,
+        };
+        let y = match row {
+            0 => ph - cue.margin_v as f32,
+            1 => ph / 2.0,
+            _ => cue.margin_v as f32,
+        };
+        (x, y)
+    });
+
+    let mut style = TextStyle::new(font_path.to_path_buf(), cue.font_size);
+    style.color = cue.color;
+    style.outline_color = cue.outline_color;
+    style.outline_width = cue.outline_width;
+    style.shadow_color = if cue.shadow_depth > 0.0 { cue.shadow_color } else { [0, 0, 0, 0] };
+    style.shadow_offset_x = cue.shadow_depth;
+    style.shadow_offset_y = cue.shadow_depth;
+    style.align = align;
+    style.vertical_align = vertical_align;
+    style.position_x = x / pw;
+    style.position_y = y / ph;
+    // \pos가 없으면 좌우 여백 안쪽에서 줄바꿈
+    style.max_width = if cue.position.is_some() {
+        0.0
+    } else {
+        ((pw - (cue.margin_l + cue.margin_r) as f32) / pw).clamp(0.1, 1.0)
+    };
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 글자당 10px 고정폭 측정
+    fn mono(s: &str) -> f32 {
+        s.chars().count() as f32 * 10.0
+    }
+
+    #[test]
+    fn test_wrap_words() {
+        let lines = wrap_lines("hello big world", 90.0, &mono);
+        assert_eq!(lines, vec!["hello big", "world"]);
+    }
+
+    #[test]
+    fn test_wrap_hard_breaks_and_no_limit() {
+        let lines = wrap_lines("one\r\ntwo three", f32::INFINITY, &mono);
+        assert_eq!(lines, vec!["one", "two three"]);
+    }
+
+    #[test]
+    fn test_wrap_long_word_by_chars() {
+        // 공백 없는 긴 문장 (CJK 등) → 글자 단위
+        let lines = wrap_lines("가나다라마바", 40.0, &mono);
+        assert_eq!(lines, vec!["가나다라", "마바"]);
+    }
+
+    #[test]
+    fn test_cue_text_style_alignment_and_position() {
+        let font = Path::new("font.ttf");
+        let mut cue = CueStyle { alignment: 9, margin_r: 40, margin_v: 20, ..Default::default() };
+        let style = cue_text_style(&cue, (400, 200), font);
+        assert_eq!((style.align, style.vertical_align), (TextAlign::Right, VerticalAlign::Top));
+        assert_eq!((style.position_x, style.position_y), (0.9, 0.1));
+
+        cue.alignment = 5;
+        cue.position = Some((100.0, 50.0));
+        let style = cue_text_style(&cue, (400, 200), font);
+        assert_eq!((style.align, style.vertical_align), (TextAlign::Center, VerticalAlign::Middle));
+        assert_eq!((style.position_x, style.position_y), (0.25, 0.25));
+        assert_eq!(style.max_width, 0.0);
+    }
+
+    #[test]
+    fn test_dilate_grows_mask() {
+        let mut mask = vec![0.0f32; 25];
+        mask[12] = 1.0; // 5x5 중앙
+        let out = dilate(&mask, 5, 5, 1.0);
+        assert_eq!(out[12], 1.0);
+        assert_eq!(out[7], 1.0); // 위
+        assert_eq!(out[11], 1.0); // 왼쪽
+        assert_eq!(out[0], 0.0); // 모서리는 반경 밖
+    }
+
+    #[test]
+    fn test_compose_layers_order() {
+        let fill = [1.0f32, 0.0];
+        let outline = [1.0f32, 1.0];
+        let out = compose_layers(
+            &[(Some(&outline), [0, 0, 0, 255]), (Some(&fill), [255, 255, 255, 255])],
+            2,
+        );
+        // 본문이 외곽선 위
+        assert_eq!(&out[0..4], &[255, 255, 255, 255]);
+        assert_eq!(&out[4..8], &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_overlay_to_canvas_clips() {
+        let overlay = SubtitleOverlay {
+            start_ms: 0,
+            end_ms: 0,
+            x: -1,
+            y: 1,
+            width: 2,
+            height: 1,
+            rgba_data: vec![1, 1, 1, 255, 2, 2, 2, 255],
+        };
+        let canvas = overlay_to_canvas(&overlay, 3, 3);
+        // (-1,1)은 잘리고 (0,1)에 두 번째 픽셀
+        assert_eq!(&canvas[12..16], &[2, 2, 2, 255]);
+        assert_eq!(canvas[3], 0);
+    }
+}
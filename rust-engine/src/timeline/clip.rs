@@ -1,6 +1,8 @@
 // 클립 모듈 - 타임라인에 배치되는 미디어 세그먼트
 
 use std::path::PathBuf;
+use super::keyframe::{ClipKeyframes, KeyframeProperty};
+use crate::subtitle::text::TextLayer;
 
 /// 클립 타입
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,6 +10,8 @@ pub enum ClipType {
     Video,
     Audio,
     Image,
+    Generator,  // 파일 없이 프레임 생성 (단색/그라데이션/테스트 패턴)
+    Text,       // 타이틀/자막 텍스트 (엔진에서 폰트 래스터라이즈)
 }
 
 /// 트랜지션 타입 (incoming 클립에 설정)
@@ -38,14 +42,184 @@ impl TransitionType {
     }
 }
 
+/// 블렌드 모드 (상위 레이어를 하위 레이어와 합성하는 방식)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BlendMode {
+    Normal = 0,       // 일반 (source-over)
+    Multiply = 1,     // 곱하기 (어둡게)
+    Screen = 2,       // 스크린 (밝게)
+    Overlay = 3,      // 오버레이 (대비 강화)
+    Add = 4,          // 더하기 (Linear Dodge)
+    Subtract = 5,     // 빼기
+    Darken = 6,       // 어두운 쪽 선택
+    Lighten = 7,      // 밝은 쪽 선택
+}
+
+impl BlendMode {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Normal,
+            1 => Self::Multiply,
+            2 => Self::Screen,
+            3 => Self::Overlay,
+            4 => Self::Add,
+            5 => Self::Subtract,
+            6 => Self::Darken,
+            7 => Self::Lighten,
+            _ => Self::Normal,
+        }
+    }
+}
+
+/// 종횡비가 다른 소스를 캔버스에 맞추는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u32)]
+pub enum FitMode {
+    #[default]
+    Fit = 0,        // 전체 표시 + 레터박스/필러박스 (검은 여백)
+    Fill = 1,       // 캔버스를 채우고 넘치는 부분 크롭
+    Stretch = 2,    // 종횡비 무시하고 늘림
+    BlurFill = 3,   // Fit + 흐린 확대본을 여백 배경으로
+}
+
+impl FitMode {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Fit,
+            1 => Self::Fill,
+            2 => Self::Stretch,
+            3 => Self::BlurFill,
+            _ => Self::Fit,
+        }
+    }
+}
+
+/// 클립 트랜스폼 (캔버스 내 배치)
+/// 클립 원본은 fit_mode에 따라 캔버스 크기로 디코딩된 뒤 이 값으로 변형됨
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipTransform {
+    pub position_x: f32,    // 앵커 이동량 (타임라인 픽셀, 0 = 제자리)
+    pub position_y: f32,
+    pub scale_x: f32,       // 1.0 = 캔버스 크기
+    pub scale_y: f32,
+    pub rotation_deg: f32,  // 시계 방향 (도)
+    pub crop_left: f32,     // 0.0~1.0 (클립 너비/높이 대비 잘라낼 비율)
+    pub crop_top: f32,
+    pub crop_right: f32,
+    pub crop_bottom: f32,
+    pub anchor_x: f32,      // 0.0~1.0 (회전/스케일 기준점, 0.5 = 중앙)
+    pub anchor_y: f32,
+}
+
+impl Default for ClipTransform {
+    fn default() -> Self {
+        Self {
+            position_x: 0.0,
+            position_y: 0.0,
+            scale_x: 1.0,
+            scale_y: 1.0,
+            rotation_deg: 0.0,
+            crop_left: 0.0,
+            crop_top: 0.0,
+            crop_right: 0.0,
+            crop_bottom: 0.0,
+            anchor_x: 0.5,
+            anchor_y: 0.5,
+        }
+    }
+}
+
+impl ClipTransform {
+    /// 변형이 없는지 확인 — true이면 트랜스폼 스테이지 건너뜀
+    /// 앵커는 다른 값이 모두 기본일 때 결과에 영향 없음
+    pub fn is_identity(&self) -> bool {
+        self.position_x.abs() < 0.001
+            && self.position_y.abs() < 0.001
+            && (self.scale_x - 1.0).abs() < 0.0001
+            && (self.scale_y - 1.0).abs() < 0.0001
+            && (self.rotation_deg % 360.0).abs() < 0.001
+            && self.crop_left <= 0.0
+            && self.crop_top <= 0.0
+            && self.crop_right <= 0.0
+            && self.crop_bottom <= 0.0
+    }
+}
+
+/// 제너레이터 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum GeneratorKind {
+    SolidColor = 0,      // 단색 (color_a)
+    LinearGradient = 1,  // 선형 그라데이션 color_a → color_b (angle_deg 방향)
+    RadialGradient = 2,  // 원형 그라데이션 중앙 color_a → 가장자리 color_b
+    ColorBars = 3,       // SMPTE 컬러바
+    Checkerboard = 4,    // 체커보드 color_a / color_b
+    Countdown = 5,       // 카운트다운 리더 (남은 초 표시, 배경 color_b / 숫자 color_a)
+}
+
+impl GeneratorKind {
+    /// 알 수 없는 값이면 None (FFI 입력 검증용)
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::SolidColor),
+            1 => Some(Self::LinearGradient),
+            2 => Some(Self::RadialGradient),
+            3 => Some(Self::ColorBars),
+            4 => Some(Self::Checkerboard),
+            5 => Some(Self::Countdown),
+            _ => None,
+        }
+    }
+}
+
+/// 제너레이터 클립 파라미터
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneratorParams {
+    pub kind: GeneratorKind,
+    pub color_a: [u8; 4],   // RGBA 주 색상
+    pub color_b: [u8; 4],   // RGBA 보조 색상
+    pub angle_deg: f32,     // 선형 그라데이션 방향 (0 = 좌→우, 90 = 위→아래)
+    pub cell_size: f32,     // 체커보드 칸 크기 (타임라인 픽셀)
+}
+
+impl GeneratorParams {
+    pub fn new(kind: GeneratorKind) -> Self {
+        let color_b = if kind == GeneratorKind::Countdown {
+            [128, 128, 128, 255]
+        } else {
+            [255, 255, 255, 255]
+        };
+        Self {
+            kind,
+            color_a: [0, 0, 0, 255],
+            color_b,
+            angle_deg: 0.0,
+            cell_size: 64.0,
+        }
+    }
+
+    /// 시간에 따라 프레임이 달라지는지 (false면 1회 생성 후 캐시)
+    pub fn is_animated(&self) -> bool {
+        self.kind == GeneratorKind::Countdown
+    }
+}
+
 /// 비디오 클립
 #[derive(Debug, Clone)]
 pub struct VideoClip {
     pub id: u64,
+    pub clip_type: ClipType,    // Video / Image (정지 이미지) / Generator / Text
     /// 원본 파일 경로 (Export, 오디오용)
     pub file_path: PathBuf,
     /// 프리뷰용 Proxy 경로 (있으면 미리보기/스크럽 시 사용, Export는 원본)
     pub proxy_path: Option<PathBuf>,
+    /// 이미지 시퀀스 프레임레이트 (Some이면 file_path는 "render_%05d.png" 형식 패턴)
+    pub sequence_fps: Option<f64>,
+    /// 제너레이터 파라미터 (ClipType::Generator일 때만 Some, file_path는 비어 있음)
+    pub generator: Option<GeneratorParams>,
+    /// 텍스트 내용 + 스타일 (ClipType::Text일 때만 Some)
+    pub text: Option<Box<TextLayer>>,
     pub start_time_ms: i64,    // 타임라인 상 시작 시간
     pub duration_ms: i64,       // 타임라인 상 지속 시간
     pub trim_start_ms: i64,     // 원본 파일에서 트림 시작
@@ -53,6 +227,11 @@ pub struct VideoClip {
     pub volume: f32,            // 0.0~2.0, 기본 1.0 (비디오 파일 내 오디오 볼륨)
     pub speed: f64,             // 0.25~4.0, 기본 1.0
     pub transition_type: TransitionType,  // incoming 클립 트랜지션 타입
+    pub blend_mode: BlendMode,  // 하위 트랙과의 합성 방식
+    pub opacity: f32,           // 0.0~1.0, 기본 1.0
+    pub fit_mode: FitMode,         // 종횡비가 다른 소스의 캔버스 맞춤 방식
+    pub transform: ClipTransform,  // 캔버스 내 위치/크기/회전/크롭
+    pub keyframes: ClipKeyframes,  // 속성 애니메이션 (있으면 정적 값보다 우선)
 }
 
 impl VideoClip {
@@ -66,8 +245,12 @@ impl VideoClip {
     ) -> Self {
         Self {
             id,
+            clip_type: ClipType::Video,
             file_path,
             proxy_path,
+            sequence_fps: None,
+            generator: None,
+            text: None,
             start_time_ms,
             duration_ms,
             trim_start_ms: 0,
@@ -75,9 +258,49 @@ impl VideoClip {
             volume: 1.0,
             speed: 1.0,
             transition_type: TransitionType::None,
+            blend_mode: BlendMode::Normal,
+            opacity: 1.0,
+            fit_mode: FitMode::default(),
+            transform: ClipTransform::default(),
+            keyframes: ClipKeyframes::new(),
         }
     }
 
+    /// 정지 이미지 클립 생성 (길이 임의, 오디오 없음)
+    pub fn new_image(id: u64, file_path: PathBuf, start_time_ms: i64, duration_ms: i64) -> Self {
+        let mut clip = Self::new(id, file_path, start_time_ms, duration_ms, None);
+        clip.clip_type = ClipType::Image;
+        clip
+    }
+
+    /// 이미지 시퀀스 클립 생성 (pattern: "render_%05d.png", fps: 시퀀스 프레임레이트)
+    pub fn new_sequence(id: u64, pattern: PathBuf, fps: f64, start_time_ms: i64, duration_ms: i64) -> Self {
+        let mut clip = Self::new(id, pattern, start_time_ms, duration_ms, None);
+        clip.sequence_fps = Some(fps);
+        clip
+    }
+
+    /// 제너레이터 클립 생성 (미디어 파일 없음)
+    pub fn new_generator(id: u64, params: GeneratorParams, start_time_ms: i64, duration_ms: i64) -> Self {
+        let mut clip = Self::new(id, PathBuf::new(), start_time_ms, duration_ms, None);
+        clip.clip_type = ClipType::Generator;
+        clip.generator = Some(params);
+        clip
+    }
+
+    /// 텍스트 클립 생성 (타이틀/자막)
+    pub fn new_text(id: u64, layer: TextLayer, start_time_ms: i64, duration_ms: i64) -> Self {
+        let mut clip = Self::new(id, PathBuf::new(), start_time_ms, duration_ms, None);
+        clip.clip_type = ClipType::Text;
+        clip.text = Some(Box::new(layer));
+        clip
+    }
+
+    /// 소스에 오디오 스트림이 있을 수 있는지 (비디오 파일만, 이미지/시퀀스/제너레이터/텍스트는 없음)
+    pub fn has_audio(&self) -> bool {
+        self.clip_type == ClipType::Video && self.sequence_fps.is_none()
+    }
+
     /// 클립의 끝 시간
     pub fn end_time_ms(&self) -> i64 {
         self.start_time_ms + self.duration_ms
@@ -88,6 +311,46 @@ impl VideoClip {
         time_ms >= self.start_time_ms && time_ms < self.end_time_ms()
     }
 
+    /// 타임라인 시간의 트랜스폼 (PositionX/Y, Scale, Rotation 키프레임 우선)
+    /// Scale 키프레임은 가로/세로 모두에 적용 (균등 스케일)
+    pub fn transform_at(&self, timeline_time_ms: i64) -> ClipTransform {
+        let mut t = self.transform;
+        if self.keyframes.is_empty() {
+            return t;
+        }
+
+        let clip_time = (timeline_time_ms - self.start_time_ms) as f64;
+        if let Some(v) = self.keyframes.evaluate(KeyframeProperty::PositionX, clip_time) {
+            t.position_x = v as f32;
+        }
+        if let Some(v) = self.keyframes.evaluate(KeyframeProperty::PositionY, clip_time) {
+            t.position_y = v as f32;
+        }
+        if let Some(v) = self.keyframes.evaluate(KeyframeProperty::Scale, clip_time) {
+            t.scale_x = v as f32;
+            t.scale_y = v as f32;
+        }
+        if let Some(v) = self.keyframes.evaluate(KeyframeProperty::Rotation, clip_time) {
+            t.rotation_deg = v as f32;
+        }
+        t
+    }
+
+    /// 타임라인 시간의 불투명도 (Opacity 키프레임 우선)
+    pub fn opacity_at(&self, timeline_time_ms: i64) -> f32 {
+        let clip_time = (timeline_time_ms - self.start_time_ms) as f64;
+        self.keyframes
+            .evaluate(KeyframeProperty::Opacity, clip_time)
+            .map(|v| v as f32)
+            .unwrap_or(self.opacity)
+            .clamp(0.0, 1.0)
+    }
+
+    /// 일반 합성(Normal + 불투명도 100%)인지 확인 — 단일 레이어 직접 경로 판정용
+    pub fn is_normal_composite(&self, timeline_time_ms: i64) -> bool {
+        self.blend_mode == BlendMode::Normal && self.opacity_at(timeline_time_ms) >= 1.0
+    }
+
     /// 타임라인 시간을 원본 파일 시간으로 변환
     /// speed=2.0이면 타임라인 1초에 원본 2초 재생
     pub fn timeline_to_source_time(&self, timeline_time_ms: i64) -> Option<i64> {
@@ -113,6 +376,7 @@ pub struct AudioClip {
     pub speed: f64,          // 0.25~4.0, 기본 1.0
     pub fade_in_ms: i64,     // 0 = 페이드 없음
     pub fade_out_ms: i64,    // 0 = 페이드 없음
+    pub keyframes: ClipKeyframes,  // Volume 키프레임 (있으면 volume보다 우선)
 }
 
 impl AudioClip {
@@ -129,6 +393,7 @@ impl AudioClip {
             speed: 1.0,
             fade_in_ms: 0,
             fade_out_ms: 0,
+            keyframes: ClipKeyframes::new(),
         }
     }
 
@@ -141,6 +406,16 @@ impl AudioClip {
     pub fn contains_time(&self, time_ms: i64) -> bool {
         time_ms >= self.start_time_ms && time_ms < self.end_time_ms()
     }
+
+    /// 타임라인 시간의 볼륨 (Volume 키프레임 우선, 샘플 단위 보간을 위해 f64 시간)
+    pub fn volume_at(&self, timeline_time_ms: f64) -> f32 {
+        let clip_time = timeline_time_ms - self.start_time_ms as f64;
+        self.keyframes
+            .evaluate(KeyframeProperty::Volume, clip_time)
+            .map(|v| v as f32)
+            .unwrap_or(self.volume)
+            .max(0.0)
+    }
 }
 
 #[cfg(test)]
@@ -167,6 +442,49 @@ mod tests {
         assert!(!clip.contains_time(6000));
     }
 
+    #[test]
+    fn test_blend_mode_from_u32() {
+        assert_eq!(BlendMode::from_u32(0), BlendMode::Normal);
+        assert_eq!(BlendMode::from_u32(3), BlendMode::Overlay);
+        assert_eq!(BlendMode::from_u32(7), BlendMode::Lighten);
+        // 알 수 없는 값은 Normal
+        assert_eq!(BlendMode::from_u32(99), BlendMode::Normal);
+    }
+
+    #[test]
+    fn test_opacity_keyframes_are_clip_relative() {
+        use crate::timeline::keyframe::{Keyframe, InterpolationType};
+
+        let mut clip = VideoClip::new(1, PathBuf::from("test.mp4"), 2000, 3000, None);
+        assert_eq!(clip.opacity_at(2500), 1.0);
+        assert!(clip.is_normal_composite(2500));
+
+        clip.keyframes.add(KeyframeProperty::Opacity, Keyframe::new(0.0, 0.0, InterpolationType::Linear));
+        clip.keyframes.add(KeyframeProperty::Opacity, Keyframe::new(1000.0, 1.0, InterpolationType::Linear));
+        // 타임라인 2500ms = 클립 상대 500ms
+        assert_eq!(clip.opacity_at(2500), 0.5);
+        assert!(!clip.is_normal_composite(2500));
+        assert!(clip.is_normal_composite(4000));
+    }
+
+    #[test]
+    fn test_transform_keyframes_override_static() {
+        use crate::timeline::keyframe::{Keyframe, InterpolationType};
+
+        let mut clip = VideoClip::new(1, PathBuf::from("test.mp4"), 1000, 3000, None);
+        assert!(clip.transform_at(1500).is_identity());
+
+        clip.transform.position_x = 100.0;
+        clip.keyframes.add(KeyframeProperty::Scale, Keyframe::new(0.0, 1.0, InterpolationType::Linear));
+        clip.keyframes.add(KeyframeProperty::Scale, Keyframe::new(1000.0, 2.0, InterpolationType::Linear));
+
+        let t = clip.transform_at(1500);
+        assert_eq!(t.position_x, 100.0);
+        assert_eq!(t.scale_x, 1.5);
+        assert_eq!(t.scale_y, 1.5);
+        assert!(!t.is_identity());
+    }
+
     #[test]
     fn test_timeline_to_source_time() {
         let mut clip = VideoClip::new(1, PathBuf::from("test.mp4"), 2000, 3000, None);
@@ -0,0 +1,289 @@
+// 키프레임 모듈 - 클립 속성 애니메이션 (C# KeyframeModel.cs와 동일한 보간)
+// 키프레임 시간은 클립 시작 기준 상대 시간 (ms)
+
+use std::collections::HashMap;
+
+/// 키프레임 보간 타입 (C# InterpolationType과 동일 순서)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum InterpolationType {
+    Linear = 0,     // 선형 보간
+    Bezier = 1,     // 베지어 곡선 (Direction Handles 사용)
+    EaseIn = 2,     // 가속
+    EaseOut = 3,    // 감속
+    EaseInOut = 4,  // 가속+감속
+    Hold = 5,       // 계단식 (다음 키프레임까지 값 유지)
+}
+
+impl InterpolationType {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Linear,
+            1 => Self::Bezier,
+            2 => Self::EaseIn,
+            3 => Self::EaseOut,
+            4 => Self::EaseInOut,
+            5 => Self::Hold,
+            _ => Self::Linear,
+        }
+    }
+}
+
+/// 애니메이션 가능한 클립 속성
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum KeyframeProperty {
+    Opacity = 0,      // 0.0~1.0
+    Volume = 1,       // 0.0~2.0
+    PositionX = 2,    // 픽셀 (타임라인 해상도 기준, 중앙=0)
+    PositionY = 3,
+    Scale = 4,        // 1.0 = 원본 크기
+    Rotation = 5,     // 도(degree), 시계 방향
+    Brightness = 6,   // -1.0~1.0 (EffectParams와 동일 범위)
+    Contrast = 7,
+    Saturation = 8,
+    Temperature = 9,
+}
+
+impl KeyframeProperty {
+    /// 알 수 없는 값이면 None (FFI 입력 검증용)
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Opacity),
+            1 => Some(Self::Volume),
+            2 => Some(Self::PositionX),
+            3 => Some(Self::PositionY),
+            4 => Some(Self::Scale),
+            5 => Some(Self::Rotation),
+            6 => Some(Self::Brightness),
+            7 => Some(Self::Contrast),
+            8 => Some(Self::Saturation),
+            9 => Some(Self::Temperature),
+            _ => None,
+        }
+    }
+}
+
+/// 단일 키프레임
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    pub time_ms: f64,   // 클립 시작 기준 상대 시간
+    pub value: f64,
+    pub interpolation: InterpolationType,
+    /// 베지어 진입 핸들 값 오프셋 (None이면 자동 2/3 지점)
+    pub in_handle: Option<f64>,
+    /// 베지어 진출 핸들 값 오프셋 (None이면 자동 1/3 지점)
+    pub out_handle: Option<f64>,
+}
+
+impl Keyframe {
+    pub fn new(time_ms: f64, value: f64, interpolation: InterpolationType) -> Self {
+        Self {
+            time_ms,
+            value,
+            interpolation,
+            in_handle: None,
+            out_handle: None,
+        }
+    }
+}
+
+/// 속성 1개의 키프레임 목록 (시간순 정렬 유지)
+#[derive(Debug, Clone, Default)]
+pub struct KeyframeTrack {
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl KeyframeTrack {
+    pub fn new() -> Self {
+        Self { keyframes: Vec::new() }
+    }
+
+    /// 키프레임 추가 (같은 시간이 있으면 교체)
+    pub fn add(&mut self, keyframe: Keyframe) {
+        if let Some(existing) = self.keyframes.iter_mut().find(|k| k.time_ms == keyframe.time_ms) {
+            *existing = keyframe;
+            return;
+        }
+        self.keyframes.push(keyframe);
+        self.keyframes.sort_by(|a, b| a.time_ms.total_cmp(&b.time_ms));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    /// 주어진 시간의 보간 값 (키프레임 없으면 None)
+    /// 범위 밖이면 첫/마지막 키프레임 값 유지
+    pub fn evaluate(&self, time_ms: f64) -> Option<f64> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+
+        if time_ms <= first.time_ms {
+            return Some(first.value);
+        }
+        if time_ms >= last.time_ms {
+            return Some(last.value);
+        }
+
+        // 구간 탐색: time_ms < kf2.time_ms 인 첫 구간
+        let i = self.keyframes.partition_point(|k| k.time_ms <= time_ms);
+        let kf1 = &self.keyframes[i - 1];
+        let kf2 = &self.keyframes[i];
+        let span = kf2.time_ms - kf1.time_ms;
+        if span <= 0.0 {
+            return Some(kf2.value);
+        }
+        let t = (time_ms - kf1.time_ms) / span;
+
+        Some(interpolate(kf1, kf2, t))
+    }
+}
+
+/// 두 키프레임 사이 보간 (kf1의 보간 타입 사용)
+fn interpolate(kf1: &Keyframe, kf2: &Keyframe, t: f64) -> f64 {
+    let v1 = kf1.value;
+    let v2 = kf2.value;
+
+    match kf1.interpolation {
+        InterpolationType::Linear => v1 + t * (v2 - v1),
+        InterpolationType::EaseIn => v1 + t * t * (v2 - v1),
+        InterpolationType::EaseOut => v1 + (1.0 - (1.0 - t) * (1.0 - t)) * (v2 - v1),
+        InterpolationType::EaseInOut => {
+            let eased = if t < 0.5 {
+                2.0 * t * t
+            } else {
+                1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+            };
+            v1 + eased * (v2 - v1)
+        }
+        InterpolationType::Hold => v1,
+        InterpolationType::Bezier => {
+            // Cubic Bezier: P0 (kf1) → P1 (OutHandle) → P2 (InHandle) → P3 (kf2)
+            let p0 = v1;
+            let p3 = v2;
+            let p1 = kf1.out_handle.map(|o| v1 + o).unwrap_or(p0 + (p3 - p0) / 3.0);
+            let p2 = kf2.in_handle.map(|o| v2 + o).unwrap_or(p3 - (p3 - p0) / 3.0);
+            let u = 1.0 - t;
+            u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+        }
+    }
+}
+
+/// 클립 1개의 전체 키프레임 (속성별 트랙)
+#[derive(Debug, Clone, Default)]
+pub struct ClipKeyframes {
+    tracks: HashMap<KeyframeProperty, KeyframeTrack>,
+}
+
+impl ClipKeyframes {
+    pub fn new() -> Self {
+        Self { tracks: HashMap::new() }
+    }
+
+    /// 키프레임 추가
+    pub fn add(&mut self, property: KeyframeProperty, keyframe: Keyframe) {
+        self.tracks.entry(property).or_default().add(keyframe);
+    }
+
+    /// 특정 속성의 키프레임 전체 제거
+    pub fn clear(&mut self, property: KeyframeProperty) {
+        self.tracks.remove(&property);
+    }
+
+    /// 모든 속성의 키프레임 제거
+    pub fn clear_all(&mut self) {
+        self.tracks.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tracks.values().all(|t| t.is_empty())
+    }
+
+    /// 속성에 키프레임이 있는지 확인
+    pub fn has(&self, property: KeyframeProperty) -> bool {
+        self.tracks.get(&property).is_some_and(|t| !t.is_empty())
+    }
+
+    /// 클립 상대 시간의 보간 값 (해당 속성 키프레임 없으면 None → 정적 값 사용)
+    pub fn evaluate(&self, property: KeyframeProperty, clip_time_ms: f64) -> Option<f64> {
+        self.tracks.get(&property)?.evaluate(clip_time_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(interp: InterpolationType) -> KeyframeTrack {
+        let mut t = KeyframeTrack::new();
+        t.add(Keyframe::new(0.0, 0.0, interp));
+        t.add(Keyframe::new(1000.0, 100.0, interp));
+        t
+    }
+
+    #[test]
+    fn test_empty_track() {
+        assert_eq!(KeyframeTrack::new().evaluate(500.0), None);
+    }
+
+    #[test]
+    fn test_clamp_outside_range() {
+        let t = track(InterpolationType::Linear);
+        assert_eq!(t.evaluate(-100.0), Some(0.0));
+        assert_eq!(t.evaluate(5000.0), Some(100.0));
+    }
+
+    #[test]
+    fn test_interpolation_types() {
+        assert_eq!(track(InterpolationType::Linear).evaluate(250.0), Some(25.0));
+        assert_eq!(track(InterpolationType::EaseIn).evaluate(500.0), Some(25.0));
+        assert_eq!(track(InterpolationType::EaseOut).evaluate(500.0), Some(75.0));
+        assert_eq!(track(InterpolationType::EaseInOut).evaluate(250.0), Some(12.5));
+        assert_eq!(track(InterpolationType::EaseInOut).evaluate(750.0), Some(87.5));
+        assert_eq!(track(InterpolationType::Hold).evaluate(999.0), Some(0.0));
+
+        // 핸들 없는 베지어 = 1/3, 2/3 제어점 → 선형과 동일
+        let v = track(InterpolationType::Bezier).evaluate(250.0).unwrap();
+        assert!((v - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_bezier_handles() {
+        let mut t = KeyframeTrack::new();
+        let mut k1 = Keyframe::new(0.0, 0.0, InterpolationType::Bezier);
+        k1.out_handle = Some(100.0);
+        let mut k2 = Keyframe::new(1000.0, 100.0, InterpolationType::Linear);
+        k2.in_handle = Some(0.0);
+        t.add(k1);
+        t.add(k2);
+        // P0=0, P1=100, P2=100, P3=100 → t=0.5: 0.375*100 + 0.375*100 + 0.125*100 = 87.5
+        assert!((t.evaluate(500.0).unwrap() - 87.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_add_keeps_sorted_and_replaces() {
+        let mut t = KeyframeTrack::new();
+        t.add(Keyframe::new(1000.0, 1.0, InterpolationType::Linear));
+        t.add(Keyframe::new(0.0, 0.0, InterpolationType::Linear));
+        t.add(Keyframe::new(1000.0, 2.0, InterpolationType::Linear));
+        assert_eq!(t.keyframes.len(), 2);
+        assert_eq!(t.keyframes[0].time_ms, 0.0);
+        assert_eq!(t.keyframes[1].value, 2.0);
+    }
+
+    #[test]
+    fn test_clip_keyframes() {
+        let mut kf = ClipKeyframes::new();
+        assert!(kf.is_empty());
+        kf.add(KeyframeProperty::Opacity, Keyframe::new(0.0, 1.0, InterpolationType::Linear));
+        kf.add(KeyframeProperty::Opacity, Keyframe::new(1000.0, 0.0, InterpolationType::Linear));
+        assert!(kf.has(KeyframeProperty::Opacity));
+        assert!(!kf.has(KeyframeProperty::Volume));
+        assert_eq!(kf.evaluate(KeyframeProperty::Opacity, 500.0), Some(0.5));
+        assert_eq!(kf.evaluate(KeyframeProperty::Volume, 500.0), None);
+
+        kf.clear(KeyframeProperty::Opacity);
+        assert!(kf.is_empty());
+    }
+}
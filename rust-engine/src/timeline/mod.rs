@@ -2,9 +2,11 @@
 // 클립, 트랙, 타임라인 관리
 
 pub mod clip;
+pub mod keyframe;
 pub mod track;
 pub mod timeline;
 
-pub use clip::{ClipType, VideoClip, AudioClip, TransitionType};
+pub use clip::{ClipType, VideoClip, AudioClip, TransitionType, BlendMode, ClipTransform, FitMode, GeneratorKind, GeneratorParams};
+pub use keyframe::{ClipKeyframes, Keyframe, KeyframeProperty, KeyframeTrack, InterpolationType};
 pub use track::{VideoTrack, AudioTrack};
 pub use timeline::Timeline;
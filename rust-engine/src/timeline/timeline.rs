@@ -1,7 +1,11 @@
 // 타임라인 모듈 - 전체 프로젝트의 타임라인 관리
 
 use super::track::{VideoTrack, AudioTrack};
-use super::clip::{VideoClip, AudioClip};
+use super::clip::{VideoClip, AudioClip, GeneratorParams};
+use crate::subtitle::text::TextLayer;
+use crate::subtitle::overlay::SubtitleOverlayList;
+use super::keyframe::ClipKeyframes;
+use std::sync::Arc;
 
 /// 타임라인 - 비디오 편집 프로젝트의 핵심
 #[derive(Debug, Clone)]
@@ -11,6 +15,8 @@ pub struct Timeline {
     pub fps: f64,
     pub video_tracks: Vec<VideoTrack>,
     pub audio_tracks: Vec<AudioTrack>,
+    /// 번인 자막 오버레이 (프리뷰/Export 렌더러가 공유, None이면 자막 없음)
+    pub subtitles: Option<Arc<SubtitleOverlayList>>,
     next_clip_id: u64,
     next_track_id: u64,
 }
@@ -24,6 +30,7 @@ impl Timeline {
             fps,
             video_tracks: Vec::new(),
             audio_tracks: Vec::new(),
+            subtitles: None,
             next_clip_id: 1,
             next_track_id: 1,
         }
@@ -71,6 +78,112 @@ impl Timeline {
         Some(clip_id)
     }
 
+    /// 이미지 클립 추가 (PNG/JPEG/WebP 정지 이미지)
+    pub fn add_image_clip(
+        &mut self,
+        track_id: u64,
+        file_path: std::path::PathBuf,
+        start_time_ms: i64,
+        duration_ms: i64,
+    ) -> Option<u64> {
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id)?;
+
+        let clip_id = self.next_clip_id;
+        self.next_clip_id += 1;
+
+        track.add_clip(VideoClip::new_image(clip_id, file_path, start_time_ms, duration_ms));
+
+        Some(clip_id)
+    }
+
+    /// 이미지 시퀀스 클립 추가 (pattern: "render_%05d.png", fps: 시퀀스 프레임레이트)
+    pub fn add_sequence_clip(
+        &mut self,
+        track_id: u64,
+        pattern: std::path::PathBuf,
+        fps: f64,
+        start_time_ms: i64,
+        duration_ms: i64,
+    ) -> Option<u64> {
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id)?;
+
+        let clip_id = self.next_clip_id;
+        self.next_clip_id += 1;
+
+        track.add_clip(VideoClip::new_sequence(clip_id, pattern, fps, start_time_ms, duration_ms));
+
+        Some(clip_id)
+    }
+
+    /// 제너레이터 클립 추가 (단색/그라데이션/테스트 패턴, 미디어 파일 없음)
+    pub fn add_generator_clip(
+        &mut self,
+        track_id: u64,
+        params: GeneratorParams,
+        start_time_ms: i64,
+        duration_ms: i64,
+    ) -> Option<u64> {
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id)?;
+
+        let clip_id = self.next_clip_id;
+        self.next_clip_id += 1;
+
+        track.add_clip(VideoClip::new_generator(clip_id, params, start_time_ms, duration_ms));
+
+        Some(clip_id)
+    }
+
+    /// 제너레이터 파라미터 변경 (제너레이터 클립이 아니면 false)
+    pub fn set_generator_params(&mut self, clip_id: u64, params: GeneratorParams) -> bool {
+        for track in &mut self.video_tracks {
+            if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+                if clip.generator.is_none() {
+                    return false;
+                }
+                clip.generator = Some(params);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// 텍스트 클립 추가 (타이틀/자막, 엔진에서 렌더링)
+    pub fn add_text_clip(
+        &mut self,
+        track_id: u64,
+        layer: TextLayer,
+        start_time_ms: i64,
+        duration_ms: i64,
+    ) -> Option<u64> {
+        let track = self.video_tracks.iter_mut().find(|t| t.id == track_id)?;
+
+        let clip_id = self.next_clip_id;
+        self.next_clip_id += 1;
+
+        track.add_clip(VideoClip::new_text(clip_id, layer, start_time_ms, duration_ms));
+
+        Some(clip_id)
+    }
+
+    /// 텍스트 클립 내용/스타일 변경 (텍스트 클립이 아니면 false)
+    pub fn set_text_layer(&mut self, clip_id: u64, layer: TextLayer) -> bool {
+        for track in &mut self.video_tracks {
+            if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+                if clip.text.is_none() {
+                    return false;
+                }
+                clip.text = Some(Box::new(layer));
+                return true;
+            }
+        }
+        false
+    }
+
+    /// 번인 자막 오버레이 목록 교체 (None이면 제거)
+    pub fn set_subtitles(&mut self, subtitles: Option<SubtitleOverlayList>) {
+        self.subtitles = subtitles.map(Arc::new);
+    }
+
     /// 오디오 클립 추가
     pub fn add_audio_clip(
         &mut self,
@@ -140,6 +253,21 @@ impl Timeline {
         false
     }
 
+    /// 클립 ID로 키프레임 찾기 (비디오 트랙 + 오디오 트랙 모두 검색)
+    pub fn get_clip_keyframes_mut(&mut self, clip_id: u64) -> Option<&mut ClipKeyframes> {
+        for track in &mut self.video_tracks {
+            if let Some(clip) = track.get_clip_by_id_mut(clip_id) {
+                return Some(&mut clip.keyframes);
+            }
+        }
+        for track in &mut self.audio_tracks {
+            if let Some(clip) = track.clips.iter_mut().find(|c| c.id == clip_id) {
+                return Some(&mut clip.keyframes);
+            }
+        }
+        None
+    }
+
     /// 특정 시간에 활성화된 비디오 클립들 찾기 (모든 트랙)
     pub fn get_video_clips_at_time(&self, time_ms: i64) -> Vec<(&VideoTrack, &VideoClip)> {
         let mut clips = Vec::new();
@@ -176,6 +304,10 @@ impl Timeline {
 
         // 비디오 트랙의 클립 → AudioClip으로 변환 (비디오 파일의 오디오 스트림 추출)
         for (_, video_clip) in self.get_video_clips_at_time(time_ms) {
+            // 이미지/이미지 시퀀스는 오디오 스트림 없음
+            if !video_clip.has_audio() {
+                continue;
+            }
             sources.push(AudioClip {
                 id: video_clip.id,
                 file_path: video_clip.file_path.clone(),
@@ -187,6 +319,7 @@ impl Timeline {
                 speed: video_clip.speed,
                 fade_in_ms: 0,
                 fade_out_ms: 0,
+                keyframes: video_clip.keyframes.clone(),
             });
         }
 
@@ -197,6 +330,7 @@ impl Timeline {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::timeline::ClipType;
     use std::path::PathBuf;
 
     #[test]
@@ -240,6 +374,75 @@ mod tests {
         assert_eq!(timeline.video_tracks[0].clips[0].id, clip_id.unwrap());
     }
 
+    #[test]
+    fn test_add_image_clip_has_no_audio() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        timeline.add_video_clip(track_id, PathBuf::from("test.mp4"), 0, 5000, None);
+        let image_id = timeline.add_image_clip(track_id, PathBuf::from("logo.png"), 5000, 3000).unwrap();
+
+        let clip = &timeline.video_tracks[0].clips[1];
+        assert_eq!(clip.id, image_id);
+        assert_eq!(clip.clip_type, ClipType::Image);
+
+        assert_eq!(timeline.get_all_audio_sources_at_time(1000).len(), 1);
+        assert_eq!(timeline.get_all_audio_sources_at_time(6000).len(), 0);
+    }
+
+    #[test]
+    fn test_add_sequence_clip_has_no_audio() {
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        timeline.add_sequence_clip(track_id, PathBuf::from("render_%05d.png"), 24.0, 0, 2000).unwrap();
+
+        let clip = &timeline.video_tracks[0].clips[0];
+        assert_eq!(clip.sequence_fps, Some(24.0));
+        assert_eq!(clip.clip_type, ClipType::Video);
+        assert!(!clip.has_audio());
+        assert!(timeline.get_all_audio_sources_at_time(1000).is_empty());
+    }
+
+    #[test]
+    fn test_generator_clip() {
+        use crate::timeline::GeneratorKind;
+
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        let video_id = timeline.add_video_clip(track_id, PathBuf::from("test.mp4"), 0, 1000, None).unwrap();
+        let gen_id = timeline
+            .add_generator_clip(track_id, GeneratorParams::new(GeneratorKind::SolidColor), 1000, 2000)
+            .unwrap();
+
+        assert_eq!(timeline.video_tracks[0].clips[1].clip_type, ClipType::Generator);
+        assert!(timeline.get_all_audio_sources_at_time(1500).is_empty());
+
+        let bars = GeneratorParams::new(GeneratorKind::ColorBars);
+        assert!(timeline.set_generator_params(gen_id, bars));
+        assert_eq!(timeline.video_tracks[0].clips[1].generator, Some(bars));
+        // 일반 비디오 클립은 변경 불가
+        assert!(!timeline.set_generator_params(video_id, bars));
+    }
+
+    #[test]
+    fn test_text_clip() {
+        use crate::subtitle::text::TextStyle;
+
+        let mut timeline = Timeline::new(1920, 1080, 30.0);
+        let track_id = timeline.add_video_track();
+        let layer = TextLayer {
+            text: "Title".to_string(),
+            style: TextStyle::new(PathBuf::from("font.ttf"), 64.0),
+        };
+        let clip_id = timeline.add_text_clip(track_id, layer.clone(), 0, 3000).unwrap();
+
+        assert_eq!(timeline.video_tracks[0].clips[0].clip_type, ClipType::Text);
+        assert!(timeline.get_all_audio_sources_at_time(1000).is_empty());
+
+        let updated = TextLayer { text: "New title".to_string(), ..layer };
+        assert!(timeline.set_text_layer(clip_id, updated));
+        assert_eq!(timeline.video_tracks[0].clips[0].text.as_ref().unwrap().text, "New title");
+    }
+
     #[test]
     fn test_remove_video_clip() {
         let mut timeline = Timeline::new(1920, 1080, 30.0);
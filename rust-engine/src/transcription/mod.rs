@@ -2,6 +2,7 @@
 // whisper-rs 바인딩을 통한 자동 자막 생성
 // Export 패턴(AtomicU32 progress, AtomicBool finished) 동일하게 적용
 
+use crate::subtitle::cue::SubtitleCue;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
 
@@ -12,6 +13,13 @@ pub struct TranscriptSegment {
     pub text: String,
 }
 
+impl TranscriptSegment {
+    /// 자막 cue로 변환 (whisper 텍스트 앞뒤 공백 제거)
+    pub fn to_cue(&self) -> SubtitleCue {
+        SubtitleCue::new(self.start_ms, self.end_ms, self.text.trim())
+    }
+}
+
 /// 트랜스크립션 작업 핸들 (C#에서 폴링으로 상태 확인)
 pub struct TranscriberJob {
     /// 진행률 0~100